@@ -67,7 +67,7 @@
 //! Clawspec supports several authentication methods:
 //!
 //! ```rust,no_run
-//! use clawspec_core::{ApiClient, Authentication};
+//! use clawspec_core::{ApiClient, ApiKeyLocation, Authentication};
 //!
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! // Bearer token (most common for APIs)
@@ -93,6 +93,16 @@
 //!         key: "your-api-key".into(),
 //!     })
 //!     .build()?;
+//!
+//! // API key in query string, or in a cookie
+//! let client = ApiClient::builder()
+//!     .with_host("api.example.com")
+//!     .with_authentication(Authentication::api_key(
+//!         "api_key",
+//!         "your-api-key",
+//!         ApiKeyLocation::Query,
+//!     ))
+//!     .build()?;
 //! # Ok(())
 //! # }
 //! ```
@@ -1,12 +1,18 @@
 use std::fmt;
+use std::sync::Arc;
 
 use http::HeaderValue;
-use reqwest::header::{AUTHORIZATION, HeaderName};
+use reqwest::header::{AUTHORIZATION, COOKIE, HeaderName};
 use serde::{Deserialize, Serialize};
+use url::Url;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
+#[cfg(feature = "jwt")]
+use super::jwt::JwtBuilder;
 #[cfg(feature = "oauth2")]
-use super::oauth2::SharedOAuth2Config;
+use super::oauth2::{OAUTH2_SCHEME_NAME, SharedOAuth2Config};
+use super::security::{ApiKeyLocation, SecurityRequirement, SecurityScheme};
+use super::signing::RequestSigner;
 
 /// Errors that can occur during authentication processing.
 ///
@@ -51,6 +57,44 @@ pub enum AuthenticationError {
         message: String,
     },
 
+    /// API key query parameter or cookie name is invalid.
+    #[display("Invalid API key name '{name}': {message}")]
+    InvalidApiKeyName {
+        /// The invalid name that was provided.
+        name: String,
+        /// Description of why the name is invalid.
+        message: String,
+    },
+
+    /// Request signing failed.
+    #[display("Request signing failed: {message}")]
+    SigningFailed {
+        /// Description of the signing failure.
+        message: String,
+    },
+
+    /// Digest authentication challenge is invalid or unsupported.
+    #[display("Invalid Digest authentication challenge: {message}")]
+    InvalidDigestChallenge {
+        /// Description of the invalid or unsupported challenge.
+        message: String,
+    },
+
+    /// Digest authentication has no authentication header before the challenge.
+    #[display("Digest authentication has no authentication header before the server challenge")]
+    DigestNotInHeader,
+
+    /// Signed requests have no authentication header before being signed.
+    #[display("Signed requests have no authentication header before being signed")]
+    SignatureNotInHeader,
+
+    /// API key is sent in the query string, not in a header.
+    #[display("API key '{name}' is sent in the query string, not in a header")]
+    ApiKeyNotInHeader {
+        /// The query parameter name of the API key.
+        name: String,
+    },
+
     /// Base64 encoding failed during Basic authentication processing.
     #[display("Base64 encoding failed: {message}")]
     EncodingError {
@@ -70,6 +114,14 @@ pub enum AuthenticationError {
         /// Description of the OAuth2 error.
         message: String,
     },
+
+    /// JSON Web Token error.
+    #[cfg(feature = "jwt")]
+    #[display("JWT error: {message}")]
+    JwtError {
+        /// Description of the JWT error.
+        message: String,
+    },
 }
 
 /// Secure wrapper for sensitive string data that automatically zeroes memory on drop.
@@ -193,11 +245,22 @@ impl SecureString {
 ///     password: "pass".into(),
 /// };
 ///
+/// // Digest authentication
+/// let auth = Authentication::Digest {
+///     username: "user".to_string(),
+///     password: "pass".into(),
+/// };
+///
 /// // API key in header
 /// let auth = Authentication::ApiKey {
 ///     header_name: "X-API-Key".to_string(),
 ///     key: "secret-key".into(),
 /// };
+///
+/// // API key in query string, or in a cookie
+/// use clawspec_core::ApiKeyLocation;
+/// let auth = Authentication::api_key("api_key", "secret-key", ApiKeyLocation::Query);
+/// let auth = Authentication::api_key("session", "secret-key", ApiKeyLocation::Cookie);
 /// ```
 // Serialize/Deserialize can only be derived when oauth2 feature is not enabled
 // because SharedOAuth2Config doesn't implement these traits
@@ -221,6 +284,18 @@ pub enum Authentication {
         password: SecureString,
     },
 
+    /// HTTP Digest authentication (RFC 7616).
+    ///
+    /// The request is sent without credentials, and sent again with an
+    /// `Authorization: Digest ...` header answering the `401 Unauthorized` challenge.
+    /// The `MD5` and `SHA-256` algorithms are supported, with their session variants.
+    Digest {
+        /// The username for Digest authentication.
+        username: String,
+        /// The password for Digest authentication.
+        password: SecureString,
+    },
+
     /// API key authentication with custom header.
     /// Adds `<header_name>: <key>` header.
     ApiKey {
@@ -230,6 +305,39 @@ pub enum Authentication {
         key: SecureString,
     },
 
+    /// API key authentication with a query parameter.
+    /// Adds `<name>=<key>` to the query string.
+    ApiKeyQuery {
+        /// The query parameter name for the API key.
+        name: String,
+        /// The API key value.
+        key: SecureString,
+    },
+
+    /// API key authentication with a cookie.
+    /// Adds the `<name>=<key>` cookie.
+    ApiKeyCookie {
+        /// The cookie name for the API key.
+        name: String,
+        /// The API key value.
+        key: SecureString,
+    },
+
+    /// Signed requests.
+    ///
+    /// The signer is called with the complete request, usually adding headers.
+    /// This variant cannot be serialized.
+    #[cfg_attr(not(feature = "oauth2"), serde(skip))]
+    Signed(Arc<dyn RequestSigner>),
+
+    /// JSON Web Token authentication, with the tokens minted locally.
+    ///
+    /// This variant requires the `jwt` feature to be enabled.
+    /// Adds `Authorization: Bearer <token>` header, the token is minted again once expired.
+    #[cfg(feature = "jwt")]
+    #[cfg_attr(not(feature = "oauth2"), serde(skip))]
+    Jwt(Arc<JwtBuilder>),
+
     /// OAuth2 authentication.
     ///
     /// This variant requires the `oauth2` feature to be enabled.
@@ -238,26 +346,111 @@ pub enum Authentication {
     OAuth2(SharedOAuth2Config),
 }
 
+/// Where the credentials of an [`Authentication`] are sent.
+pub(crate) enum Credentials<'a> {
+    /// In a header.
+    Header(HeaderName, HeaderValue),
+    /// In a query parameter.
+    Query {
+        name: &'a str,
+        key: &'a SecureString,
+    },
+    /// In a cookie, as a `<name>=<key>` pair.
+    Cookie(String),
+    /// In the answer to the Digest challenge of the server, once challenged.
+    Digest,
+    /// In the signature of the complete request.
+    Signed(&'a dyn RequestSigner),
+}
+
 impl Authentication {
+    /// Creates an API key authentication, sent at the given location.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use clawspec_core::{ApiKeyLocation, Authentication};
+    ///
+    /// let auth = Authentication::api_key("api_key", "secret-key", ApiKeyLocation::Query);
+    /// assert!(matches!(auth, Authentication::ApiKeyQuery { .. }));
+    /// ```
+    pub fn api_key(
+        name: impl Into<String>,
+        key: impl Into<SecureString>,
+        location: ApiKeyLocation,
+    ) -> Self {
+        let name = name.into();
+        let key = key.into();
+        match location {
+            ApiKeyLocation::Header => Self::ApiKey {
+                header_name: name,
+                key,
+            },
+            ApiKeyLocation::Query => Self::ApiKeyQuery { name, key },
+            ApiKeyLocation::Cookie => Self::ApiKeyCookie { name, key },
+        }
+    }
+
+    /// Creates a signed requests authentication.
+    ///
+    /// See [`SigV4Signer`](super::SigV4Signer) and [`HmacSigner`](super::HmacSigner)
+    /// for the built-in signers.
+    pub fn signed(signer: impl RequestSigner + 'static) -> Self {
+        Self::Signed(Arc::new(signer))
+    }
+
+    /// Creates a JSON Web Token authentication, minting the tokens with the builder.
+    ///
+    /// The operations are documented with a `bearer` security scheme, with the `JWT` format.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use clawspec_core::{Authentication, JwtBuilder};
+    ///
+    /// let auth = Authentication::jwt(JwtBuilder::hs256("test-secret").with_subject("user-123"));
+    /// assert_eq!(auth.to_string(), "Jwt (HS256)");
+    /// ```
+    #[cfg(feature = "jwt")]
+    pub fn jwt(builder: JwtBuilder) -> Self {
+        Self::Jwt(Arc::new(builder))
+    }
+
     /// Converts the authentication into HTTP headers.
     ///
     /// Returns a tuple of (HeaderName, HeaderValue) that can be added to the request.
+    /// The value is marked as sensitive, so it is not displayed by debug logs.
+    /// An API key cookie is returned as a `Cookie` header.
     ///
     /// # Errors
     ///
     /// Returns `AuthenticationError` if the authentication data contains invalid characters
-    /// or cannot be properly formatted for HTTP headers.
+    /// or cannot be properly formatted for HTTP headers, if the API key is sent in the
+    /// query string, if the requests are signed, or if the credentials answer a Digest challenge.
     pub fn to_header(&self) -> Result<(HeaderName, HeaderValue), AuthenticationError> {
-        match self {
-            Authentication::Bearer(token) => {
-                let header_value = format!("Bearer {}", token.as_str());
-                let value = HeaderValue::from_str(&header_value).map_err(|e| {
-                    AuthenticationError::InvalidBearerToken {
+        match self.credentials()? {
+            Credentials::Header(name, value) => Ok((name, value)),
+            Credentials::Query { name, .. } => Err(AuthenticationError::ApiKeyNotInHeader {
+                name: name.to_string(),
+            }),
+            Credentials::Signed(_) => Err(AuthenticationError::SignatureNotInHeader),
+            Credentials::Digest => Err(AuthenticationError::DigestNotInHeader),
+            Credentials::Cookie(cookie) => {
+                let mut value = HeaderValue::from_str(&cookie).map_err(|e| {
+                    AuthenticationError::InvalidApiKey {
                         message: e.to_string(),
                     }
                 })?;
-                Ok((AUTHORIZATION, value))
+                value.set_sensitive(true);
+                Ok((COOKIE, value))
             }
+        }
+    }
+
+    /// Validates the authentication, and returns where its credentials are sent.
+    pub(crate) fn credentials(&self) -> Result<Credentials<'_>, AuthenticationError> {
+        match self {
+            Authentication::Bearer(token) => bearer_credentials(token),
 
             Authentication::Basic { username, password } => {
                 // Validate username doesn't contain invalid characters
@@ -272,14 +465,17 @@ impl Authentication {
                 let credentials = base64::engine::general_purpose::STANDARD.encode(credentials_str);
 
                 let header_value = format!("Basic {credentials}");
-                let value = HeaderValue::from_str(&header_value).map_err(|e| {
+                let mut value = HeaderValue::from_str(&header_value).map_err(|e| {
                     AuthenticationError::InvalidPassword {
                         message: e.to_string(),
                     }
                 })?;
-                Ok((AUTHORIZATION, value))
+                value.set_sensitive(true);
+                Ok(Credentials::Header(AUTHORIZATION, value))
             }
 
+            Authentication::Digest { .. } => Ok(Credentials::Digest),
+
             Authentication::ApiKey { header_name, key } => {
                 let header = HeaderName::from_bytes(header_name.as_bytes()).map_err(|e| {
                     AuthenticationError::InvalidHeaderName {
@@ -287,14 +483,47 @@ impl Authentication {
                         message: e.to_string(),
                     }
                 })?;
-                let value = HeaderValue::from_str(key.as_str()).map_err(|e| {
+                let mut value = HeaderValue::from_str(key.as_str()).map_err(|e| {
                     AuthenticationError::InvalidApiKey {
                         message: e.to_string(),
                     }
                 })?;
-                Ok((header, value))
+                value.set_sensitive(true);
+                Ok(Credentials::Header(header, value))
+            }
+
+            Authentication::ApiKeyQuery { name, key } => {
+                if name.is_empty() {
+                    return Err(AuthenticationError::InvalidApiKeyName {
+                        name: name.clone(),
+                        message: "Query parameter name cannot be empty".to_string(),
+                    });
+                }
+                Ok(Credentials::Query { name, key })
+            }
+
+            Authentication::ApiKeyCookie { name, key } => {
+                // Cookie names are tokens, like header names
+                if HeaderName::from_bytes(name.as_bytes()).is_err() {
+                    return Err(AuthenticationError::InvalidApiKeyName {
+                        name: name.clone(),
+                        message: "Cookie name must be a token".to_string(),
+                    });
+                }
+                if !key.as_str().bytes().all(is_cookie_octet) {
+                    return Err(AuthenticationError::InvalidApiKey {
+                        message: "Cookie value cannot contain whitespace, '\"', ',', ';' or '\\'"
+                            .to_string(),
+                    });
+                }
+                Ok(Credentials::Cookie(format!("{name}={}", key.as_str())))
             }
 
+            Authentication::Signed(signer) => Ok(Credentials::Signed(signer.as_ref())),
+
+            #[cfg(feature = "jwt")]
+            Authentication::Jwt(builder) => bearer_credentials(&builder.token()?),
+
             #[cfg(feature = "oauth2")]
             Authentication::OAuth2(_) => {
                 // OAuth2 authentication requires async token acquisition
@@ -303,6 +532,120 @@ impl Authentication {
             }
         }
     }
+
+    /// Masks the API key sent in the query string of the URL, for logging.
+    pub(crate) fn mask_url(&self, url: &Url) -> Url {
+        let Self::ApiKeyQuery { name, key } = self else {
+            return url.clone();
+        };
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(param, value)| {
+                let value = if param == name.as_str() {
+                    key.to_string()
+                } else {
+                    value.into_owned()
+                };
+                (param.into_owned(), value)
+            })
+            .collect();
+        let mut masked = url.clone();
+        masked.query_pairs_mut().clear().extend_pairs(pairs);
+        masked
+    }
+}
+
+/// Returns the `Authorization: Bearer <token>` header credentials.
+fn bearer_credentials(token: &SecureString) -> Result<Credentials<'static>, AuthenticationError> {
+    let header_value = format!("Bearer {}", token.as_str());
+    let mut value = HeaderValue::from_str(&header_value).map_err(|e| {
+        AuthenticationError::InvalidBearerToken {
+            message: e.to_string(),
+        }
+    })?;
+    value.set_sensitive(true);
+    Ok(Credentials::Header(AUTHORIZATION, value))
+}
+
+/// The name of the security scheme documenting Digest authentication.
+pub(crate) const DIGEST_SCHEME_NAME: &str = "digestAuth";
+
+/// Checks whether the byte is allowed in a cookie value (RFC 6265).
+fn is_cookie_octet(byte: u8) -> bool {
+    matches!(byte, 0x21 | 0x23..=0x2B | 0x2D..=0x3A | 0x3C..=0x5B | 0x5D..=0x7E)
+}
+
+/// A security scheme inferred from an [`Authentication`], with its name.
+#[derive(Debug, Clone)]
+pub(crate) struct InferredSecurity {
+    pub(crate) name: String,
+    pub(crate) scheme: SecurityScheme,
+    pub(crate) scopes: Vec<String>,
+}
+
+impl InferredSecurity {
+    fn new(name: impl Into<String>, scheme: SecurityScheme) -> Self {
+        Self {
+            name: name.into(),
+            scheme,
+            scopes: Vec::new(),
+        }
+    }
+
+    /// Names an API key scheme after the camel-cased name of the key.
+    pub(crate) fn api_key(name: &str, location: ApiKeyLocation) -> Self {
+        use cruet::Inflector;
+        Self::new(
+            name.to_camel_case(),
+            SecurityScheme::api_key(name, location),
+        )
+    }
+
+    /// Returns the security requirement of the scheme.
+    pub(crate) fn requirement(&self) -> SecurityRequirement {
+        SecurityRequirement::with_scopes(&self.name, &self.scopes)
+    }
+}
+
+impl Authentication {
+    /// Infers the security scheme documenting this authentication.
+    pub(crate) fn inferred_security(&self) -> InferredSecurity {
+        match self {
+            Self::Bearer(_) => InferredSecurity::new("bearerAuth", SecurityScheme::bearer()),
+            Self::Basic { .. } => InferredSecurity::new("basicAuth", SecurityScheme::basic()),
+            Self::Digest { .. } => {
+                InferredSecurity::new(DIGEST_SCHEME_NAME, SecurityScheme::digest())
+            }
+            Self::ApiKey { header_name, .. } => {
+                InferredSecurity::api_key(header_name, ApiKeyLocation::Header)
+            }
+            Self::ApiKeyQuery { name, .. } => {
+                InferredSecurity::api_key(name, ApiKeyLocation::Query)
+            }
+            Self::ApiKeyCookie { name, .. } => {
+                InferredSecurity::api_key(name, ApiKeyLocation::Cookie)
+            }
+            Self::Signed(signer) => {
+                InferredSecurity::new(signer.security_scheme_name(), signer.security_scheme())
+            }
+            #[cfg(feature = "jwt")]
+            Self::Jwt(_) => {
+                InferredSecurity::new("bearerAuth", SecurityScheme::bearer_with_format("JWT"))
+            }
+            #[cfg(feature = "oauth2")]
+            Self::OAuth2(config) => match config.0.flows() {
+                Some(flows) => InferredSecurity {
+                    name: OAUTH2_SCHEME_NAME.to_string(),
+                    scheme: SecurityScheme::OAuth2 {
+                        flows: Box::new(flows),
+                        description: None,
+                    },
+                    scopes: config.0.scopes.clone(),
+                },
+                None => InferredSecurity::new("bearerAuth", SecurityScheme::bearer()),
+            },
+        }
+    }
 }
 
 impl fmt::Debug for Authentication {
@@ -314,11 +657,29 @@ impl fmt::Debug for Authentication {
                 .field("username", username)
                 .field("password", &"[REDACTED]")
                 .finish(),
+            Self::Digest { username, .. } => f
+                .debug_struct("Digest")
+                .field("username", username)
+                .field("password", &"[REDACTED]")
+                .finish(),
             Self::ApiKey { header_name, .. } => f
                 .debug_struct("ApiKey")
                 .field("header_name", header_name)
                 .field("key", &"[REDACTED]")
                 .finish(),
+            Self::ApiKeyQuery { name, .. } => f
+                .debug_struct("ApiKeyQuery")
+                .field("name", name)
+                .field("key", &"[REDACTED]")
+                .finish(),
+            Self::ApiKeyCookie { name, .. } => f
+                .debug_struct("ApiKeyCookie")
+                .field("name", name)
+                .field("key", &"[REDACTED]")
+                .finish(),
+            Self::Signed(signer) => f.debug_tuple("Signed").field(signer).finish(),
+            #[cfg(feature = "jwt")]
+            Self::Jwt(builder) => f.debug_tuple("Jwt").field(builder).finish(),
             #[cfg(feature = "oauth2")]
             Self::OAuth2(config) => f.debug_tuple("OAuth2").field(config).finish(),
         }
@@ -332,9 +693,15 @@ impl fmt::Display for Authentication {
                 write!(f, "Bearer {token}")
             }
             Self::Basic { username, .. } => write!(f, "Basic (username: {username})"),
+            Self::Digest { username, .. } => write!(f, "Digest (username: {username})"),
             Self::ApiKey { header_name, key } => {
                 write!(f, "ApiKey ({header_name}: {key})")
             }
+            Self::ApiKeyQuery { name, key } => write!(f, "ApiKey (query {name}: {key})"),
+            Self::ApiKeyCookie { name, key } => write!(f, "ApiKey (cookie {name}: {key})"),
+            Self::Signed(signer) => write!(f, "Signed ({})", signer.security_scheme_name()),
+            #[cfg(feature = "jwt")]
+            Self::Jwt(builder) => write!(f, "Jwt ({})", builder.algorithm()),
             #[cfg(feature = "oauth2")]
             Self::OAuth2(config) => {
                 write!(f, "OAuth2 (client_id: {})", config.0.client_id)
@@ -399,6 +766,76 @@ mod tests {
         assert_eq!(auth.to_string(), "ApiKey (X-API-Key: secr...2345)");
     }
 
+    #[test]
+    fn test_api_key_locations() {
+        let auth = Authentication::api_key("X-API-Key", "key", ApiKeyLocation::Header);
+        assert!(matches!(auth, Authentication::ApiKey { .. }));
+
+        let auth = Authentication::api_key("api_key", "secret-key-12345", ApiKeyLocation::Query);
+        assert_eq!(auth.to_string(), "ApiKey (query api_key: secr...2345)");
+        insta::assert_snapshot!(format!("{auth:?}"), @r#"ApiKeyQuery { name: "api_key", key: "[REDACTED]" }"#);
+        let error = auth.to_header().expect_err("should not be a header");
+        insta::assert_snapshot!(error, @"API key 'api_key' is sent in the query string, not in a header");
+
+        let auth = Authentication::api_key("session", "secret-key-12345", ApiKeyLocation::Cookie);
+        assert_eq!(auth.to_string(), "ApiKey (cookie session: secr...2345)");
+        let (header_name, header_value) = auth.to_header().expect("should be a cookie header");
+        assert_eq!(header_name, COOKIE);
+        assert_eq!(header_value, "session=secret-key-12345");
+        insta::assert_snapshot!(format!("{header_value:?}"), @"Sensitive");
+    }
+
+    #[test]
+    fn test_api_key_cookie_errors() {
+        let auth = Authentication::api_key("my session", "key", ApiKeyLocation::Cookie);
+        insta::assert_snapshot!(auth.to_header().expect_err("should reject the name"), @"Invalid API key name 'my session': Cookie name must be a token");
+
+        let auth = Authentication::api_key("session", "a;b", ApiKeyLocation::Cookie);
+        insta::assert_snapshot!(auth.to_header().expect_err("should reject the key"), @r#"API key contains invalid characters: Cookie value cannot contain whitespace, '"', ',', ';' or '\'"#);
+    }
+
+    #[test]
+    fn test_digest_authentication() {
+        let auth = Authentication::Digest {
+            username: "admin".to_string(),
+            password: "secret-password".into(),
+        };
+
+        insta::assert_snapshot!(auth.to_header().expect_err("no header before the challenge"), @"Digest authentication has no authentication header before the server challenge");
+        insta::assert_snapshot!(format!("{auth:?}"), @r#"Digest { username: "admin", password: "[REDACTED]" }"#);
+        insta::assert_snapshot!(auth, @"Digest (username: admin)");
+    }
+
+    #[cfg(feature = "jwt")]
+    #[test]
+    fn test_jwt_authentication() {
+        let auth = Authentication::jwt(JwtBuilder::hs256("test-secret").with_subject("user-123"));
+
+        let (name, value) = auth.to_header().expect("bearer header");
+        assert_eq!(name, AUTHORIZATION);
+        assert!(value.is_sensitive());
+        assert!(value.to_str().expect("ascii").starts_with("Bearer ey"));
+        insta::assert_snapshot!(format!("{auth:?}"), @r#"Jwt(JwtBuilder { algorithm: HS256, key_id: None, claims: {"sub": String("user-123")}, expires_in: 3600s, .. })"#);
+        insta::assert_snapshot!(auth, @"Jwt (HS256)");
+    }
+
+    #[test]
+    fn test_mask_api_key_in_url() {
+        let auth = Authentication::api_key("api_key", "secret-key-12345", ApiKeyLocation::Query);
+        let url = Url::parse("http://localhost/users?page=1&api_key=secret-key-12345")
+            .expect("valid URL");
+
+        insta::assert_snapshot!(auth.mask_url(&url), @"http://localhost/users?page=1&api_key=secr...2345");
+    }
+
+    #[test]
+    fn test_sensitive_headers() {
+        let auth = Authentication::Bearer("my-secret-token".into());
+        let (_, header_value) = auth.to_header().unwrap();
+
+        assert!(header_value.is_sensitive());
+    }
+
     #[test]
     fn test_secure_string_mask_short_tokens() {
         assert_eq!(SecureString::mask_sensitive("short"), "***");
@@ -4,12 +4,18 @@ use std::net::{IpAddr, Ipv4Addr};
 use http::Uri;
 use http::uri::{PathAndQuery, Scheme};
 use indexmap::IndexMap;
-use utoipa::openapi::{Info, Server};
+use utoipa::openapi::{Info, Server, Tag};
 
+use super::cookie_jar::CookieJar;
 use super::openapi::channel::CollectorHandle;
+use super::openapi::{ReusableComponents, SchemaNamingStrategy};
 use super::security::{SecurityRequirement, SecurityScheme};
 use super::{ApiClient, ApiClientError};
 
+/// The name of the security scheme declared for a client certificate.
+#[cfg(feature = "tls")]
+const MUTUAL_TLS_SCHEME_NAME: &str = "mutualTLS";
+
 /// Builder for creating `ApiClient` instances with comprehensive configuration options.
 ///
 /// `ApiClientBuilder` provides a fluent interface for configuring all aspects of an API client,
@@ -72,6 +78,17 @@ pub struct ApiClientBuilder {
     authentication: Option<super::Authentication>,
     security_schemes: IndexMap<String, SecurityScheme>,
     default_security: Vec<SecurityRequirement>,
+    infer_security: bool,
+    cookie_jar: bool,
+    session_cookie: Option<String>,
+    schema_naming: SchemaNamingStrategy,
+    reusable_components: Option<ReusableComponents>,
+    tags: IndexMap<String, Tag>,
+    tag_groups: IndexMap<String, Vec<String>>,
+    #[cfg(feature = "tls")]
+    root_certificates: Vec<reqwest::Certificate>,
+    #[cfg(feature = "tls")]
+    client_identity: Option<reqwest::Identity>,
 }
 
 impl ApiClientBuilder {
@@ -119,8 +136,31 @@ impl ApiClientBuilder {
             authentication,
             security_schemes,
             default_security,
+            infer_security,
+            cookie_jar,
+            session_cookie,
+            schema_naming,
+            reusable_components,
+            tags,
+            tag_groups,
+            #[cfg(feature = "tls")]
+            root_certificates,
+            #[cfg(feature = "tls")]
+            client_identity,
         } = self;
 
+        #[cfg(feature = "tls")]
+        let client = if root_certificates.is_empty() && client_identity.is_none() {
+            client
+        } else {
+            let builder = reqwest::Client::builder().tls_certs_merge(root_certificates);
+            let builder = match client_identity {
+                Some(identity) => builder.identity(identity),
+                None => builder,
+            };
+            builder.build()?
+        };
+
         let builder = Uri::builder()
             .scheme(scheme)
             .authority(format!("{host}:{port}"));
@@ -148,6 +188,12 @@ impl ApiClientBuilder {
             authentication,
             security_schemes,
             default_security,
+            infer_security,
+            cookie_jar: cookie_jar.then(|| CookieJar::new(session_cookie)),
+            schema_naming,
+            reusable_components,
+            tags,
+            tag_groups,
         })
     }
 
@@ -208,10 +254,80 @@ impl ApiClientBuilder {
         self
     }
 
+    /// Declares a tag, with its description and external docs.
+    ///
+    /// Declared tags come first in the specification, in declaration order, followed by the
+    /// other tags of the operations sorted alphabetically. Use
+    /// [`with_tag_simple()`](Self::with_tag_simple) for convenience.
+    pub fn with_tag(mut self, tag: Tag) -> Self {
+        self.tags.insert(tag.name.clone(), tag);
+        self
+    }
+
+    /// Groups tags, for the renderers supporting the `x-tagGroups` extension like Redoc.
+    ///
+    /// Such renderers only display the tags of a group, so every tag should belong to one.
+    pub fn with_tag_group(
+        mut self,
+        name: impl Into<String>,
+        tags: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.tag_groups
+            .entry(name.into())
+            .or_default()
+            .extend(tags.into_iter().map(Into::into));
+        self
+    }
+
     /// Sets the default authentication for all requests. Can be overridden per-request.
     ///
-    /// Supports `Bearer`, `Basic`, and `ApiKey` authentication types.
+    /// Supports `Bearer`, `Basic`, `Digest`, and `ApiKey` authentication types.
+    ///
+    /// An OAuth2 Authorization Code configuration is also documented: an `oauth2`
+    /// security scheme with the authorization code flow is declared, and required by
+    /// default, unless a scheme with this name is already declared.
+    ///
+    /// Likewise, Digest authentication declares a `digestAuth` HTTP `digest` scheme,
+    /// and JWT authentication a `bearerAuth` HTTP `bearer` scheme with the `JWT` format,
+    /// both required by default.
     pub fn with_authentication(mut self, authentication: super::Authentication) -> Self {
+        if let super::Authentication::Digest { .. } = &authentication
+            && !self
+                .security_schemes
+                .contains_key(super::auth::DIGEST_SCHEME_NAME)
+        {
+            self.security_schemes.insert(
+                super::auth::DIGEST_SCHEME_NAME.to_string(),
+                SecurityScheme::digest(),
+            );
+            self.default_security
+                .push(SecurityRequirement::new(super::auth::DIGEST_SCHEME_NAME));
+        }
+        #[cfg(feature = "jwt")]
+        if let super::Authentication::Jwt(_) = &authentication
+            && !self.security_schemes.contains_key("bearerAuth")
+        {
+            self.security_schemes.insert(
+                "bearerAuth".to_string(),
+                SecurityScheme::bearer_with_format("JWT"),
+            );
+            self.default_security
+                .push(SecurityRequirement::new("bearerAuth"));
+        }
+        #[cfg(feature = "oauth2")]
+        if let super::Authentication::OAuth2(config) = &authentication
+            && let Some(scheme) = config.inner().security_scheme()
+            && !self
+                .security_schemes
+                .contains_key(super::oauth2::OAUTH2_SCHEME_NAME)
+        {
+            self.security_schemes
+                .insert(super::oauth2::OAUTH2_SCHEME_NAME.to_string(), scheme);
+            self.default_security.push(SecurityRequirement::with_scopes(
+                super::oauth2::OAUTH2_SCHEME_NAME,
+                config.inner().scopes.clone(),
+            ));
+        }
         self.authentication = Some(authentication);
         self
     }
@@ -269,6 +385,16 @@ impl ApiClientBuilder {
         self
     }
 
+    /// Convenience method to declare a tag with a description without importing utoipa types.
+    pub fn with_tag_simple(self, name: impl Into<String>, description: impl Into<String>) -> Self {
+        use utoipa::openapi::tag::TagBuilder;
+        let tag = TagBuilder::new()
+            .name(name)
+            .description(Some(description))
+            .build();
+        self.with_tag(tag)
+    }
+
     /// Registers a named security scheme for OpenAPI `components.securitySchemes`.
     pub fn with_security_scheme(mut self, name: impl Into<String>, scheme: SecurityScheme) -> Self {
         self.security_schemes.insert(name.into(), scheme);
@@ -290,6 +416,100 @@ impl ApiClientBuilder {
         self
     }
 
+    /// Infers the security of each operation from the authentication of its calls.
+    ///
+    /// Each call documents the security scheme of its [`Authentication`](super::Authentication),
+    /// and requires it, unless its security is set with
+    /// [`ApiCall::with_security`](super::ApiCall::with_security) and similar methods.
+    /// A successful call made with
+    /// [`with_authentication_none`](super::ApiCall::with_authentication_none) documents
+    /// a public operation, with `security: []`.
+    ///
+    /// The inferred schemes are named:
+    ///
+    /// - `bearerAuth` for bearer tokens, and pre-acquired OAuth2 tokens,
+    /// - `basicAuth` for basic authentication,
+    /// - after the camel-cased name of the API key, e.g. `xApiKey` for `X-API-Key`,
+    /// - `oauth2` for the other OAuth2 grants,
+    /// - after [`RequestSigner::security_scheme_name()`](super::RequestSigner::security_scheme_name)
+    ///   for signed requests,
+    /// - after the camel-cased name of the [session cookie](Self::with_session_cookie),
+    ///   for the calls without authentication sending it from the cookie jar.
+    ///
+    /// A scheme declared with [`with_security_scheme()`](Self::with_security_scheme)
+    /// takes precedence over the inferred scheme with the same name, e.g. to describe it.
+    /// The client default security still applies to the operations without calls
+    /// inferring their security.
+    pub fn with_security_inference(mut self) -> Self {
+        self.infer_security = true;
+        self
+    }
+
+    /// Enables a cookie jar, storing the cookies set by the responses, and sending them
+    /// with the later calls, e.g. the session cookie set by a login endpoint.
+    ///
+    /// The `Set-Cookie` headers of the responses setting cookies are documented.
+    /// The cookies set with [`ApiCall::with_cookies`](super::ApiCall::with_cookies) take
+    /// precedence over the cookies of the jar with the same name.
+    /// A cookie is removed from the jar once expired, e.g. with `Max-Age=0`.
+    /// The jar is shared by the clones of the client.
+    ///
+    /// With [`with_security_inference()`](Self::with_security_inference), the calls without
+    /// authentication sending the [session cookie](Self::with_session_cookie) of the jar
+    /// document a cookie `apiKey` security scheme for it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use clawspec_core::ApiClient;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = ApiClient::builder()
+    ///     .with_cookie_jar()
+    ///     .with_session_cookie("session_id")
+    ///     .with_security_inference()
+    ///     .build()?;
+    ///
+    /// // The session cookie set by the login response is sent with the later calls
+    /// // client.post("/login")?.json(&credentials)?.await?.as_empty().await?;
+    /// // client.get("/profile")?.await?.as_json::<Profile>().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_cookie_jar(mut self) -> Self {
+        self.cookie_jar = true;
+        self
+    }
+
+    /// Sets the name of the cookie holding the session, in the
+    /// [cookie jar](Self::with_cookie_jar).
+    ///
+    /// With [`with_security_inference()`](Self::with_security_inference), only this cookie
+    /// documents the security of the calls without authentication, the other cookies of
+    /// the jar, like preferences, are ignored.
+    /// Without a cookie jar, the session cookie is ignored.
+    pub fn with_session_cookie(mut self, name: impl Into<String>) -> Self {
+        self.session_cookie = Some(name.into());
+        self
+    }
+
+    /// Sets the naming strategy of the collected component schemas.
+    ///
+    /// See [`SchemaNamingStrategy`] for the available strategies.
+    pub fn with_schema_naming(mut self, naming: SchemaNamingStrategy) -> Self {
+        self.schema_naming = naming;
+        self
+    }
+
+    /// Hoists the responses and parameters repeated across operations into components.
+    ///
+    /// Disabled by default, see [`ReusableComponents`].
+    pub fn with_reusable_components(mut self, reusable_components: ReusableComponents) -> Self {
+        self.reusable_components = Some(reusable_components);
+        self
+    }
+
     // =========================================================================
     // OAuth2 convenience methods (requires "oauth2" feature)
     // =========================================================================
@@ -416,6 +636,78 @@ impl ApiClientBuilder {
 
         Ok(self.with_authentication(Authentication::OAuth2(SharedOAuth2Config::new(config))))
     }
+
+    // =========================================================================
+    // TLS methods (requires "tls" feature)
+    // =========================================================================
+
+    /// Trusts additional root certificates, in PEM format.
+    ///
+    /// Use it to call a server with a certificate issued by a private CA,
+    /// e.g. a self-signed certificate in tests. The PEM may contain several certificates.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a PEM certificate is invalid.
+    ///
+    /// ```rust,no_run
+    /// use clawspec_core::ApiClient;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let ca = std::fs::read("certs/ca.pem")?;
+    /// let client = ApiClient::builder()
+    ///     .with_https()
+    ///     .with_host("localhost")
+    ///     .with_port(8443)
+    ///     .with_root_certificate(ca)?
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tls")]
+    pub fn with_root_certificate(mut self, pem: impl AsRef<[u8]>) -> Result<Self, ApiClientError> {
+        let certificates = reqwest::Certificate::from_pem_bundle(pem.as_ref())?;
+        self.root_certificates.extend(certificates);
+        Ok(self)
+    }
+
+    /// Sets the client certificate for mutual TLS, in PEM format.
+    ///
+    /// The PEM must contain the certificate chain and the private key.
+    /// A `mutualTLS` security scheme is declared, and required by default,
+    /// unless a scheme with this name is already declared.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the PEM certificate or private key is invalid.
+    ///
+    /// ```rust,no_run
+    /// use clawspec_core::ApiClient;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let identity = std::fs::read("certs/client.pem")?;
+    /// let client = ApiClient::builder()
+    ///     .with_https()
+    ///     .with_host("api.example.com")
+    ///     .with_client_identity(identity)?
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "tls")]
+    pub fn with_client_identity(mut self, pem: impl AsRef<[u8]>) -> Result<Self, ApiClientError> {
+        let identity = reqwest::Identity::from_pem(pem.as_ref())?;
+        self.client_identity = Some(identity);
+        if !self.security_schemes.contains_key(MUTUAL_TLS_SCHEME_NAME) {
+            self.security_schemes.insert(
+                MUTUAL_TLS_SCHEME_NAME.to_string(),
+                SecurityScheme::mutual_tls(),
+            );
+            self.default_security
+                .push(SecurityRequirement::new(MUTUAL_TLS_SCHEME_NAME));
+        }
+        Ok(self)
+    }
 }
 
 impl Default for ApiClientBuilder {
@@ -431,6 +723,17 @@ impl Default for ApiClientBuilder {
             authentication: None,
             security_schemes: IndexMap::new(),
             default_security: Vec::new(),
+            infer_security: false,
+            cookie_jar: false,
+            session_cookie: None,
+            schema_naming: SchemaNamingStrategy::default(),
+            reusable_components: None,
+            tags: IndexMap::new(),
+            tag_groups: IndexMap::new(),
+            #[cfg(feature = "tls")]
+            root_certificates: Vec::new(),
+            #[cfg(feature = "tls")]
+            client_identity: None,
         }
     }
 }
@@ -680,6 +983,47 @@ mod tests {
         assert_eq!(client.default_security[0].name, "bearerAuth");
     }
 
+    #[cfg(feature = "tls")]
+    #[test]
+    fn test_builder_rejects_invalid_certificates() {
+        let result = ApiClientBuilder::default().with_root_certificate(
+            "-----BEGIN CERTIFICATE-----\nnot base64!\n-----END CERTIFICATE-----\n",
+        );
+        assert!(matches!(result, Err(ApiClientError::ReqwestError(_))));
+
+        let result = ApiClientBuilder::default().with_client_identity("not an identity");
+        assert!(matches!(result, Err(ApiClientError::ReqwestError(_))));
+    }
+
+    #[cfg(feature = "tls")]
+    #[tokio::test]
+    async fn test_builder_with_client_identity_keeps_declared_scheme() {
+        use super::super::security::SecurityScheme;
+        use crate::test_client::TestServerTls;
+
+        let tls = TestServerTls::mutual().expect("should generate certificates");
+        let identity = tls.client_identity_pem().expect("should have an identity");
+
+        let client = ApiClientBuilder::default()
+            .with_security_scheme(
+                MUTUAL_TLS_SCHEME_NAME,
+                SecurityScheme::mutual_tls().with_description("Partner certificate"),
+            )
+            .with_client_identity(identity.as_str())
+            .expect("valid identity")
+            .with_root_certificate(tls.ca_certificate_pem())
+            .expect("valid certificate")
+            .build()
+            .expect("should build client");
+
+        assert!(client.default_security.is_empty());
+        let scheme = &client.security_schemes[MUTUAL_TLS_SCHEME_NAME];
+        assert!(matches!(
+            scheme,
+            SecurityScheme::MutualTls { description: Some(description) } if description == "Partner certificate"
+        ));
+    }
+
     #[tokio::test]
     async fn test_builder_with_multiple_default_securities() {
         use super::super::security::{ApiKeyLocation, SecurityRequirement, SecurityScheme};
@@ -723,6 +1067,109 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn test_collected_openapi_resolves_schema_name_conflicts() {
+        mod v1 {
+            #[derive(serde::Serialize, utoipa::ToSchema)]
+            pub struct User {
+                pub id: u64,
+            }
+        }
+        mod v2 {
+            #[derive(serde::Serialize, utoipa::ToSchema)]
+            pub struct User {
+                pub name: String,
+            }
+        }
+        let mut client = ApiClientBuilder::default()
+            .with_schema_naming(SchemaNamingStrategy::short_name().fail_on_conflict())
+            .build()
+            .expect("should build client");
+        client.register_schema::<v1::User>().await;
+        client.register_schema::<v2::User>().await;
+
+        let error = client
+            .try_collected_openapi()
+            .await
+            .expect_err("should fail on the conflict");
+        assert!(matches!(error, ApiClientError::SchemaNameConflict { .. }));
+
+        let openapi = client.collected_openapi().await;
+        let components = openapi.components.expect("should have components");
+        assert_eq!(components.schemas.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_declared_tags_and_groups_appear_in_openapi() {
+        let mut client = ApiClientBuilder::default()
+            .with_tag_simple("users", "User management")
+            .with_tag(Tag::new("admin"))
+            .with_tag_group("Core", ["users"])
+            .with_tag_group("Internal", ["admin", "health"])
+            .build()
+            .expect("should build client");
+
+        let openapi = client.collected_openapi().await;
+
+        let value = serde_json::to_value(&openapi).expect("should serialize");
+        insta::assert_snapshot!(value["tags"], @r#"
+        [{"description":"User management","name":"users"},{"name":"admin"}]
+        "#);
+        insta::assert_snapshot!(value["x-tagGroups"], @r#"
+        [{"name":"Core","tags":["users"]},{"name":"Internal","tags":["admin","health"]}]
+        "#);
+    }
+
+    #[cfg(feature = "oauth2")]
+    #[tokio::test]
+    async fn test_oauth2_authorization_code_is_documented() {
+        use super::super::Authentication;
+        use super::super::oauth2::{OAuth2Config, OAuth2Login, SharedOAuth2Config};
+
+        let config = OAuth2Config::authorization_code(
+            "client-id",
+            "http://localhost:8080/authorize",
+            "http://localhost:8080/token",
+            "http://localhost:3000/callback",
+            OAuth2Login::form([("username", "alice")]),
+        )
+        .expect("should create builder")
+        .add_scope("read:users")
+        .build()
+        .expect("should build config");
+        let mut client = ApiClientBuilder::default()
+            .with_authentication(Authentication::OAuth2(SharedOAuth2Config::new(config)))
+            .build()
+            .expect("should build client");
+
+        let openapi = client.collected_openapi().await;
+
+        let value = serde_json::to_value(&openapi).expect("should serialize");
+        insta::assert_snapshot!(value["components"]["securitySchemes"], @r#"
+        {"oauth2":{"flows":{"authorizationCode":{"authorizationUrl":"http://localhost:8080/authorize","scopes":{"read:users":""},"tokenUrl":"http://localhost:8080/token"}},"type":"oauth2"}}
+        "#);
+        insta::assert_snapshot!(value["security"], @r#"
+        [{"oauth2":["read:users"]}]
+        "#);
+    }
+
+    #[cfg(feature = "jwt")]
+    #[tokio::test]
+    async fn test_jwt_authentication_is_documented() {
+        use super::super::{Authentication, JwtBuilder};
+
+        let mut client = ApiClientBuilder::default()
+            .with_authentication(Authentication::jwt(JwtBuilder::hs256("test-secret")))
+            .build()
+            .expect("should build client");
+
+        let openapi = client.collected_openapi().await;
+
+        let value = serde_json::to_value(&openapi).expect("should serialize");
+        insta::assert_snapshot!(value["components"]["securitySchemes"], @r#"{"bearerAuth":{"bearerFormat":"JWT","scheme":"bearer","type":"http"}}"#);
+        insta::assert_snapshot!(value["security"], @r#"[{"bearerAuth":[]}]"#);
+    }
+
     #[tokio::test]
     async fn test_security_schemes_appear_in_openapi() {
         use super::super::security::{SecurityRequirement, SecurityScheme};
@@ -147,6 +147,30 @@ impl ApiCall {
         self
     }
 
+    /// Adds a provider state required by this interaction.
+    ///
+    /// Provider states describe the data the provider must have for the interaction
+    /// to succeed (e.g. `"user 42 exists"`). They are not sent to the server and don't
+    /// appear in the OpenAPI specification; they are written to the
+    /// [Pact contract](crate::ApiClient::collected_pact) of the recorded calls.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use clawspec_core::ApiClient;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = ApiClient::builder().build()?;
+    /// let call = client
+    ///     .get("/users/42")?
+    ///     .with_provider_state("user 42 exists");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_provider_state(mut self, state: impl Into<String>) -> Self {
+        self.metadata.provider_states.push(state.into());
+        self
+    }
+
     /// Sets the security requirements for this specific operation.
     ///
     /// This method overrides the default security configured on the client.
@@ -274,6 +298,55 @@ impl ApiCall {
         self
     }
 
+    /// Uses an OAuth2 token with exactly these scopes for this request.
+    ///
+    /// The token is acquired with the scopes, and cached per set of scopes, so
+    /// endpoints requiring different scopes (e.g. read vs admin) can be tested with
+    /// the same client. The scopes are recorded in the operation security requirement,
+    /// on the OAuth2 security scheme declared on the client (`oauth2` if none).
+    ///
+    /// The request fails if its authentication is not OAuth2, or is a pre-acquired token
+    /// (see [`ApiClientBuilder::with_oauth2_token`](crate::ApiClientBuilder::with_oauth2_token)).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let mut client = ApiClient::builder()
+    ///     .with_security_scheme("oauth2", scheme)
+    ///     .with_oauth2_client_credentials("client-id", "secret", "https://auth.example.com/token")?
+    ///     .build()?;
+    ///
+    /// client
+    ///     .delete("/admin/users/42")?
+    ///     .with_oauth2_scopes(["admin:users"])
+    ///     .await?
+    ///     .as_empty()
+    ///     .await?;
+    /// ```
+    ///
+    /// # Generated OpenAPI
+    ///
+    /// ```yaml
+    /// paths:
+    ///   /admin/users/42:
+    ///     delete:
+    ///       security:
+    ///         - oauth2: [admin:users]
+    /// ```
+    #[cfg(feature = "oauth2")]
+    pub fn with_oauth2_scopes(
+        mut self,
+        scopes: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        let scopes: Vec<String> = scopes.into_iter().map(Into::into).collect();
+        self.security = Some(vec![SecurityRequirement::with_scopes(
+            self.oauth2_scheme.clone(),
+            scopes.clone(),
+        )]);
+        self.oauth2_scopes = Some(scopes);
+        self
+    }
+
     // =============================================================================
     // Request Configuration Methods
     // =============================================================================
@@ -455,6 +528,7 @@ impl ApiCall {
     /// ```
     pub fn with_authentication(mut self, authentication: crate::client::Authentication) -> Self {
         self.authentication = Some(authentication);
+        self.authentication_removed = false;
         self
     }
 
@@ -484,6 +558,39 @@ impl ApiCall {
     /// ```
     pub fn with_authentication_none(mut self) -> Self {
         self.authentication = None;
+        self.authentication_removed = true;
+        self
+    }
+
+    /// Documents the `401 Unauthorized` challenge of the Digest authentication.
+    ///
+    /// With [`Authentication::Digest`](crate::client::Authentication::Digest), the request
+    /// is first answered by a `401 Unauthorized` challenge, and sent again with the
+    /// credentials. By default, only the final response is documented.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use clawspec_core::{ApiClient, Authentication};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = ApiClient::builder()
+    ///     .with_authentication(Authentication::Digest {
+    ///         username: "admin".to_string(),
+    ///         password: "secret".into(),
+    ///     })
+    ///     .build()?;
+    ///
+    /// // Documents both the 401 challenge and the 200 response
+    /// let response = client
+    ///     .get("/device/status")?
+    ///     .with_digest_challenge()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_digest_challenge(mut self) -> Self {
+        self.document_digest_challenge = true;
         self
     }
 
@@ -2,19 +2,26 @@ use std::future::{Future, IntoFuture};
 use std::pin::Pin;
 
 use headers::HeaderMapExt;
-use http::header::{HeaderName, HeaderValue};
-use http::{Method, Uri};
-use reqwest::{Body, Request};
+use http::header::{AUTHORIZATION, HeaderName, HeaderValue};
+use http::{Method, StatusCode, Uri};
+use reqwest::{Body, Request, Response};
 use tracing::debug;
 use url::Url;
 
 use super::{ApiCall, BODY_MAX_LENGTH, CollectorSender};
+use crate::client::auth::{Credentials, InferredSecurity};
 use crate::client::call_parameters::{CallParameters, OperationMetadata};
+use crate::client::cookie_jar::CookieJar;
+use crate::client::digest::DigestChallenge;
 use crate::client::openapi::CalledOperation;
 use crate::client::openapi::channel::CollectorMessage;
 use crate::client::parameters::PathResolved;
 use crate::client::response::ExpectedStatusCodes;
-use crate::client::{ApiClientError, CallBody, CallPath, CallQuery, CallResult};
+use crate::client::security::ApiKeyLocation;
+use crate::client::signing::SignableRequest;
+use crate::client::{
+    ApiClientError, Authentication, AuthenticationError, CallBody, CallPath, CallQuery, CallResult,
+};
 
 impl ApiCall {
     pub(in crate::client) fn build(
@@ -46,11 +53,20 @@ impl ApiCall {
                 description: None,
                 #[cfg(feature = "redaction")]
                 response_description: None,
+                provider_states: vec![],
             },
             #[cfg(feature = "redaction")]
             response_description: None,
             skip_collection: false,
             security: default_security,
+            infer_security: false,
+            authentication_removed: false,
+            document_digest_challenge: false,
+            cookie_jar: None,
+            #[cfg(feature = "oauth2")]
+            oauth2_scopes: None,
+            #[cfg(feature = "oauth2")]
+            oauth2_scheme: crate::client::oauth2::OAUTH2_SCHEME_NAME.to_string(),
         };
         Ok(result)
     }
@@ -134,15 +150,59 @@ impl ApiCall {
             response_description,
             skip_collection,
             security,
+            infer_security,
+            authentication_removed,
+            document_digest_challenge,
+            cookie_jar,
+            #[cfg(feature = "oauth2")]
+            oauth2_scopes,
+            #[cfg(feature = "oauth2")]
+                oauth2_scheme: _,
         } = self;
 
+        // Infer the security from the authentication, unless set for this request
+        let inferred = authentication
+            .as_ref()
+            .filter(|_| infer_security)
+            .map(Authentication::inferred_security);
+
         // Resolve OAuth2 authentication to Bearer if needed
-        let resolved_auth = Self::resolve_authentication(authentication).await?;
+        #[cfg(feature = "oauth2")]
+        let resolved_auth =
+            Self::resolve_authentication(authentication, oauth2_scopes.as_deref()).await?;
+        #[cfg(not(feature = "oauth2"))]
+        let resolved_auth = authentication;
 
         // Build URL and request
         let url = Self::build_url(&base_uri, &path, &query)?;
         let parameters = CallParameters::with_all(query.clone(), headers.clone(), cookies.clone());
-        let request = Self::build_request(method.clone(), url, &parameters, &body, &resolved_auth)?;
+        let (request, jar_cookies) = Self::build_request(
+            method.clone(),
+            url,
+            &parameters,
+            &body,
+            &resolved_auth,
+            cookie_jar.as_ref(),
+        )?;
+
+        // The session cookie sent from the jar documents the session without authentication
+        let session_cookie = cookie_jar
+            .as_ref()
+            .and_then(CookieJar::session_cookie)
+            .filter(|name| jar_cookies.iter().any(|sent| sent == name));
+        let inferred = inferred.or_else(|| {
+            session_cookie
+                .filter(|_| infer_security)
+                .map(|name| InferredSecurity::api_key(name, ApiKeyLocation::Cookie))
+        });
+        let public = infer_security
+            && authentication_removed
+            && session_cookie.is_none()
+            && security.is_none();
+        let security = match (security, &inferred) {
+            (None, Some(inferred)) => Some(vec![inferred.requirement()]),
+            (security, _) => security,
+        };
 
         // Create operation for OpenAPI documentation
         let operation_id = metadata.operation_id.clone();
@@ -166,10 +226,14 @@ impl ApiCall {
             security,
         );
 
-        // Execute HTTP request
-        debug!(?request, "sending...");
-        let response = client.execute(request).await?;
-        debug!(?response, "...receiving");
+        // Execute HTTP request, answering the Digest challenge if needed
+        let (response, digest_challenge) =
+            Self::execute(&client, request, &body, &resolved_auth).await?;
+
+        // Store the cookies set by the response
+        if let Some(jar) = &cookie_jar {
+            operation.set_cookies = jar.store(response.headers());
+        }
 
         // Validate status code
         let status_code = response.status().as_u16();
@@ -189,12 +253,20 @@ impl ApiCall {
             return Err(ApiClientError::UnexpectedStatusCode { status_code, body });
         }
 
+        // A successful call without authentication documents a public operation
+        if public && response.status().is_success() {
+            operation.set_security(vec![]);
+        }
+        if let Some(inferred) = inferred {
+            operation.inferred_security_scheme = Some((inferred.name, inferred.scheme));
+        }
+
         // Process response and collect schemas (only if collection is enabled)
         let call_result = if skip_collection {
             CallResult::new_without_collection(response).await?
         } else {
             let call_result =
-                CallResult::new(operation_id, collector_sender.clone(), response).await?;
+                CallResult::new(operation_id.clone(), collector_sender.clone(), response).await?;
             operation.add_response(call_result.clone());
             Self::collect_schemas_and_operation(
                 &collector_sender,
@@ -204,12 +276,88 @@ impl ApiCall {
                 operation,
             )
             .await;
+
+            // Document the Digest challenge, once the operation is registered
+            if let Some(challenge) = digest_challenge.filter(|_| document_digest_challenge) {
+                let challenge =
+                    CallResult::new(operation_id, collector_sender.clone(), challenge).await?;
+                challenge.get_output(None).await?;
+            }
             call_result
         };
 
         Ok(call_result)
     }
 
+    /// Sends the request, answering the Digest authentication challenge if needed.
+    ///
+    /// Returns the response, with the `401 Unauthorized` challenge response if the
+    /// request was sent again with the Digest credentials.
+    async fn execute(
+        client: &reqwest::Client,
+        request: Request,
+        body: &Option<CallBody>,
+        authentication: &Option<Authentication>,
+    ) -> Result<(Response, Option<Response>), ApiClientError> {
+        let url = match authentication {
+            Some(auth) => auth.mask_url(request.url()),
+            None => request.url().clone(),
+        };
+        let retry = match authentication {
+            Some(Authentication::Digest { username, password }) => {
+                request.try_clone().map(|retry| (retry, username, password))
+            }
+            _ => None,
+        };
+
+        debug!(method = %request.method(), %url, headers = ?request.headers(), "sending...");
+        // The errors display the URL, masked like in the logs
+        let response = client
+            .execute(request)
+            .await
+            .map_err(|error| error.with_url(url.clone()))?;
+        debug!(?response, "...receiving");
+
+        let Some((mut retry, username, password)) =
+            retry.filter(|_| response.status() == StatusCode::UNAUTHORIZED)
+        else {
+            return Ok((response, None));
+        };
+        let Some(challenge) = DigestChallenge::from_headers(response.headers())? else {
+            return Ok((response, None));
+        };
+
+        let uri = match url.query() {
+            Some(query) => format!("{}?{query}", url.path()),
+            None => url.path().to_string(),
+        };
+        let data = body.as_ref().map_or(&[][..], |body| body.data.as_slice());
+        let cnonce = uuid::Uuid::new_v4().simple().to_string();
+        let authorization = challenge.authorization(
+            username,
+            password.as_str(),
+            retry.method().as_str(),
+            &uri,
+            data,
+            &cnonce,
+        );
+        let mut value = HeaderValue::from_str(&authorization).map_err(|e| {
+            AuthenticationError::InvalidUsername {
+                message: e.to_string(),
+            }
+        })?;
+        value.set_sensitive(true);
+        retry.headers_mut().insert(AUTHORIZATION, value);
+
+        debug!(method = %retry.method(), %url, headers = ?retry.headers(), "answering the Digest challenge...");
+        let answer = client
+            .execute(retry)
+            .await
+            .map_err(|error| error.with_url(url.clone()))?;
+        debug!(response = ?answer, "...receiving");
+        Ok((answer, Some(response)))
+    }
+
     pub(super) fn build_url(
         base_uri: &Uri,
         path: &CallPath,
@@ -237,15 +385,29 @@ impl ApiCall {
         url: Url,
         parameters: &CallParameters,
         body: &Option<CallBody>,
-        authentication: &Option<crate::client::Authentication>,
-    ) -> Result<Request, ApiClientError> {
+        authentication: &Option<Authentication>,
+        cookie_jar: Option<&CookieJar>,
+    ) -> Result<(Request, Vec<String>), ApiClientError> {
+        let credentials = authentication
+            .as_ref()
+            .map(Authentication::credentials)
+            .transpose()?;
+
         let mut request = Request::new(method, url);
+
+        // Add API key query parameter if present
+        if let Some(Credentials::Query { name, key }) = &credentials {
+            request
+                .url_mut()
+                .query_pairs_mut()
+                .append_pair(name, key.as_str());
+        }
+
         let req_headers = request.headers_mut();
 
         // Add authentication header if present
-        if let Some(auth) = authentication {
-            let (header_name, header_value) = auth.to_header()?;
-            req_headers.insert(header_name, header_value);
+        if let Some(Credentials::Header(header_name, header_value)) = &credentials {
+            req_headers.insert(header_name.clone(), header_value.clone());
         }
 
         // Add custom headers
@@ -256,15 +418,27 @@ impl ApiCall {
             );
         }
 
-        // Add cookies as Cookie header
-        let cookie_header = parameters.to_cookie_header()?;
+        // Add cookies as Cookie header, with the API key cookie if present
+        let mut cookie_header = parameters.to_cookie_header()?;
+        if let Some(Credentials::Cookie(cookie)) = &credentials {
+            cookie_header = if cookie_header.is_empty() {
+                cookie.clone()
+            } else {
+                format!("{cookie}; {cookie_header}")
+            };
+        }
         if !cookie_header.is_empty() {
-            req_headers.insert(
-                HeaderName::from_static("cookie"),
-                HeaderValue::from_str(&cookie_header)?,
-            );
+            let mut cookie_value = HeaderValue::from_str(&cookie_header)?;
+            cookie_value.set_sensitive(matches!(credentials, Some(Credentials::Cookie(_))));
+            req_headers.insert(HeaderName::from_static("cookie"), cookie_value);
         }
 
+        // Add the cookies of the jar, before signing the request
+        let jar_cookies = match cookie_jar {
+            Some(jar) => jar.add_to(req_headers)?,
+            None => vec![],
+        };
+
         // Set body
         if let Some(body) = body {
             req_headers.typed_insert(body.content_type.clone());
@@ -272,7 +446,16 @@ impl ApiCall {
             *req_body = Some(Body::from(body.data.clone()));
         }
 
-        Ok(request)
+        // Sign the complete request
+        if let Some(Credentials::Signed(signer)) = credentials {
+            let method = request.method().clone();
+            let url = request.url().clone();
+            let data = body.as_ref().map_or(&[][..], |body| body.data.as_slice());
+            let mut signable = SignableRequest::new(&method, &url, request.headers_mut(), data);
+            signer.sign(&mut signable)?;
+        }
+
+        Ok((request, jar_cookies))
     }
 
     #[cfg(feature = "redaction")]
@@ -290,6 +473,7 @@ impl ApiCall {
             tags,
             description,
             response_description: _,
+            provider_states,
         } = metadata;
 
         CalledOperation::build(
@@ -303,6 +487,7 @@ impl ApiCall {
                 tags,
                 description,
                 response_description,
+                provider_states,
             },
             security,
         )
@@ -354,43 +539,49 @@ impl ApiCall {
 
         // Register the operation
         sender
-            .send(CollectorMessage::RegisterOperation(operation))
+            .send(CollectorMessage::RegisterOperation(Box::new(operation)))
             .await;
     }
 
     /// Resolves authentication, acquiring OAuth2 tokens if needed.
     ///
-    /// For OAuth2 authentication, this method acquires a valid token and converts
-    /// the authentication to Bearer token. For other authentication types, the
-    /// original authentication is returned unchanged.
+    /// For OAuth2 authentication, this method acquires a valid token, with the
+    /// requested scopes if any, and converts the authentication to Bearer token.
+    /// For other authentication types, the original authentication is returned
+    /// unchanged, and requesting scopes is an error.
+    #[cfg(feature = "oauth2")]
     async fn resolve_authentication(
         authentication: Option<crate::client::Authentication>,
+        scopes: Option<&[String]>,
     ) -> Result<Option<crate::client::Authentication>, ApiClientError> {
-        #[cfg(feature = "oauth2")]
-        {
-            use crate::client::Authentication;
-
-            match authentication {
-                Some(Authentication::OAuth2(ref config)) => {
-                    // Acquire a valid token
-                    let token = config
-                        .0
-                        .get_valid_token()
-                        .await
-                        .map_err(ApiClientError::oauth2_error)?;
-
-                    // Convert to Bearer authentication
-                    Ok(Some(Authentication::Bearer(
-                        token.access_token().to_string().into(),
-                    )))
-                }
-                other => Ok(other),
+        use crate::client::Authentication;
+
+        match authentication {
+            // A pre-acquired token is documented with a bearer scheme, not an OAuth2 one
+            Some(Authentication::OAuth2(ref config))
+                if scopes.is_some() && config.0.is_pre_acquired() =>
+            {
+                Err(ApiClientError::oauth2_error(
+                    "OAuth2 scopes cannot be requested with a pre-acquired token",
+                ))
             }
-        }
+            Some(Authentication::OAuth2(ref config)) => {
+                // Acquire a valid token
+                let token = match scopes {
+                    Some(scopes) => config.0.get_valid_token_with_scopes(scopes).await,
+                    None => config.0.get_valid_token().await,
+                }
+                .map_err(ApiClientError::oauth2_error)?;
 
-        #[cfg(not(feature = "oauth2"))]
-        {
-            Ok(authentication)
+                // Convert to Bearer authentication
+                Ok(Some(Authentication::Bearer(
+                    token.access_token().to_string().into(),
+                )))
+            }
+            _ if scopes.is_some() => Err(ApiClientError::oauth2_error(
+                "OAuth2 scopes require an OAuth2 authentication",
+            )),
+            other => Ok(other),
         }
     }
 }
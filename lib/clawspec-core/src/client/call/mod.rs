@@ -1,6 +1,7 @@
 use http::{Method, Uri};
 
 use super::call_parameters::OperationMetadata;
+use super::cookie_jar::CookieJar;
 use super::openapi::channel::CollectorSender;
 use super::response::ExpectedStatusCodes;
 use super::security::SecurityRequirement;
@@ -124,4 +125,18 @@ pub struct ApiCall {
     pub(super) skip_collection: bool,
     /// Security requirements for this operation (None = inherit from global)
     pub(super) security: Option<Vec<SecurityRequirement>>,
+    /// Whether the security is inferred from the authentication
+    pub(super) infer_security: bool,
+    /// Whether the authentication was removed for this request
+    pub(super) authentication_removed: bool,
+    /// Whether to document the `401 Unauthorized` challenge of the Digest authentication
+    pub(super) document_digest_challenge: bool,
+    /// The cookie jar of the client, if enabled
+    pub(super) cookie_jar: Option<CookieJar>,
+    /// OAuth2 scopes of the token for this request (None = configured scopes)
+    #[cfg(feature = "oauth2")]
+    pub(super) oauth2_scopes: Option<Vec<String>>,
+    /// Name of the OAuth2 security scheme recording the scopes
+    #[cfg(feature = "oauth2")]
+    pub(super) oauth2_scheme: String,
 }
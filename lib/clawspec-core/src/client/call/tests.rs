@@ -55,6 +55,7 @@ fn test_operation_metadata_creation() {
         description: Some("Test operation description".to_string()),
         #[cfg(feature = "redaction")]
         response_description: Some("Test response description".to_string()),
+        provider_states: vec![],
     };
 
     assert_eq!(metadata.operation_id, "test-operation");
@@ -421,8 +422,9 @@ fn test_build_request_simple() {
     let body = None;
     let parameters = CallParameters::default();
 
-    let request = ApiCall::build_request(method.clone(), url.clone(), &parameters, &body, &None)
-        .expect("should build request");
+    let (request, _) =
+        ApiCall::build_request(method.clone(), url.clone(), &parameters, &body, &None, None)
+            .expect("should build request");
 
     assert_eq!(request.method(), &method);
     assert_eq!(request.url(), &url);
@@ -437,7 +439,7 @@ fn test_build_request_with_headers() {
     let body = None;
     let parameters = CallParameters::with_all(CallQuery::new(), headers, None);
 
-    let request = ApiCall::build_request(method, url, &parameters, &body, &None)
+    let (request, _) = ApiCall::build_request(method, url, &parameters, &body, &None, None)
         .expect("should build request");
 
     assert!(request.headers().get("authorization").is_some());
@@ -454,7 +456,7 @@ fn test_build_request_with_body() {
     let body = Some(CallBody::json(&test_data).expect("should create JSON body"));
     let parameters = CallParameters::default();
 
-    let request = ApiCall::build_request(method, url, &parameters, &body, &None)
+    let (request, _) = ApiCall::build_request(method, url, &parameters, &body, &None, None)
         .expect("should build request");
 
     assert!(request.body().is_some());
@@ -734,7 +736,7 @@ fn test_build_request_with_bearer_auth() {
     let body = None;
     let auth = Some(crate::client::Authentication::Bearer("test-token".into()));
 
-    let request = ApiCall::build_request(method, url, &parameters, &body, &auth)
+    let (request, _) = ApiCall::build_request(method, url, &parameters, &body, &auth, None)
         .expect("should build request");
 
     let auth_header = request.headers().get("authorization");
@@ -753,7 +755,7 @@ fn test_build_request_with_basic_auth() {
         password: "pass".into(),
     });
 
-    let request = ApiCall::build_request(method, url, &parameters, &body, &auth)
+    let (request, _) = ApiCall::build_request(method, url, &parameters, &body, &auth, None)
         .expect("should build request");
 
     let auth_header = request.headers().get("authorization");
@@ -773,7 +775,7 @@ fn test_build_request_with_api_key_auth() {
         key: "secret-key-123".into(),
     });
 
-    let request = ApiCall::build_request(method, url, &parameters, &body, &auth)
+    let (request, _) = ApiCall::build_request(method, url, &parameters, &body, &auth, None)
         .expect("should build request");
 
     let api_key_header = request.headers().get("X-API-Key");
@@ -781,6 +783,94 @@ fn test_build_request_with_api_key_auth() {
     assert_eq!(api_key_header.unwrap(), "secret-key-123");
 }
 
+#[test]
+fn test_build_request_with_api_key_query_auth() {
+    let method = Method::GET;
+    let url: Url = "http://localhost:8080/users?page=1".parse().unwrap();
+    let parameters = CallParameters::default();
+    let body = None;
+    let auth = Some(crate::client::Authentication::api_key(
+        "api_key",
+        "secret key",
+        crate::client::ApiKeyLocation::Query,
+    ));
+
+    let (request, _) = ApiCall::build_request(method, url, &parameters, &body, &auth, None)
+        .expect("should build request");
+
+    insta::assert_snapshot!(request.url(), @"http://localhost:8080/users?page=1&api_key=secret+key");
+}
+
+#[test]
+fn test_build_request_with_api_key_cookie_auth() {
+    let method = Method::GET;
+    let url: Url = "http://localhost:8080/users".parse().unwrap();
+    let cookies = crate::client::CallCookies::new().add_cookie("theme", "dark");
+    let parameters = CallParameters::with_all(CallQuery::default(), None, Some(cookies));
+    let body = None;
+    let auth = Some(crate::client::Authentication::api_key(
+        "session",
+        "secret-key-123",
+        crate::client::ApiKeyLocation::Cookie,
+    ));
+
+    let (request, _) = ApiCall::build_request(method, url, &parameters, &body, &auth, None)
+        .expect("should build request");
+
+    let cookie_header = request
+        .headers()
+        .get("cookie")
+        .expect("should have cookies");
+    assert_eq!(cookie_header, "session=secret-key-123; theme=dark");
+    assert!(cookie_header.is_sensitive());
+}
+
+#[test]
+fn test_build_request_signs_cookies_of_the_jar() {
+    use crate::client::AuthenticationError;
+    use crate::client::cookie_jar::CookieJar;
+    use crate::client::signing::{RequestSigner, SignableRequest};
+
+    /// Signs the request by copying its `Cookie` header.
+    #[derive(Debug)]
+    struct CookieSigner;
+
+    impl RequestSigner for CookieSigner {
+        fn sign(&self, request: &mut SignableRequest<'_>) -> Result<(), AuthenticationError> {
+            if let Some(cookie) = request.headers().get("cookie").cloned() {
+                request.headers_mut().insert("x-signed-cookie", cookie);
+            }
+            Ok(())
+        }
+    }
+
+    let jar = CookieJar::default();
+    let mut set_cookies = http::HeaderMap::new();
+    set_cookies.insert(
+        http::header::SET_COOKIE,
+        http::HeaderValue::from_static("session_id=abc123"),
+    );
+    jar.store(&set_cookies);
+    let url: Url = "http://localhost:8080/users".parse().unwrap();
+    let auth = Some(crate::client::Authentication::signed(CookieSigner));
+
+    let (request, jar_cookies) = ApiCall::build_request(
+        Method::GET,
+        url,
+        &CallParameters::default(),
+        &None,
+        &auth,
+        Some(&jar),
+    )
+    .expect("should build request");
+
+    assert_eq!(jar_cookies, ["session_id"]);
+    assert_eq!(
+        request.headers().get("x-signed-cookie"),
+        Some(&http::HeaderValue::from_static("session_id=abc123"))
+    );
+}
+
 #[test]
 fn test_build_request_without_auth() {
     let method = Method::GET;
@@ -789,7 +879,7 @@ fn test_build_request_without_auth() {
     let body = None;
     let auth = None;
 
-    let request = ApiCall::build_request(method, url, &parameters, &body, &auth)
+    let (request, _) = ApiCall::build_request(method, url, &parameters, &body, &auth, None)
         .expect("should build request");
 
     assert!(request.headers().get("authorization").is_none());
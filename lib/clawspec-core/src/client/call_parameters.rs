@@ -20,6 +20,8 @@ pub(super) struct OperationMetadata {
     pub(super) description: Option<String>,
     #[cfg(feature = "redaction")]
     pub(super) response_description: Option<String>,
+    /// Provider states required by the interaction (for contract testing)
+    pub(super) provider_states: Vec<String>,
 }
 
 impl CallParameters {
@@ -0,0 +1,233 @@
+use std::sync::{Arc, Mutex};
+
+use http::header::{COOKIE, SET_COOKIE};
+use http::{HeaderMap, HeaderValue};
+use indexmap::IndexMap;
+use jiff::Timestamp;
+use jiff::fmt::rfc2822::DateTimeParser;
+
+use super::ApiClientError;
+use super::auth::SecureString;
+
+/// The cookies set by the responses, sent with the later calls.
+///
+/// The jar is shared by the clones of the [`ApiClient`](super::ApiClient).
+/// The cookies are stored by name, regardless of their domain and path,
+/// since the client calls a single server.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CookieJar {
+    cookies: Arc<Mutex<IndexMap<String, SecureString>>>,
+    session_cookie: Option<String>,
+}
+
+impl CookieJar {
+    /// Creates an empty jar, with the name of the cookie holding the session, if any.
+    pub(crate) fn new(session_cookie: Option<String>) -> Self {
+        Self {
+            cookies: Arc::default(),
+            session_cookie,
+        }
+    }
+
+    /// Returns the name of the cookie holding the session, if any.
+    pub(crate) fn session_cookie(&self) -> Option<&str> {
+        self.session_cookie.as_deref()
+    }
+
+    /// Stores the cookies of the `Set-Cookie` response headers.
+    ///
+    /// An expired cookie, with a `Max-Age` of zero or less, or an `Expires` date in the past,
+    /// is removed from the jar. Returns the names of the set cookies.
+    pub(crate) fn store(&self, headers: &HeaderMap) -> Vec<String> {
+        let mut cookies = self.cookies.lock().unwrap_or_else(|e| e.into_inner());
+        let mut names = vec![];
+        for value in headers.get_all(SET_COOKIE) {
+            let Some(cookie) = value.to_str().ok().and_then(SetCookie::parse) else {
+                continue;
+            };
+            if cookie.expired {
+                cookies.shift_remove(&cookie.name);
+            } else {
+                cookies.insert(cookie.name.clone(), cookie.value);
+            }
+            names.push(cookie.name);
+        }
+        names
+    }
+
+    /// Adds the cookies of the jar to the `Cookie` request header.
+    ///
+    /// The cookies already in the header take precedence over the cookies of the jar
+    /// with the same name. Returns the names of the added cookies.
+    pub(crate) fn add_to(&self, headers: &mut HeaderMap) -> Result<Vec<String>, ApiClientError> {
+        let cookies = self.cookies.lock().unwrap_or_else(|e| e.into_inner());
+        let mut cookie_header = headers
+            .get(COOKIE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let sent: Vec<String> = cookie_header
+            .split(';')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(name, _)| name.trim().to_string())
+            .collect();
+
+        let mut names = vec![];
+        for (name, value) in cookies.iter().filter(|(name, _)| !sent.contains(name)) {
+            if !cookie_header.is_empty() {
+                cookie_header.push_str("; ");
+            }
+            cookie_header.push_str(&format!("{name}={}", value.as_str()));
+            names.push(name.clone());
+        }
+        if !names.is_empty() {
+            let mut value = HeaderValue::from_str(&cookie_header)?;
+            value.set_sensitive(true);
+            headers.insert(COOKIE, value);
+        }
+        Ok(names)
+    }
+}
+
+/// A cookie of a `Set-Cookie` header (RFC 6265).
+struct SetCookie {
+    name: String,
+    value: SecureString,
+    expired: bool,
+}
+
+impl SetCookie {
+    fn parse(header: &str) -> Option<Self> {
+        let mut parts = header.split(';');
+        let (name, value) = parts.next()?.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+        // The double quotes are part of the value (RFC 6265, section 4.1.1)
+        let value = value.trim();
+
+        let mut max_age = None;
+        let mut expires = None;
+        for attribute in parts {
+            let (attribute, attribute_value) = attribute.split_once('=').unwrap_or((attribute, ""));
+            let attribute_value = attribute_value.trim();
+            if attribute.trim().eq_ignore_ascii_case("max-age") {
+                max_age = attribute_value.parse::<i64>().ok();
+            } else if attribute.trim().eq_ignore_ascii_case("expires") {
+                expires = DateTimeParser::new()
+                    .relaxed_weekday(true)
+                    .parse_timestamp(attribute_value)
+                    .ok();
+            }
+        }
+        // The `Max-Age` attribute takes precedence over the `Expires` attribute
+        let expired = match (max_age, expires) {
+            (Some(max_age), _) => max_age <= 0,
+            (None, Some(expires)) => expires <= Timestamp::now(),
+            (None, None) => false,
+        };
+
+        Some(Self {
+            name: name.to_string(),
+            value: value.into(),
+            expired,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_cookies(values: &[&str]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for value in values {
+            headers.append(
+                SET_COOKIE,
+                HeaderValue::from_str(value).expect("valid header"),
+            );
+        }
+        headers
+    }
+
+    fn cookie_header(jar: &CookieJar, headers: &mut HeaderMap) -> String {
+        jar.add_to(headers).expect("valid cookies");
+        headers
+            .get(COOKIE)
+            .map(|value| value.to_str().expect("ascii").to_string())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn should_store_and_replay_cookies() {
+        let jar = CookieJar::default();
+
+        let names = jar.store(&set_cookies(&[
+            "session_id=abc123; Path=/; HttpOnly; Secure",
+            "theme=dark; Max-Age=3600",
+        ]));
+
+        assert_eq!(names, ["session_id", "theme"]);
+        let mut headers = HeaderMap::new();
+        insta::assert_snapshot!(cookie_header(&jar, &mut headers), @"session_id=abc123; theme=dark");
+        assert!(headers.get(COOKIE).expect("cookie").is_sensitive());
+    }
+
+    #[test]
+    fn should_keep_the_quotes_of_the_value() {
+        let jar = CookieJar::default();
+
+        jar.store(&set_cookies(&["theme=\"dark\"; Path=/", "empty=\"\""]));
+
+        insta::assert_snapshot!(cookie_header(&jar, &mut HeaderMap::new()), @r#"theme="dark"; empty="""#);
+    }
+
+    #[test]
+    fn should_replace_and_remove_expired_cookies() {
+        let jar = CookieJar::default();
+        jar.store(&set_cookies(&[
+            "session_id=abc123",
+            "theme=dark",
+            "lang=en",
+        ]));
+
+        jar.store(&set_cookies(&[
+            "session_id=def456",
+            "theme=; Max-Age=0",
+            "lang=; Expires=Thu, 01 Jan 1970 00:00:00 GMT",
+        ]));
+
+        insta::assert_snapshot!(cookie_header(&jar, &mut HeaderMap::new()), @"session_id=def456");
+    }
+
+    #[test]
+    fn should_prefer_cookies_of_the_call() {
+        let jar = CookieJar::default();
+        jar.store(&set_cookies(&["session_id=abc123", "theme=dark"]));
+        let mut headers = HeaderMap::new();
+        headers.insert(COOKIE, HeaderValue::from_static("theme=light"));
+
+        let cookies = cookie_header(&jar, &mut headers);
+
+        insta::assert_snapshot!(cookies, @"theme=light; session_id=abc123");
+    }
+
+    #[test]
+    fn should_ignore_invalid_set_cookie() {
+        let jar = CookieJar::default();
+
+        let names = jar.store(&set_cookies(&["no-value", "=anonymous"]));
+
+        assert!(names.is_empty());
+        assert!(cookie_header(&jar, &mut HeaderMap::new()).is_empty());
+    }
+
+    #[test]
+    fn should_redact_debug() {
+        let jar = CookieJar::default();
+        jar.store(&set_cookies(&["session_id=abc123"]));
+
+        insta::assert_snapshot!(format!("{jar:?}"), @r#"CookieJar { cookies: Mutex { data: {"session_id": SecureString { value: "[REDACTED]" }}, poisoned: false, .. }, session_cookie: None }"#);
+    }
+}
@@ -0,0 +1,367 @@
+//! HTTP Digest access authentication (RFC 7616).
+//!
+//! The server answers a first request with a `401 Unauthorized` response, and a
+//! `WWW-Authenticate: Digest ...` challenge. The request is then sent again, with an
+//! `Authorization: Digest ...` header computed from the challenge and the credentials.
+
+use http::HeaderMap;
+use http::header::WWW_AUTHENTICATE;
+use md5::Md5;
+use sha2::{Digest, Sha256};
+
+use super::AuthenticationError;
+use super::signing::hex;
+
+/// The nonce count, the challenge nonce is used once.
+const NONCE_COUNT: &str = "00000001";
+
+/// The hash algorithm of a challenge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Algorithm {
+    Md5,
+    Sha256,
+}
+
+impl Algorithm {
+    fn hash(self, data: &[u8]) -> String {
+        match self {
+            Self::Md5 => hex(&Md5::digest(data)),
+            Self::Sha256 => hex(&Sha256::digest(data)),
+        }
+    }
+}
+
+/// The quality of protection of a challenge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Qop {
+    /// Authentication only.
+    Auth,
+    /// Authentication with integrity protection of the body.
+    AuthInt,
+}
+
+impl Qop {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Auth => "auth",
+            Self::AuthInt => "auth-int",
+        }
+    }
+}
+
+/// A Digest challenge, from a `WWW-Authenticate` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DigestChallenge {
+    realm: String,
+    nonce: String,
+    opaque: Option<String>,
+    algorithm: Algorithm,
+    session: bool,
+    qop: Option<Qop>,
+}
+
+impl DigestChallenge {
+    /// Finds the Digest challenge in the `WWW-Authenticate` headers, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the challenge is invalid, or uses an unsupported algorithm
+    /// or quality of protection.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Result<Option<Self>, AuthenticationError> {
+        headers
+            .get_all(WWW_AUTHENTICATE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .find_map(|value| {
+                let (scheme, params) = value.trim().split_once(' ')?;
+                scheme.eq_ignore_ascii_case("digest").then_some(params)
+            })
+            .map(Self::parse)
+            .transpose()
+    }
+
+    fn parse(params: &str) -> Result<Self, AuthenticationError> {
+        let mut realm = None;
+        let mut nonce = None;
+        let mut opaque = None;
+        let mut algorithm = (Algorithm::Md5, false);
+        let mut qop = None;
+
+        for (name, value) in parse_params(params) {
+            match name.as_str() {
+                "realm" => realm = Some(value),
+                "nonce" => nonce = Some(value),
+                "opaque" => opaque = Some(value),
+                "algorithm" => algorithm = parse_algorithm(&value)?,
+                "qop" => qop = Some(parse_qop(&value)?),
+                _ => {}
+            }
+        }
+
+        let (algorithm, session) = algorithm;
+        Ok(Self {
+            realm: realm.ok_or_else(|| invalid_challenge("missing realm"))?,
+            nonce: nonce.ok_or_else(|| invalid_challenge("missing nonce"))?,
+            opaque,
+            algorithm,
+            session,
+            qop,
+        })
+    }
+
+    /// Computes the `Authorization` header value answering the challenge.
+    ///
+    /// The `uri` is the request target, the path with the query string.
+    pub(crate) fn authorization(
+        &self,
+        username: &str,
+        password: &str,
+        method: &str,
+        uri: &str,
+        body: &[u8],
+        cnonce: &str,
+    ) -> String {
+        let Self {
+            realm,
+            nonce,
+            opaque,
+            algorithm,
+            session,
+            qop,
+        } = self;
+        let hash = |data: String| algorithm.hash(data.as_bytes());
+
+        let mut ha1 = hash(format!("{username}:{realm}:{password}"));
+        if *session {
+            ha1 = hash(format!("{ha1}:{nonce}:{cnonce}"));
+        }
+        let ha2 = match qop {
+            Some(Qop::AuthInt) => hash(format!("{method}:{uri}:{}", algorithm.hash(body))),
+            _ => hash(format!("{method}:{uri}")),
+        };
+        let response = match qop {
+            Some(qop) => hash(format!(
+                "{ha1}:{nonce}:{NONCE_COUNT}:{cnonce}:{}:{ha2}",
+                qop.as_str()
+            )),
+            None => hash(format!("{ha1}:{nonce}:{ha2}")),
+        };
+
+        let algorithm = match (algorithm, session) {
+            (Algorithm::Md5, false) => "MD5",
+            (Algorithm::Md5, true) => "MD5-sess",
+            (Algorithm::Sha256, false) => "SHA-256",
+            (Algorithm::Sha256, true) => "SHA-256-sess",
+        };
+        let mut authorization = format!(
+            "Digest username={}, realm={}, nonce={}, uri={}, algorithm={algorithm}, response=\"{response}\"",
+            quote(username),
+            quote(realm),
+            quote(nonce),
+            quote(uri),
+        );
+        if let Some(qop) = qop {
+            authorization.push_str(&format!(
+                ", qop={}, nc={NONCE_COUNT}, cnonce={}",
+                qop.as_str(),
+                quote(cnonce)
+            ));
+        }
+        if let Some(opaque) = opaque {
+            authorization.push_str(&format!(", opaque={}", quote(opaque)));
+        }
+        authorization
+    }
+}
+
+/// Parses the algorithm, with whether it is a session variant.
+fn parse_algorithm(value: &str) -> Result<(Algorithm, bool), AuthenticationError> {
+    let (name, session) = match value.len().checked_sub(5) {
+        Some(index) if value[index..].eq_ignore_ascii_case("-sess") => (&value[..index], true),
+        _ => (value, false),
+    };
+    if name.eq_ignore_ascii_case("MD5") {
+        Ok((Algorithm::Md5, session))
+    } else if name.eq_ignore_ascii_case("SHA-256") {
+        Ok((Algorithm::Sha256, session))
+    } else {
+        Err(invalid_challenge(format!(
+            "unsupported algorithm '{value}'"
+        )))
+    }
+}
+
+/// Parses the offered qualities of protection, preferring `auth`.
+fn parse_qop(value: &str) -> Result<Qop, AuthenticationError> {
+    let offered: Vec<_> = value.split(',').map(str::trim).collect();
+    if offered.contains(&"auth") {
+        Ok(Qop::Auth)
+    } else if offered.contains(&"auth-int") {
+        Ok(Qop::AuthInt)
+    } else {
+        Err(invalid_challenge(format!(
+            "unsupported quality of protection '{value}'"
+        )))
+    }
+}
+
+/// Parses the comma-separated `name=value` parameters, with optionally quoted values.
+fn parse_params(input: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let mut chars = input.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace() || *c == ',').is_some() {}
+        let name: String = chars.by_ref().take_while(|c| *c != '=').collect();
+        if name.is_empty() {
+            break;
+        }
+        let value = if chars.next_if_eq(&'"').is_some() {
+            let mut value = String::new();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => value.extend(chars.next()),
+                    '"' => break,
+                    c => value.push(c),
+                }
+            }
+            value
+        } else {
+            let value: String = chars.by_ref().take_while(|c| *c != ',').collect();
+            value.trim().to_string()
+        };
+        params.push((name.trim().to_ascii_lowercase(), value));
+    }
+    params
+}
+
+/// Quotes a parameter value.
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn invalid_challenge(message: impl Into<String>) -> AuthenticationError {
+    AuthenticationError::InvalidDigestChallenge {
+        message: message.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+
+    use super::*;
+
+    /// The challenge of the RFC 7616 example, with the algorithm.
+    fn rfc_challenge(algorithm: &str) -> DigestChallenge {
+        let mut headers = HeaderMap::new();
+        let value = format!(
+            r#"Digest realm="http-auth@example.org", qop="auth, auth-int", algorithm={algorithm}, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#
+        );
+        headers.insert(
+            WWW_AUTHENTICATE,
+            HeaderValue::from_str(&value).expect("valid"),
+        );
+        DigestChallenge::from_headers(&headers)
+            .expect("valid challenge")
+            .expect("should find the challenge")
+    }
+
+    fn rfc_authorization(challenge: &DigestChallenge) -> String {
+        challenge.authorization(
+            "Mufasa",
+            "Circle of Life",
+            "GET",
+            "/dir/index.html",
+            b"",
+            "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ",
+        )
+    }
+
+    #[test]
+    fn should_answer_rfc_md5_challenge() {
+        let challenge = rfc_challenge("MD5");
+
+        insta::assert_snapshot!(rfc_authorization(&challenge), @r#"Digest username="Mufasa", realm="http-auth@example.org", nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", uri="/dir/index.html", algorithm=MD5, response="8ca523f5e9506fed4657c9700eebdbec", qop=auth, nc=00000001, cnonce="f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#);
+    }
+
+    #[test]
+    fn should_answer_rfc_sha256_challenge() {
+        let challenge = rfc_challenge("SHA-256");
+
+        insta::assert_snapshot!(rfc_authorization(&challenge), @r#"Digest username="Mufasa", realm="http-auth@example.org", nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", uri="/dir/index.html", algorithm=SHA-256, response="753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1", qop=auth, nc=00000001, cnonce="f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#);
+    }
+
+    #[test]
+    fn should_answer_rfc_2069_challenge() {
+        let challenge = DigestChallenge::parse(
+            r#"realm="testrealm@host.com", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093""#,
+        )
+        .expect("valid challenge");
+
+        let authorization =
+            challenge.authorization("Mufasa", "CircleOfLife", "GET", "/dir/index.html", b"", "");
+
+        insta::assert_snapshot!(authorization, @r#"Digest username="Mufasa", realm="testrealm@host.com", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093", uri="/dir/index.html", algorithm=MD5, response="1949323746fe6a43ef61f9606e7febea""#);
+    }
+
+    #[test]
+    fn should_parse_session_and_auth_int() {
+        let challenge = DigestChallenge::parse(
+            r#"realm="api", nonce="abc", algorithm=sha-256-SESS, qop="auth-int""#,
+        )
+        .expect("valid challenge");
+
+        assert_eq!(challenge.algorithm, Algorithm::Sha256);
+        assert!(challenge.session);
+        assert_eq!(challenge.qop, Some(Qop::AuthInt));
+    }
+
+    #[test]
+    fn should_parse_quoted_values() {
+        let params = parse_params(r#"realm="a, \"quoted\" realm", nonce=abc ,stale=FALSE"#);
+
+        insta::assert_debug_snapshot!(params, @r#"
+        [
+            (
+                "realm",
+                "a, \"quoted\" realm",
+            ),
+            (
+                "nonce",
+                "abc",
+            ),
+            (
+                "stale",
+                "FALSE",
+            ),
+        ]
+        "#);
+    }
+
+    #[test]
+    fn should_ignore_other_challenges() {
+        let mut headers = HeaderMap::new();
+        headers.append(
+            WWW_AUTHENTICATE,
+            HeaderValue::from_static(r#"Basic realm="api""#),
+        );
+
+        let challenge = DigestChallenge::from_headers(&headers).expect("no error");
+
+        assert!(challenge.is_none());
+    }
+
+    #[test]
+    fn should_reject_invalid_challenges() {
+        let missing_nonce = DigestChallenge::parse(r#"realm="api""#).expect_err("no nonce");
+        let algorithm =
+            DigestChallenge::parse(r#"realm="api", nonce="abc", algorithm=SHA-512-256"#)
+                .expect_err("unsupported algorithm");
+        let qop = DigestChallenge::parse(r#"realm="api", nonce="abc", qop="auth-conf""#)
+            .expect_err("unsupported qop");
+
+        insta::assert_snapshot!(missing_nonce, @"Invalid Digest authentication challenge: missing nonce");
+        insta::assert_snapshot!(algorithm, @"Invalid Digest authentication challenge: unsupported algorithm 'SHA-512-256'");
+        insta::assert_snapshot!(qop, @"Invalid Digest authentication challenge: unsupported quality of protection 'auth-conf'");
+    }
+}
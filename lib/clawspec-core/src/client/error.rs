@@ -193,6 +193,21 @@ pub enum ApiClientError {
         body: String,
     },
 
+    /// Several schemas resolve to the same component name.
+    ///
+    /// Occurs when collecting the OpenAPI specification with a
+    /// [`SchemaNamingStrategy`](crate::SchemaNamingStrategy) failing on conflicts.
+    #[display(
+        "Schema name conflict for '{name}' between {types:?}: rename a type with `#[schema(as = ...)]` or with the schema naming strategy"
+    )]
+    #[from(skip)]
+    SchemaNameConflict {
+        /// The conflicting schema name.
+        name: String,
+        /// The Rust types resolving to this name, empty for nested schemas.
+        types: Vec<String>,
+    },
+
     /// JSON redaction operation failed.
     ///
     /// Occurs when applying redactions to JSON responses.
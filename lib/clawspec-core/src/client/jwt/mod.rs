@@ -0,0 +1,314 @@
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use serde_json::{Map, Value};
+
+use super::auth::{AuthenticationError, SecureString};
+
+/// The default lifetime of the minted tokens.
+const DEFAULT_EXPIRES_IN: Duration = Duration::from_secs(3600);
+
+/// A token is minted again when it expires in less than this margin.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(5);
+
+/// Mints JSON Web Tokens locally, to authenticate the requests of a test.
+///
+/// The tokens are signed with `HS256`, `RS256`, or `ES256`, from a test key.
+/// The `iat`, `exp`, and `jti` claims are set when a token is minted, unless set with
+/// [`with_claim`](Self::with_claim). A token is reused until it expires, then minted again.
+///
+/// Use it with [`Authentication::jwt`](crate::Authentication::jwt), the operations are
+/// documented with a `bearer` security scheme with the `JWT` bearer format.
+///
+/// This type requires the `jwt` feature to be enabled.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use clawspec_core::{ApiClient, Authentication, JwtBuilder};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let jwt = JwtBuilder::hs256("test-secret")
+///     .with_subject("user-123")
+///     .with_issuer("https://auth.example.com")
+///     .with_audience("my-api")
+///     .with_claim("roles", vec!["admin"])
+///     .with_expires_in(Duration::from_secs(300));
+///
+/// let client = ApiClient::builder()
+///     .with_authentication(Authentication::jwt(jwt))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(derive_more::Debug)]
+pub struct JwtBuilder {
+    algorithm: Algorithm,
+    #[debug(skip)]
+    key: EncodingKey,
+    key_id: Option<String>,
+    claims: Map<String, Value>,
+    expires_in: Duration,
+    #[debug(skip)]
+    token: Mutex<Option<MintedToken>>,
+}
+
+/// A minted token, with its expiration time in seconds since the Unix epoch.
+struct MintedToken {
+    token: SecureString,
+    expires_at: u64,
+}
+
+impl JwtBuilder {
+    fn new(algorithm: Algorithm, key: EncodingKey) -> Self {
+        Self {
+            algorithm,
+            key,
+            key_id: None,
+            claims: Map::new(),
+            expires_in: DEFAULT_EXPIRES_IN,
+            token: Mutex::new(None),
+        }
+    }
+
+    /// Creates a builder signing the tokens with `HS256`, and the shared secret.
+    pub fn hs256(secret: impl Into<SecureString>) -> Self {
+        let secret = secret.into();
+        Self::new(
+            Algorithm::HS256,
+            EncodingKey::from_secret(secret.as_str().as_bytes()),
+        )
+    }
+
+    /// Creates a builder signing the tokens with `RS256`, and the PEM encoded RSA private key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the PEM is not a valid RSA private key.
+    pub fn rs256(private_key_pem: impl AsRef<[u8]>) -> Result<Self, AuthenticationError> {
+        let key = EncodingKey::from_rsa_pem(private_key_pem.as_ref()).map_err(jwt_error)?;
+        Ok(Self::new(Algorithm::RS256, key))
+    }
+
+    /// Creates a builder signing the tokens with `ES256`, and the PEM encoded P-256 private key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the PEM is not a valid EC private key.
+    pub fn es256(private_key_pem: impl AsRef<[u8]>) -> Result<Self, AuthenticationError> {
+        let key = EncodingKey::from_ec_pem(private_key_pem.as_ref()).map_err(jwt_error)?;
+        Ok(Self::new(Algorithm::ES256, key))
+    }
+
+    /// Sets the subject (`sub`) claim.
+    pub fn with_subject(self, subject: impl Into<String>) -> Self {
+        self.with_claim("sub", subject.into())
+    }
+
+    /// Sets the issuer (`iss`) claim.
+    pub fn with_issuer(self, issuer: impl Into<String>) -> Self {
+        self.with_claim("iss", issuer.into())
+    }
+
+    /// Sets the audience (`aud`) claim.
+    pub fn with_audience(self, audience: impl Into<String>) -> Self {
+        self.with_claim("aud", audience.into())
+    }
+
+    /// Sets a claim, replacing the previous value.
+    ///
+    /// Setting the `iat`, `exp`, or `jti` claim replaces the value set when minting a token.
+    pub fn with_claim(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.claims.insert(name.into(), value.into());
+        self
+    }
+
+    /// Sets the lifetime of the minted tokens, one hour by default.
+    pub fn with_expires_in(mut self, expires_in: Duration) -> Self {
+        self.expires_in = expires_in;
+        self
+    }
+
+    /// Sets the key ID (`kid`) header of the tokens.
+    pub fn with_key_id(mut self, key_id: impl Into<String>) -> Self {
+        self.key_id = Some(key_id.into());
+        self
+    }
+
+    /// Returns the name of the signing algorithm, like `HS256`.
+    pub fn algorithm(&self) -> String {
+        format!("{:?}", self.algorithm)
+    }
+
+    /// Returns a token, minting a new one if the previous one is about to expire.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the token cannot be signed.
+    pub fn token(&self) -> Result<SecureString, AuthenticationError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        let mut cached = self.token.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(minted) = cached.as_ref()
+            && now.as_secs() + EXPIRY_MARGIN.as_secs() < minted.expires_at
+        {
+            return Ok(minted.token.clone());
+        }
+
+        let minted = self.mint(now)?;
+        let token = minted.token.clone();
+        *cached = Some(minted);
+        Ok(token)
+    }
+
+    fn mint(&self, now: Duration) -> Result<MintedToken, AuthenticationError> {
+        let issued_at = now.as_secs();
+        let mut claims = self.claims.clone();
+        claims.entry("iat").or_insert(issued_at.into());
+        claims
+            .entry("exp")
+            .or_insert((issued_at + self.expires_in.as_secs()).into());
+        claims
+            .entry("jti")
+            .or_insert(uuid::Uuid::new_v4().to_string().into());
+        let expires_at = claims.get("exp").and_then(Value::as_u64).unwrap_or(0);
+
+        let mut header = Header::new(self.algorithm);
+        header.kid.clone_from(&self.key_id);
+        let token = jsonwebtoken::encode(&header, &claims, &self.key).map_err(jwt_error)?;
+
+        Ok(MintedToken {
+            token: token.into(),
+            expires_at,
+        })
+    }
+}
+
+fn jwt_error(error: jsonwebtoken::errors::Error) -> AuthenticationError {
+    AuthenticationError::JwtError {
+        message: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonwebtoken::{DecodingKey, Validation};
+
+    use super::*;
+
+    const RSA_PRIVATE_KEY: &str = include_str!("testdata/rsa_private.pem");
+    const RSA_PUBLIC_KEY: &str = include_str!("testdata/rsa_public.pem");
+    const EC_PRIVATE_KEY: &str = include_str!("testdata/ec_private.pem");
+    const EC_PUBLIC_KEY: &str = include_str!("testdata/ec_public.pem");
+
+    fn decode(token: &SecureString, key: &DecodingKey, algorithm: Algorithm) -> Map<String, Value> {
+        let mut validation = Validation::new(algorithm);
+        validation.set_audience(&["my-api"]);
+        let mut data = jsonwebtoken::decode::<Map<String, Value>>(token.as_str(), key, &validation)
+            .expect("valid token");
+        for claim in ["iat", "exp", "jti"] {
+            assert!(data.claims.remove(claim).is_some(), "missing {claim}");
+        }
+        data.claims
+    }
+
+    fn builder(builder: JwtBuilder) -> JwtBuilder {
+        builder
+            .with_subject("user-123")
+            .with_issuer("https://auth.example.com")
+            .with_audience("my-api")
+            .with_claim("roles", vec!["admin"])
+    }
+
+    #[test]
+    fn should_mint_hs256_token() {
+        let jwt = builder(JwtBuilder::hs256("test-secret")).with_key_id("key-1");
+
+        let token = jwt.token().expect("token");
+
+        let header = jsonwebtoken::decode_header(token.as_str()).expect("header");
+        assert_eq!(header.alg, Algorithm::HS256);
+        assert_eq!(header.kid.as_deref(), Some("key-1"));
+        let claims = decode(
+            &token,
+            &DecodingKey::from_secret(b"test-secret"),
+            Algorithm::HS256,
+        );
+        insta::assert_snapshot!(Value::Object(claims), @r#"{"aud":"my-api","iss":"https://auth.example.com","roles":["admin"],"sub":"user-123"}"#);
+    }
+
+    #[test]
+    fn should_mint_rs256_token() {
+        let jwt = builder(JwtBuilder::rs256(RSA_PRIVATE_KEY).expect("RSA key"));
+
+        let token = jwt.token().expect("token");
+
+        let key = DecodingKey::from_rsa_pem(RSA_PUBLIC_KEY.as_bytes()).expect("RSA public key");
+        let claims = decode(&token, &key, Algorithm::RS256);
+        insta::assert_snapshot!(Value::Object(claims), @r#"{"aud":"my-api","iss":"https://auth.example.com","roles":["admin"],"sub":"user-123"}"#);
+    }
+
+    #[test]
+    fn should_mint_es256_token() {
+        let jwt = builder(JwtBuilder::es256(EC_PRIVATE_KEY).expect("EC key"));
+
+        let token = jwt.token().expect("token");
+
+        let key = DecodingKey::from_ec_pem(EC_PUBLIC_KEY.as_bytes()).expect("EC public key");
+        let claims = decode(&token, &key, Algorithm::ES256);
+        insta::assert_snapshot!(Value::Object(claims), @r#"{"aud":"my-api","iss":"https://auth.example.com","roles":["admin"],"sub":"user-123"}"#);
+    }
+
+    #[test]
+    fn should_reject_invalid_keys() {
+        let error = JwtBuilder::rs256("not a key").expect_err("invalid RSA key");
+        assert!(matches!(error, AuthenticationError::JwtError { .. }));
+
+        let error = JwtBuilder::es256(RSA_PRIVATE_KEY).expect_err("not an EC key");
+        assert!(matches!(error, AuthenticationError::JwtError { .. }));
+    }
+
+    #[test]
+    fn should_reuse_token_until_expired() {
+        let jwt = JwtBuilder::hs256("test-secret");
+        let token = jwt.token().expect("token");
+        assert_eq!(jwt.token().expect("token"), token);
+
+        let jwt = JwtBuilder::hs256("test-secret").with_expires_in(Duration::ZERO);
+        let token = jwt.token().expect("token");
+        assert_ne!(jwt.token().expect("token"), token);
+    }
+
+    #[test]
+    fn should_set_expiration() {
+        let jwt = JwtBuilder::hs256("test-secret").with_expires_in(Duration::from_secs(300));
+
+        let token = jwt.token().expect("token");
+
+        let data = jsonwebtoken::decode::<Map<String, Value>>(
+            token.as_str(),
+            &DecodingKey::from_secret(b"test-secret"),
+            &Validation::new(Algorithm::HS256),
+        )
+        .expect("valid token");
+        let issued_at = data.claims["iat"].as_u64().expect("iat");
+        let expires_at = data.claims["exp"].as_u64().expect("exp");
+        assert_eq!(expires_at - issued_at, 300);
+    }
+
+    #[test]
+    fn should_redact_debug() {
+        let jwt = JwtBuilder::hs256("test-secret").with_subject("user-123");
+        let _ = jwt.token().expect("token");
+
+        let debug = format!("{jwt:?}");
+
+        assert!(!debug.contains("test-secret"));
+        insta::assert_snapshot!(debug, @r#"JwtBuilder { algorithm: HS256, key_id: None, claims: {"sub": String("user-123")}, expires_in: 3600s, .. }"#);
+    }
+}
@@ -1488,15 +1488,688 @@ mod client_tests {
         let components = openapi.components.as_ref().expect("should have components");
         assert!(components.security_schemes.contains_key("oauth2"));
     }
+
+    #[cfg(feature = "oauth2")]
+    #[tokio::test]
+    async fn should_use_per_call_oauth2_scopes() {
+        use wiremock::matchers::body_string_contains;
+
+        let mock_server = MockServer::start().await;
+        for (scope, token) in [
+            ("read%3Ausers", "read-token"),
+            ("admin%3Ausers", "admin-token"),
+        ] {
+            Mock::given(method("POST"))
+                .and(path("/oauth/token"))
+                .and(body_string_contains(format!("scope={scope}")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "access_token": token,
+                    "token_type": "Bearer",
+                    "expires_in": 3600
+                })))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/users"))
+            .and(header("Authorization", "Bearer read-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/users/1"))
+            .and(header("Authorization", "Bearer admin-token"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let token_url = format!("{}/oauth/token", mock_server.uri());
+        let uri: http::Uri = mock_server.uri().parse().expect("valid URI");
+        let mut client = ApiClient::builder()
+            .with_host(uri.host().expect("should have host"))
+            .with_port(uri.port_u16().expect("should have port"))
+            .with_security_scheme(
+                "corpAuth",
+                SecurityScheme::OAuth2 {
+                    flows: Box::new(OAuth2Flows::client_credentials(
+                        &token_url,
+                        [("read:users", "Read user data")],
+                    )),
+                    description: None,
+                },
+            )
+            .with_oauth2_client_credentials("client-id", "secret", &token_url)
+            .expect("should configure OAuth2")
+            .build()
+            .expect("should build client");
+
+        for _ in 0..2 {
+            client
+                .get("/users")
+                .expect("should create call")
+                .with_oauth2_scopes(["read:users"])
+                .await
+                .expect("should succeed")
+                .as_empty()
+                .await
+                .expect("should complete");
+        }
+        client
+            .delete("/users/1")
+            .expect("should create call")
+            .with_oauth2_scopes(["admin:users"])
+            .await
+            .expect("should succeed")
+            .as_empty()
+            .await
+            .expect("should complete");
+
+        let openapi = client.collected_openapi().await;
+        let value = serde_json::to_value(&openapi).expect("should serialize");
+        insta::assert_snapshot!(value["paths"]["/users"]["get"]["security"], @r#"[{"corpAuth":["read:users"]}]"#);
+        insta::assert_snapshot!(value["paths"]["/users/1"]["delete"]["security"], @r#"[{"corpAuth":["admin:users"]}]"#);
+        insta::assert_snapshot!(
+            value["components"]["securitySchemes"]["corpAuth"]["flows"]["clientCredentials"]["scopes"],
+            @r#"{"admin:users":"","read:users":"Read user data"}"#
+        );
+    }
+
+    #[cfg(feature = "oauth2")]
+    #[tokio::test]
+    async fn should_reject_oauth2_scopes_without_oauth2_authentication() {
+        let mock_server = MockServer::start().await;
+        let client = client_for_mock(&mock_server).await;
+
+        let result = client
+            .get("/users")
+            .expect("should create call")
+            .with_oauth2_scopes(["read:users"])
+            .await;
+
+        let error = result.expect_err("should fail without OAuth2");
+        insta::assert_snapshot!(error, @"OAuth2 error: OAuth2 scopes require an OAuth2 authentication");
+    }
+
+    #[cfg(feature = "oauth2")]
+    #[tokio::test]
+    async fn should_reject_oauth2_scopes_with_pre_acquired_token() {
+        let mock_server = MockServer::start().await;
+        let uri: http::Uri = mock_server.uri().parse().expect("valid URI");
+        let client = ApiClient::builder()
+            .with_host(uri.host().expect("should have host"))
+            .with_port(uri.port_u16().expect("should have port"))
+            .with_oauth2_token("my-token")
+            .expect("should configure OAuth2")
+            .build()
+            .expect("should build client");
+
+        let result = client
+            .get("/users")
+            .expect("should create call")
+            .with_oauth2_scopes(["read:users"])
+            .await;
+
+        let error = result.expect_err("should fail with a pre-acquired token");
+        insta::assert_snapshot!(error, @"OAuth2 error: OAuth2 scopes cannot be requested with a pre-acquired token");
+    }
 }
 
-// =============================================================================
-// Tests for security.rs - Security scheme configurations
-// =============================================================================
+// =============================================================================
+// Tests for security.rs - Security scheme configurations
+// =============================================================================
+
+mod security_tests {
+    use super::*;
+    use crate::client::security::{OAuth2Flow, OAuth2Flows, OAuth2ImplicitFlow};
+
+    #[tokio::test]
+    async fn should_infer_security_from_authentication() {
+        use crate::client::Authentication;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .mount(&mock_server)
+            .await;
+
+        let uri: http::Uri = mock_server.uri().parse().expect("valid URI");
+        let mut client = ApiClient::builder()
+            .with_host(uri.host().expect("should have host"))
+            .with_port(uri.port_u16().expect("should have port"))
+            .with_authentication(Authentication::Bearer("token".into()))
+            .with_security_inference()
+            .build()
+            .expect("should build client");
+
+        let calls = [
+            ("/users", None),
+            (
+                "/admin",
+                Some(Authentication::Basic {
+                    username: "admin".to_string(),
+                    password: "secret".into(),
+                }),
+            ),
+            (
+                "/reports",
+                Some(Authentication::ApiKey {
+                    header_name: "X-API-Key".to_string(),
+                    key: "key".into(),
+                }),
+            ),
+        ];
+        for (path, authentication) in calls {
+            let call = client.get(path).expect("should create call");
+            let call = match authentication {
+                Some(authentication) => call.with_authentication(authentication),
+                None => call,
+            };
+            call.await
+                .expect("should succeed")
+                .as_empty()
+                .await
+                .expect("should complete");
+        }
+        client
+            .get("/health")
+            .expect("should create call")
+            .with_authentication_none()
+            .await
+            .expect("should succeed")
+            .as_empty()
+            .await
+            .expect("should complete");
+
+        let openapi = client.collected_openapi().await;
+        let value = serde_json::to_value(&openapi).expect("should serialize");
+        insta::assert_snapshot!(value["paths"]["/users"]["get"]["security"], @r#"[{"bearerAuth":[]}]"#);
+        insta::assert_snapshot!(value["paths"]["/admin"]["get"]["security"], @r#"[{"basicAuth":[]}]"#);
+        insta::assert_snapshot!(value["paths"]["/reports"]["get"]["security"], @r#"[{"xApiKey":[]}]"#);
+        insta::assert_snapshot!(value["paths"]["/health"]["get"]["security"], @"[]");
+        insta::assert_snapshot!(value["components"]["securitySchemes"], @r#"{"basicAuth":{"scheme":"basic","type":"http"},"bearerAuth":{"scheme":"bearer","type":"http"},"xApiKey":{"in":"header","name":"X-API-Key","type":"apiKey"}}"#);
+    }
+
+    #[tokio::test]
+    async fn should_send_api_key_in_query_and_cookie() {
+        use crate::client::Authentication;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/users"))
+            .and(query_param("api_key", "secret-key-12345"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/reports"))
+            .and(header("Cookie", "session=secret-key-12345"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let uri: http::Uri = mock_server.uri().parse().expect("valid URI");
+        let mut client = ApiClient::builder()
+            .with_host(uri.host().expect("should have host"))
+            .with_port(uri.port_u16().expect("should have port"))
+            .with_authentication(Authentication::api_key(
+                "api_key",
+                "secret-key-12345",
+                ApiKeyLocation::Query,
+            ))
+            .with_security_inference()
+            .build()
+            .expect("should build client");
+
+        client
+            .get("/users")
+            .expect("should create call")
+            .await
+            .expect("should succeed")
+            .as_empty()
+            .await
+            .expect("should complete");
+        client
+            .get("/reports")
+            .expect("should create call")
+            .with_authentication(Authentication::api_key(
+                "session",
+                "secret-key-12345",
+                ApiKeyLocation::Cookie,
+            ))
+            .await
+            .expect("should succeed")
+            .as_empty()
+            .await
+            .expect("should complete");
+
+        let openapi = client.collected_openapi().await;
+        let value = serde_json::to_value(&openapi).expect("should serialize");
+        assert!(!value.to_string().contains("secret-key-12345"));
+        insta::assert_snapshot!(value["components"]["securitySchemes"], @r#"{"apiKey":{"in":"query","name":"api_key","type":"apiKey"},"session":{"in":"cookie","name":"session","type":"apiKey"}}"#);
+        let mappings = client.collected_wiremock_mappings().await;
+        let mappings = serde_json::to_string(&mappings).expect("should serialize");
+        assert!(!mappings.contains("secret-key-12345"));
+    }
+
+    #[tokio::test]
+    async fn should_mask_query_api_key_in_errors() {
+        use crate::client::Authentication;
+
+        // Nothing listens on the port once the listener is dropped
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("should bind");
+        let port = listener.local_addr().expect("should have address").port();
+        drop(listener);
+        let client = ApiClient::builder()
+            .with_host("127.0.0.1")
+            .with_port(port)
+            .with_authentication(Authentication::api_key(
+                "api_key",
+                "secret-key-12345",
+                ApiKeyLocation::Query,
+            ))
+            .build()
+            .expect("should build client");
+
+        let error = client
+            .get("/users")
+            .expect("should create call")
+            .await
+            .expect_err("should fail to connect");
+
+        let message = error.to_string();
+        assert!(!message.contains("secret-key-12345"), "{message}");
+        assert!(message.contains("/users?api_key=secr...2345"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn should_answer_digest_challenge() {
+        use crate::client::Authentication;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(|request: &wiremock::Request| {
+                request
+                    .headers
+                    .get("authorization")
+                    .and_then(|value| value.to_str().ok())
+                    .is_some_and(|value| {
+                        value.starts_with(
+                            r#"Digest username="admin", realm="device", nonce="abc123""#,
+                        ) && value.contains("qop=auth, nc=00000001")
+                            && value.ends_with(r#"opaque="xyz""#)
+                    })
+            })
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"status": "ok"})))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(401).insert_header(
+                "WWW-Authenticate",
+                r#"Digest realm="device", qop="auth", nonce="abc123", opaque="xyz""#,
+            ))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let uri: http::Uri = mock_server.uri().parse().expect("valid URI");
+        let mut client = ApiClient::builder()
+            .with_host(uri.host().expect("should have host"))
+            .with_port(uri.port_u16().expect("should have port"))
+            .with_authentication(Authentication::Digest {
+                username: "admin".to_string(),
+                password: "secret".into(),
+            })
+            .build()
+            .expect("should build client");
+
+        client
+            .get("/device/status")
+            .expect("should create call")
+            .with_success_only()
+            .await
+            .expect("should answer the challenge")
+            .as_empty()
+            .await
+            .expect("should complete");
+        client
+            .get("/device/config")
+            .expect("should create call")
+            .with_digest_challenge()
+            .await
+            .expect("should answer the challenge")
+            .as_empty()
+            .await
+            .expect("should complete");
+
+        let openapi = client.collected_openapi().await;
+        let value = serde_json::to_value(&openapi).expect("should serialize");
+        insta::assert_snapshot!(value["components"]["securitySchemes"], @r#"{"digestAuth":{"scheme":"digest","type":"http"}}"#);
+        insta::assert_snapshot!(value["security"], @r#"[{"digestAuth":[]}]"#);
+        let status_responses = value["paths"]["/device/status"]["get"]["responses"]
+            .as_object()
+            .expect("should have responses");
+        let config_responses = value["paths"]["/device/config"]["get"]["responses"]
+            .as_object()
+            .expect("should have responses");
+        insta::assert_debug_snapshot!(status_responses.keys().collect::<Vec<_>>(), @r#"
+        [
+            "200",
+        ]
+        "#);
+        insta::assert_debug_snapshot!(config_responses.keys().collect::<Vec<_>>(), @r#"
+        [
+            "200",
+            "401",
+        ]
+        "#);
+    }
+
+    #[tokio::test]
+    async fn should_sign_requests() {
+        use std::time::{Duration, SystemTime};
+
+        use wiremock::matchers::header_exists;
+
+        use crate::client::{Authentication, HmacSigner};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/orders"))
+            .and(header("X-Timestamp", "1700000000"))
+            .and(header_exists("X-Signature"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({"id": 1})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let signer = HmacSigner::new("partner-42", "shared-secret")
+            .with_signed_headers(["content-type"])
+            .with_fixed_time(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000));
+        let uri: http::Uri = mock_server.uri().parse().expect("valid URI");
+        let mut client = ApiClient::builder()
+            .with_host(uri.host().expect("should have host"))
+            .with_port(uri.port_u16().expect("should have port"))
+            .with_authentication(Authentication::signed(signer))
+            .with_security_inference()
+            .build()
+            .expect("should build client");
+
+        client
+            .post("/orders")
+            .expect("should create call")
+            .json(&json!({"item": "book"}))
+            .expect("should set body")
+            .with_expected_status_codes(ExpectedStatusCodes::from_single(201))
+            .await
+            .expect("should succeed")
+            .as_empty()
+            .await
+            .expect("should complete");
+
+        let openapi = client.collected_openapi().await;
+        let value = serde_json::to_value(&openapi).expect("should serialize");
+        insta::assert_snapshot!(value["paths"]["/orders"]["post"]["security"], @r#"[{"hmacSignature":[]}]"#);
+        insta::assert_snapshot!(value["components"]["securitySchemes"]["hmacSignature"], @r#"{"description":"HMAC-SHA256 signature of the method, the path and query, the `X-Timestamp` header, the signed headers (`content-type`) and the SHA-256 digest of the body","in":"header","name":"X-Signature","type":"apiKey"}"#);
+    }
+
+    #[cfg(feature = "jwt")]
+    #[tokio::test]
+    async fn should_authenticate_with_minted_jwt() {
+        use wiremock::matchers::header_regex;
+
+        use crate::client::{Authentication, JwtBuilder};
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/me"))
+            .and(header_regex(
+                "Authorization",
+                r"^Bearer [\w-]+\.[\w-]+\.[\w-]+$",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"sub": "user-123"})))
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+
+        let jwt = JwtBuilder::hs256("test-secret").with_subject("user-123");
+        let uri: http::Uri = mock_server.uri().parse().expect("valid URI");
+        let mut client = ApiClient::builder()
+            .with_host(uri.host().expect("should have host"))
+            .with_port(uri.port_u16().expect("should have port"))
+            .with_authentication(Authentication::jwt(jwt))
+            .with_security_inference()
+            .build()
+            .expect("should build client");
+
+        for _ in 0..2 {
+            client
+                .get("/me")
+                .expect("should create call")
+                .await
+                .expect("should succeed")
+                .as_json::<serde_json::Value>()
+                .await
+                .expect("should complete");
+        }
+
+        let openapi = client.collected_openapi().await;
+        let value = serde_json::to_value(&openapi).expect("should serialize");
+        insta::assert_snapshot!(value["paths"]["/me"]["get"]["security"], @r#"[{"bearerAuth":[]}]"#);
+        insta::assert_snapshot!(value["components"]["securitySchemes"]["bearerAuth"], @r#"{"bearerFormat":"JWT","scheme":"bearer","type":"http"}"#);
+    }
+
+    #[tokio::test]
+    async fn should_replay_session_cookie_from_cookie_jar() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/login"))
+            .respond_with(
+                ResponseTemplate::new(204)
+                    .append_header("Set-Cookie", "theme=dark")
+                    .append_header("Set-Cookie", "session_id=abc123; Path=/; HttpOnly"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/profile"))
+            .and(header("Cookie", "theme=dark; session_id=abc123"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"name": "Alice"})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/logout"))
+            .and(header("Cookie", "theme=dark; session_id=abc123"))
+            .respond_with(
+                ResponseTemplate::new(204).append_header("Set-Cookie", "session_id=; Max-Age=0"),
+            )
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/status"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"status": "ok"})))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let uri: http::Uri = mock_server.uri().parse().expect("valid URI");
+        let mut client = ApiClient::builder()
+            .with_host(uri.host().expect("should have host"))
+            .with_port(uri.port_u16().expect("should have port"))
+            .with_cookie_jar()
+            .with_session_cookie("session_id")
+            .with_security_inference()
+            .build()
+            .expect("should build client");
+
+        for (method, path) in [
+            (http::Method::POST, "/login"),
+            (http::Method::GET, "/profile"),
+            (http::Method::POST, "/logout"),
+            (http::Method::GET, "/status"),
+        ] {
+            client
+                .call(method, path.into())
+                .expect("should create call")
+                .await
+                .expect("should succeed")
+                .as_raw()
+                .await
+                .expect("should complete");
+        }
+
+        let received = mock_server
+            .received_requests()
+            .await
+            .expect("should record requests");
+        // Without the session cookie, the call stays public
+        insta::assert_snapshot!(received[3].headers["Cookie"].to_str().expect("ascii"), @"theme=dark");
+
+        let openapi = client.collected_openapi().await;
+        let value = serde_json::to_value(&openapi).expect("should serialize");
+        insta::assert_snapshot!(value["paths"]["/login"]["post"]["responses"]["204"], @r#"{"description":"Status code 204","headers":{"Set-Cookie":{"description":"Sets the `theme`, `session_id` cookies","schema":{"type":"string"}}}}"#);
+        assert!(value["paths"]["/login"]["post"]["security"].is_null());
+        insta::assert_snapshot!(value["paths"]["/profile"]["get"]["security"], @r#"[{"sessionId":[]}]"#);
+        insta::assert_snapshot!(value["paths"]["/logout"]["post"]["responses"]["204"], @r#"{"description":"Status code 204","headers":{"Set-Cookie":{"description":"Sets the `session_id` cookie","schema":{"type":"string"}}}}"#);
+        assert!(value["paths"]["/status"]["get"]["security"].is_null());
+        insta::assert_snapshot!(value["components"]["securitySchemes"], @r#"{"sessionId":{"in":"cookie","name":"session_id","type":"apiKey"}}"#);
+    }
+
+    #[tokio::test]
+    async fn should_prefer_explicit_security_over_inference() {
+        use crate::client::Authentication;
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .mount(&mock_server)
+            .await;
+
+        let uri: http::Uri = mock_server.uri().parse().expect("valid URI");
+        let mut client = ApiClient::builder()
+            .with_host(uri.host().expect("should have host"))
+            .with_port(uri.port_u16().expect("should have port"))
+            .with_authentication(Authentication::Bearer("token".into()))
+            .with_security_scheme(
+                "bearerAuth",
+                SecurityScheme::bearer_with_format("JWT").with_description("User token"),
+            )
+            .with_security_inference()
+            .build()
+            .expect("should build client");
+
+        client
+            .get("/users")
+            .expect("should create call")
+            .with_security(SecurityRequirement::new("partnerAuth"))
+            .await
+            .expect("should succeed")
+            .as_empty()
+            .await
+            .expect("should complete");
+        client
+            .get("/me")
+            .expect("should create call")
+            .await
+            .expect("should succeed")
+            .as_empty()
+            .await
+            .expect("should complete");
+
+        let openapi = client.collected_openapi().await;
+        let value = serde_json::to_value(&openapi).expect("should serialize");
+        insta::assert_snapshot!(value["paths"]["/users"]["get"]["security"], @r#"[{"partnerAuth":[]}]"#);
+        insta::assert_snapshot!(value["paths"]["/me"]["get"]["security"], @r#"[{"bearerAuth":[]}]"#);
+        insta::assert_snapshot!(value["components"]["securitySchemes"]["bearerAuth"], @r#"{"bearerFormat":"JWT","description":"User token","scheme":"bearer","type":"http"}"#);
+    }
 
-mod security_tests {
-    use super::*;
-    use crate::client::security::{OAuth2Flow, OAuth2Flows, OAuth2ImplicitFlow};
+    #[tokio::test]
+    async fn should_not_infer_public_operation_from_failed_call() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&mock_server)
+            .await;
+
+        let uri: http::Uri = mock_server.uri().parse().expect("valid URI");
+        let mut client = ApiClient::builder()
+            .with_host(uri.host().expect("should have host"))
+            .with_port(uri.port_u16().expect("should have port"))
+            .with_authentication(crate::client::Authentication::Bearer("token".into()))
+            .with_security_inference()
+            .build()
+            .expect("should build client");
+
+        client
+            .get("/users")
+            .expect("should create call")
+            .with_authentication_none()
+            .with_expected_status_codes(ExpectedStatusCodes::from_single(401))
+            .await
+            .expect("should succeed")
+            .as_empty()
+            .await
+            .expect("should complete");
+
+        let openapi = client.collected_openapi().await;
+        let value = serde_json::to_value(&openapi).expect("should serialize");
+        insta::assert_snapshot!(value["paths"]["/users"]["get"]["security"], @"null");
+    }
+
+    #[cfg(feature = "oauth2")]
+    #[tokio::test]
+    async fn should_infer_oauth2_client_credentials_security() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth/token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "access_token": "token",
+                "token_type": "Bearer",
+                "expires_in": 3600
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .mount(&mock_server)
+            .await;
+
+        let token_url = format!("{}/oauth/token", mock_server.uri());
+        let uri: http::Uri = mock_server.uri().parse().expect("valid URI");
+        let mut client = ApiClient::builder()
+            .with_host(uri.host().expect("should have host"))
+            .with_port(uri.port_u16().expect("should have port"))
+            .with_oauth2_client_credentials("client-id", "secret", &token_url)
+            .expect("should configure OAuth2")
+            .with_security_inference()
+            .build()
+            .expect("should build client");
+
+        client
+            .get("/users")
+            .expect("should create call")
+            .with_oauth2_scopes(["read:users"])
+            .await
+            .expect("should succeed")
+            .as_empty()
+            .await
+            .expect("should complete");
+
+        let openapi = client.collected_openapi().await;
+        let value = serde_json::to_value(&openapi).expect("should serialize");
+        insta::assert_snapshot!(value["paths"]["/users"]["get"]["security"], @r#"[{"oauth2":["read:users"]}]"#);
+        let flows = &value["components"]["securitySchemes"]["oauth2"]["flows"];
+        insta::assert_snapshot!(flows["clientCredentials"]["scopes"], @r#"{"read:users":""}"#);
+    }
 
     #[tokio::test]
     async fn should_convert_all_api_key_locations() {
@@ -1975,3 +2648,448 @@ mod content_type_tests {
         assert!(text.contains("<html>"));
     }
 }
+
+// =============================================================================
+// Tests for the exports of the recorded calls
+// =============================================================================
+
+mod export_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_export_wiremock_mappings() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("POST"))
+            .and(path("/users"))
+            .and(query_param("notify", "true"))
+            .and(header("X-Request-Id", "req-001"))
+            .and(body_json(
+                json!({"name": "Alice", "email": "alice@example.com"}),
+            ))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .insert_header("x-trace-id", "trace-001")
+                    .set_body_json(json!({"id": 1, "name": "Alice", "email": "alice@example.com"})),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let mut client = client_for_mock(&mock_server).await;
+
+        let _: User = client
+            .post("/users")
+            .expect("should create call")
+            .with_header("X-Request-Id", "req-001")
+            .with_query(crate::client::CallQuery::new().add_param("notify", true))
+            .json(&CreateUserRequest {
+                name: "Alice".to_string(),
+                email: "alice@example.com".to_string(),
+            })
+            .expect("should serialize body")
+            .with_expected_status_code(http::StatusCode::CREATED)
+            .await
+            .expect("request should succeed")
+            .as_json()
+            .await
+            .expect("should parse JSON");
+
+        let mappings = client.collected_wiremock_mappings().await;
+
+        let json = serde_json::to_string_pretty(&mappings).expect("should serialize");
+        insta::assert_snapshot!(json, @r#"
+        {
+          "mappings": [
+            {
+              "name": "post-users-201",
+              "request": {
+                "method": "POST",
+                "urlPathPattern": "/users",
+                "queryParameters": {
+                  "notify": {
+                    "equalTo": "true"
+                  }
+                },
+                "headers": {
+                  "X-Request-Id": {
+                    "equalTo": "req-001"
+                  }
+                },
+                "bodyPatterns": [
+                  {
+                    "equalToJson": {
+                      "email": "alice@example.com",
+                      "name": "Alice"
+                    },
+                    "ignoreExtraElements": true
+                  }
+                ]
+              },
+              "response": {
+                "status": 201,
+                "headers": {
+                  "x-trace-id": "trace-001",
+                  "content-type": "application/json"
+                },
+                "jsonBody": {
+                  "email": "alice@example.com",
+                  "id": 1,
+                  "name": "Alice"
+                }
+              }
+            }
+          ]
+        }
+        "#);
+    }
+
+    #[tokio::test]
+    async fn should_export_path_templates_and_deduplicate_calls() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/users/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 1, "name": "John", "email": "john@example.com"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let mut client = client_for_mock(&mock_server).await;
+
+        for _ in 0..2 {
+            let _: User = client
+                .call(
+                    http::Method::GET,
+                    CallPath::from("/users/{id}").add_param("id", 1),
+                )
+                .expect("should create call")
+                .await
+                .expect("request should succeed")
+                .as_json()
+                .await
+                .expect("should parse JSON");
+        }
+
+        let mappings = client.collected_wiremock_mappings().await;
+
+        assert_eq!(mappings.len(), 1);
+        let mapping = &mappings.mappings[0];
+        assert_eq!(mapping.name, "get-users-id-200");
+        assert_eq!(mapping.request.url_path_pattern, "/users/[^/]+");
+        assert_eq!(mapping.response.status, 200);
+    }
+
+    #[tokio::test]
+    async fn should_export_different_responses_in_a_scenario() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/users/1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .append_header("Set-Cookie", "a=1")
+                    .append_header("Set-Cookie", "b=2")
+                    .set_body_json(json!({"id": 1, "name": "John", "email": "john@example.com"})),
+            )
+            .up_to_n_times(1)
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/users/1"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&mock_server)
+            .await;
+
+        let mut client = client_for_mock(&mock_server).await;
+
+        for _ in 0..3 {
+            client
+                .call(
+                    http::Method::GET,
+                    CallPath::from("/users/{id}").add_param("id", 1),
+                )
+                .expect("should create call")
+                .await
+                .expect("request should succeed")
+                .as_raw()
+                .await
+                .expect("should read body");
+        }
+
+        let mappings = client.collected_wiremock_mappings().await;
+
+        let json = serde_json::to_string_pretty(&mappings).expect("should serialize");
+        insta::assert_snapshot!(json, @r#"
+        {
+          "mappings": [
+            {
+              "name": "get-users-id-200",
+              "request": {
+                "method": "GET",
+                "urlPathPattern": "/users/[^/]+"
+              },
+              "response": {
+                "status": 200,
+                "headers": {
+                  "set-cookie": [
+                    "a=[REDACTED]",
+                    "b=[REDACTED]"
+                  ],
+                  "content-type": "application/json"
+                },
+                "jsonBody": {
+                  "email": "john@example.com",
+                  "id": 1,
+                  "name": "John"
+                }
+              },
+              "scenarioName": "get-users-id-200",
+              "requiredScenarioState": "Started",
+              "newScenarioState": "get-users-id-200-2"
+            },
+            {
+              "name": "get-users-id-404",
+              "request": {
+                "method": "GET",
+                "urlPathPattern": "/users/[^/]+"
+              },
+              "response": {
+                "status": 404
+              },
+              "scenarioName": "get-users-id-200",
+              "requiredScenarioState": "get-users-id-200-2"
+            }
+          ]
+        }
+        "#);
+    }
+
+    #[tokio::test]
+    async fn should_not_export_credentials_in_stubs() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/me"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .append_header("Set-Cookie", "session_id=abc123; Path=/; HttpOnly")
+                    .set_body_json(json!({"ok": true})),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let mut client = client_for_mock(&mock_server).await;
+
+        client
+            .get("/me")
+            .expect("should create call")
+            .with_header("Authorization", "Bearer secret-token")
+            .with_header("X-Api-Key", "secret-key")
+            .with_header("X-Request-Id", "req-001")
+            .await
+            .expect("request should succeed")
+            .as_raw()
+            .await
+            .expect("should read body");
+
+        let mappings = client.collected_wiremock_mappings().await;
+        let json = serde_json::to_string(&mappings).expect("should serialize");
+        assert!(!json.contains("secret-token"));
+        assert!(!json.contains("secret-key"));
+        assert!(!json.contains("abc123"));
+
+        let mapping = &mappings.mappings[0];
+        let headers = serde_json::to_string(&mapping.request.headers).expect("should serialize");
+        insta::assert_snapshot!(headers, @r#"{"Authorization":{"matches":".+"},"X-Api-Key":{"matches":".+"},"X-Request-Id":{"equalTo":"req-001"}}"#);
+        let headers = serde_json::to_string(&mapping.response.headers).expect("should serialize");
+        insta::assert_snapshot!(headers, @r#"{"set-cookie":"session_id=[REDACTED]; Path=/; HttpOnly","content-type":"application/json"}"#);
+
+        let pact = client.collected_pact("consumer", "provider").await;
+        let headers =
+            serde_json::to_string(&pact.interactions[0].request.headers).expect("should serialize");
+        insta::assert_snapshot!(headers, @r#"{"X-Request-Id":"req-001"}"#);
+    }
+
+    #[tokio::test]
+    async fn should_keep_similar_names_distinct() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"ok": true})))
+            .mount(&mock_server)
+            .await;
+
+        let mut client = client_for_mock(&mock_server).await;
+
+        for path in ["/users", "/user", "/users"] {
+            client
+                .get(path)
+                .expect("should create call")
+                .with_query(crate::client::CallQuery::new().add_param("page", path.len()))
+                .await
+                .expect("request should succeed")
+                .as_raw()
+                .await
+                .expect("should read body");
+        }
+
+        let mappings = client.collected_wiremock_mappings().await;
+        let names: Vec<_> = mappings.mappings.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["get-users-200", "get-user-200"]);
+
+        let pact = client.collected_pact("consumer", "provider").await;
+        let descriptions: Vec<_> = pact
+            .interactions
+            .iter()
+            .map(|i| i.description.as_str())
+            .collect();
+        insta::assert_debug_snapshot!(descriptions, @r#"
+        [
+            "Retrieve users",
+            "Retrieve user",
+        ]
+        "#);
+    }
+
+    #[tokio::test]
+    async fn should_export_pact_with_provider_states_and_matching_rules() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/users/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 1, "name": "John", "email": "john@example.com"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let mut client = client_for_mock(&mock_server).await;
+        let _: User = client
+            .call(
+                http::Method::GET,
+                CallPath::from("/users/{id}").add_param("id", 1),
+            )
+            .expect("should create call")
+            .with_provider_state("user 1 exists")
+            .await
+            .expect("request should succeed")
+            .as_json()
+            .await
+            .expect("should parse JSON");
+
+        let pact = client.collected_pact("frontend", "user-service").await;
+
+        let json = serde_json::to_string_pretty(&pact).expect("should serialize");
+        insta::assert_snapshot!(json, @r#"
+        {
+          "consumer": {
+            "name": "frontend"
+          },
+          "interactions": [
+            {
+              "description": "Retrieve user by ID",
+              "providerStates": [
+                {
+                  "name": "user 1 exists"
+                }
+              ],
+              "request": {
+                "method": "GET",
+                "path": "/users/1"
+              },
+              "response": {
+                "body": {
+                  "email": "john@example.com",
+                  "id": 1,
+                  "name": "John"
+                },
+                "headers": {
+                  "Content-Type": "application/json"
+                },
+                "matchingRules": {
+                  "body": {
+                    "$.email": {
+                      "combine": "AND",
+                      "matchers": [
+                        {
+                          "match": "type"
+                        }
+                      ]
+                    },
+                    "$.id": {
+                      "combine": "AND",
+                      "matchers": [
+                        {
+                          "match": "integer"
+                        }
+                      ]
+                    },
+                    "$.name": {
+                      "combine": "AND",
+                      "matchers": [
+                        {
+                          "match": "type"
+                        }
+                      ]
+                    }
+                  }
+                },
+                "status": 200
+              }
+            }
+          ],
+          "metadata": {
+            "pactSpecification": {
+              "version": "3.0.0"
+            }
+          },
+          "provider": {
+            "name": "user-service"
+          }
+        }
+        "#);
+    }
+
+    #[cfg(feature = "mock-server")]
+    #[tokio::test]
+    async fn should_replay_recorded_calls_with_mock_server() {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/users/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 1, "name": "John", "email": "john@example.com"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let mut client = client_for_mock(&mock_server).await;
+        let _: User = client
+            .call(
+                http::Method::GET,
+                CallPath::from("/users/{id}").add_param("id", 1),
+            )
+            .expect("should create call")
+            .await
+            .expect("request should succeed")
+            .as_json()
+            .await
+            .expect("should parse JSON");
+
+        let stub = client
+            .mock_server()
+            .await
+            .start()
+            .await
+            .expect("should start mock server");
+
+        let user: User = reqwest::get(format!("{}/users/42", stub.base_url()))
+            .await
+            .expect("should call mock server")
+            .json()
+            .await
+            .expect("should parse JSON");
+        assert_eq!(user.name, "John");
+    }
+}
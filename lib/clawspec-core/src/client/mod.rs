@@ -1,12 +1,14 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 use std::mem;
 
 use http::{Method, Uri};
-use utoipa::openapi::{Components, Info, OpenApi, Paths, Server, Tag};
+use utoipa::openapi::extensions::Extensions;
+use utoipa::openapi::{Components, Info, OpenApi, Paths, RefOr, Schema, Server, Tag};
 
 mod builder;
 use crate::client::openapi::channel::{CollectorHandle, CollectorMessage};
 use crate::client::openapi::schema::Schemas;
+use crate::export::{Pact, WireMockMappings};
 
 pub use self::builder::ApiClientBuilder;
 
@@ -32,7 +34,23 @@ pub use self::auth::{Authentication, AuthenticationError, SecureString};
 #[cfg(feature = "oauth2")]
 pub mod oauth2;
 #[cfg(feature = "oauth2")]
-pub use self::oauth2::{OAuth2Config, OAuth2ConfigBuilder, OAuth2Error, OAuth2Token};
+pub use self::oauth2::{
+    OAuth2Config, OAuth2ConfigBuilder, OAuth2Error, OAuth2Login, OAuth2LoginFuture,
+    OAuth2LoginHook, OAuth2Token,
+};
+
+mod cookie_jar;
+use self::cookie_jar::CookieJar;
+
+mod digest;
+
+#[cfg(feature = "jwt")]
+mod jwt;
+#[cfg(feature = "jwt")]
+pub use self::jwt::JwtBuilder;
+
+mod signing;
+pub use self::signing::{HmacSigner, RequestSigner, SigV4Signer, SignableRequest};
 
 mod security;
 pub use self::security::{
@@ -44,7 +62,11 @@ mod call_parameters;
 
 mod openapi;
 // CallResult, RawResult, and RawBody are public API, but CalledOperation and Collectors are internal
-pub use self::openapi::{CallResult, RawBody, RawResult};
+pub(crate) use self::openapi::operation_parameters;
+pub use self::openapi::{
+    CallResult, RawBody, RawResult, ReusableComponent, ReusableComponents, SchemaNamingStrategy,
+    SchemaType,
+};
 
 mod error;
 pub use self::error::ApiClientError;
@@ -103,6 +125,12 @@ pub struct ApiClient {
     authentication: Option<Authentication>,
     security_schemes: IndexMap<String, SecurityScheme>,
     default_security: Vec<SecurityRequirement>,
+    infer_security: bool,
+    cookie_jar: Option<CookieJar>,
+    schema_naming: SchemaNamingStrategy,
+    reusable_components: Option<ReusableComponents>,
+    tags: IndexMap<String, Tag>,
+    tag_groups: IndexMap<String, Vec<String>>,
 }
 
 // Create
@@ -196,12 +224,88 @@ impl ApiClient {
     /// - Auto-generated tags based on path patterns
     /// - Deduplicated and sorted alphabetically
     ///
+    /// The tags declared with [`ApiClientBuilder::with_tag`] come first, with their
+    /// description and external docs, and the tag groups declared with
+    /// [`ApiClientBuilder::with_tag_group`] are added as the `x-tagGroups` extension.
+    ///
     /// # Performance Notes
     ///
     /// - This method acquires read locks on internal collections
     /// - Schema processing is cached to avoid redundant work
     /// - Tags are computed on-demand from operation metadata
+    ///
+    /// # Schema Naming
+    ///
+    /// Component schemas are named with the [`SchemaNamingStrategy`] of the client.
+    /// Conflicts are resolved with a fallback name. When the strategy fails on conflicts,
+    /// or when [`SchemaNamingStrategy::last_segments`] names distinct types alike, they
+    /// are logged as errors: use [`try_collected_openapi`](Self::try_collected_openapi)
+    /// to get them as errors.
     pub async fn collected_openapi(&mut self) -> OpenApi {
+        // Falls back to a more lenient naming, then to the default naming
+        let fallbacks = [
+            self.schema_naming.lenient(),
+            SchemaNamingStrategy::default(),
+        ];
+        let mut result = self.try_collected_openapi().await;
+        for naming in fallbacks {
+            match result {
+                Ok(openapi) => return openapi,
+                Err(error) => {
+                    tracing::error!(%error, "Cannot generate the OpenAPI specification, retrying with a lenient schema naming");
+                    result = self.openapi_with_naming(&naming).await;
+                }
+            }
+        }
+        result.unwrap_or_default()
+    }
+
+    /// Generates the OpenAPI specification, failing on schema name conflicts.
+    ///
+    /// Same as [`collected_openapi`](Self::collected_openapi), but when the
+    /// [`SchemaNamingStrategy`] fails on conflicts, conflicts are returned as errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ApiClientError::SchemaNameConflict`] when two schemas resolve to the
+    /// same name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use clawspec_core::{ApiClient, SchemaNamingStrategy};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = ApiClient::builder()
+    ///     .with_schema_naming(SchemaNamingStrategy::short_name().fail_on_conflict())
+    ///     .build()?;
+    ///
+    /// let openapi = client.try_collected_openapi().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn try_collected_openapi(&mut self) -> Result<OpenApi, ApiClientError> {
+        let naming = self.schema_naming.clone();
+        self.openapi_with_naming(&naming).await
+    }
+
+    async fn openapi_with_naming(
+        &mut self,
+        naming: &SchemaNamingStrategy,
+    ) -> Result<OpenApi, ApiClientError> {
+        let collectors = self.collector_handle.get_collectors().await;
+        let named = collectors.named_schemas(naming)?;
+        mem::drop(collectors);
+
+        let mut openapi = self.build_openapi(named.schemas).await;
+        openapi::rename_schemas(&mut openapi, &named.renames)?;
+        if let Some(reusable_components) = &self.reusable_components {
+            reusable_components.apply(&mut openapi);
+        }
+        Ok(openapi)
+    }
+
+    async fn build_openapi(&mut self, schemas: Vec<(String, RefOr<Schema>)>) -> OpenApi {
         let mut builder = OpenApi::builder();
 
         // Add API info if configured
@@ -219,10 +323,27 @@ impl ApiClient {
 
         // Add components with schemas and security schemes
         let collectors = self.collector_handle.get_collectors().await;
-        let mut components_builder = Components::builder().schemas_from_iter(collectors.schemas());
+        let mut components_builder = Components::builder().schemas_from_iter(schemas);
 
-        // Add security schemes to components
-        for (name, scheme) in &self.security_schemes {
+        // Add security schemes to components, after the declared ones the inferred ones,
+        // with the OAuth2 scopes used by the operations
+        let mut security_schemes = self.security_schemes.clone();
+        let mut used_scopes: HashMap<String, BTreeSet<String>> = HashMap::new();
+        for operation in collectors.operations() {
+            if let Some((name, scheme)) = &operation.inferred_security_scheme {
+                security_schemes
+                    .entry(name.clone())
+                    .or_insert_with(|| scheme.clone());
+            }
+            for (name, scopes) in operation.security_scopes() {
+                used_scopes.entry(name).or_default().extend(scopes);
+            }
+        }
+        for (name, scheme) in &security_schemes {
+            let mut scheme = scheme.clone();
+            if let Some(scopes) = used_scopes.get(name) {
+                scheme.add_oauth2_scopes(scopes);
+            }
             components_builder = components_builder.security_scheme(name, scheme.to_utoipa());
         }
 
@@ -253,10 +374,133 @@ impl ApiClient {
             builder.security(Some(security))
         };
 
-        builder.build()
+        let mut openapi = builder.build();
+
+        // Add tag groups, for Redoc-style renderers
+        if !self.tag_groups.is_empty() {
+            let groups: Vec<_> = self
+                .tag_groups
+                .iter()
+                .map(|(name, tags)| serde_json::json!({"name": name, "tags": tags}))
+                .collect();
+            openapi
+                .extensions
+                .get_or_insert_with(Extensions::default)
+                .insert("x-tagGroups".to_string(), serde_json::Value::Array(groups));
+        }
+
+        openapi
+    }
+
+    /// Exports the collected calls as [WireMock](https://wiremock.org/) stub mappings.
+    ///
+    /// Each recorded call becomes a mapping matching the method, the path template,
+    /// the query parameters, the explicitly set headers and the JSON request body.
+    /// The response carries the recorded status, headers and body; when the body was
+    /// redacted, the redacted value is used.
+    ///
+    /// The credentials are not exported: the request headers and query parameters
+    /// holding them, like `Authorization` or an API key, only need to be present, and
+    /// the values of the sensitive response headers, like `Set-Cookie`, are redacted.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use clawspec_core::ApiClient;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = ApiClient::builder().with_port(8080).build()?;
+    /// client.get("/users")?.await?.as_empty().await?;
+    ///
+    /// let mappings = client.collected_wiremock_mappings().await;
+    /// let json = serde_json::to_string_pretty(&mappings)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn collected_wiremock_mappings(&mut self) -> WireMockMappings {
+        let collectors = self.collector_handle.get_collectors().await;
+        collectors.wiremock_mappings(&self.base_path)
+    }
+
+    /// Exports the collected calls as a [Pact](https://docs.pact.io/) contract.
+    ///
+    /// Each recorded call becomes an interaction with the concrete request and the
+    /// recorded (redacted when available) response. Matching rules are derived from the
+    /// `ToSchema` schemas, so that the provider is verified against the types rather than
+    /// the literal example values. Provider states are set with
+    /// [`ApiCall::with_provider_state`].
+    ///
+    /// The request headers holding credentials, like `Authorization` or `Cookie`, are
+    /// left out of the interactions.
+    ///
+    /// The returned pact uses the [`PactSpecification::V3`](crate::export::PactSpecification)
+    /// format by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use clawspec_core::ApiClient;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// // Point the client at the mock provider
+    /// let mut client = ApiClient::builder().with_port(1234).build()?;
+    /// client
+    ///     .get("/users/42")?
+    ///     .with_provider_state("user 42 exists")
+    ///     .await?
+    ///     .as_empty()
+    ///     .await?;
+    ///
+    /// let pact = client.collected_pact("frontend", "user-service").await;
+    /// let json = serde_json::to_string_pretty(&pact)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn collected_pact(
+        &mut self,
+        consumer: impl Into<String>,
+        provider: impl Into<String>,
+    ) -> Pact {
+        let collectors = self.collector_handle.get_collectors().await;
+        let mut pact = Pact::new(consumer, provider);
+        pact.interactions = collectors.pact_interactions(&self.base_path);
+        pact
+    }
+
+    /// Creates a [`MockServer`](crate::mock::MockServer) replaying the collected calls.
+    ///
+    /// The stubs are built from the [collected OpenAPI specification](Self::collected_openapi),
+    /// and the actual response bodies received during the calls are used as examples
+    /// for responses that don't document one.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use clawspec_core::ApiClient;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = ApiClient::builder().with_port(8080).build()?;
+    /// client.get("/users")?.await?.as_empty().await?;
+    ///
+    /// let server = client.mock_server().await.start().await?;
+    /// println!("Mock server listening on {}", server.base_url());
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "mock-server")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "mock-server")))]
+    pub async fn mock_server(&mut self) -> crate::mock::MockServer {
+        let spec = self.collected_openapi().await;
+        let server = crate::mock::MockServer::from_openapi(&spec);
+
+        let collectors = self.collector_handle.get_collectors().await;
+        collectors.add_recorded_examples(&self.base_path, server)
     }
 
     /// Computes the list of unique tags from all collected operations.
+    ///
+    /// The tags declared with [`ApiClientBuilder::with_tag`] come first, in declaration
+    /// order, followed by the other tags sorted alphabetically.
     async fn compute_tags(&self, collectors: &openapi::Collectors) -> Vec<Tag> {
         let mut tag_names = BTreeSet::new();
 
@@ -269,8 +513,15 @@ impl ApiClient {
             }
         }
 
-        // Convert to Tag objects
-        tag_names.into_iter().map(Tag::new).collect()
+        // Convert to Tag objects, after the declared ones
+        let mut tags: Vec<_> = self.tags.values().cloned().collect();
+        tags.extend(
+            tag_names
+                .into_iter()
+                .filter(|name| !self.tags.contains_key(name))
+                .map(Tag::new),
+        );
+        tags
     }
 
     /// Manually registers a type in the schema collection.
@@ -322,14 +573,14 @@ impl ApiClient {
 
 impl ApiClient {
     pub fn call(&self, method: Method, path: CallPath) -> Result<ApiCall, ApiClientError> {
-        // Convert default_security to Option only if not empty
-        let default_security = if self.default_security.is_empty() {
+        // Convert default_security to Option only if not empty, and not superseded by inference
+        let default_security = if self.default_security.is_empty() || self.infer_security {
             None
         } else {
             Some(self.default_security.clone())
         };
 
-        ApiCall::build(
+        let call = ApiCall::build(
             self.client.clone(),
             self.base_uri.clone(),
             self.collector_handle.sender(),
@@ -337,7 +588,28 @@ impl ApiClient {
             path,
             self.authentication.clone(),
             default_security,
-        )
+        )?;
+        let call = ApiCall {
+            infer_security: self.infer_security,
+            cookie_jar: self.cookie_jar.clone(),
+            ..call
+        };
+
+        // Record the OAuth2 scopes on the declared OAuth2 scheme
+        #[cfg(feature = "oauth2")]
+        let call = match self
+            .security_schemes
+            .iter()
+            .find(|(_, scheme)| matches!(scheme, SecurityScheme::OAuth2 { .. }))
+        {
+            Some((name, _)) => ApiCall {
+                oauth2_scheme: name.clone(),
+                ..call
+            },
+            None => call,
+        };
+
+        Ok(call)
     }
 
     pub fn get(&self, path: impl Into<CallPath>) -> Result<ApiCall, ApiClientError> {
@@ -7,8 +7,9 @@ use std::time::Duration;
 use url::Url;
 
 use super::error::OAuth2Error;
+use super::login::OAuth2Login;
 use super::token::{OAuth2Token, TokenCache};
-use crate::client::SecureString;
+use crate::client::{OAuth2Flow, OAuth2Flows, SecureString, SecurityScheme};
 
 /// Default threshold for token refresh (60 seconds before expiry).
 const DEFAULT_REFRESH_THRESHOLD: Duration = Duration::from_secs(60);
@@ -20,6 +21,10 @@ pub enum OAuth2GrantType {
     ClientCredentials,
     /// Pre-acquired token (externally obtained).
     PreAcquired,
+    /// Authorization Code grant with PKCE, the login step being performed headlessly.
+    AuthorizationCode,
+    /// Resource Owner Password Credentials grant (legacy identity providers).
+    Password,
 }
 
 /// OAuth2 authentication configuration.
@@ -33,8 +38,16 @@ pub struct OAuth2Config {
     pub(crate) client_secret: Option<SecureString>,
     /// Token endpoint URL.
     pub(crate) token_url: Url,
-    /// Authorization endpoint URL (optional, for documentation).
+    /// Authorization endpoint URL (required for authorization_code).
     pub(crate) auth_url: Option<Url>,
+    /// Redirect URL (required for authorization_code).
+    pub(crate) redirect_url: Option<Url>,
+    /// Login step (required for authorization_code).
+    pub(crate) login: Option<OAuth2Login>,
+    /// Resource owner username (required for password).
+    pub(crate) username: Option<String>,
+    /// Resource owner password (required for password).
+    pub(crate) password: Option<SecureString>,
     /// Requested scopes.
     pub(crate) scopes: Vec<String>,
     /// Grant type.
@@ -71,21 +84,131 @@ impl OAuth2Config {
             .with_grant_type(OAuth2GrantType::PreAcquired))
     }
 
-    /// Checks if a new token should be acquired.
+    /// Creates a builder for the Authorization Code flow with PKCE.
+    ///
+    /// The token is acquired without a browser: the authorization URL is followed,
+    /// the `login` step is performed, then the code received on the redirect URL is
+    /// exchanged. A client secret is only needed for confidential clients.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use clawspec_core::{OAuth2Config, OAuth2Login};
+    ///
+    /// # fn example() -> Result<(), clawspec_core::OAuth2Error> {
+    /// let config = OAuth2Config::authorization_code(
+    ///     "client-id",
+    ///     "http://localhost:8080/authorize",
+    ///     "http://localhost:8080/token",
+    ///     "http://localhost:3000/callback",
+    ///     OAuth2Login::form([("username", "alice"), ("password", "secret")]),
+    /// )?
+    /// .add_scope("read:users")
+    /// .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn authorization_code(
+        client_id: impl Into<String>,
+        auth_url: impl AsRef<str>,
+        token_url: impl AsRef<str>,
+        redirect_url: impl AsRef<str>,
+        login: OAuth2Login,
+    ) -> Result<OAuth2ConfigBuilder, OAuth2Error> {
+        let redirect_url =
+            Url::parse(redirect_url.as_ref()).map_err(|e| OAuth2Error::ConfigurationError {
+                reason: format!("Invalid redirect URL: {e}"),
+            })?;
+        let mut builder = OAuth2ConfigBuilder::new(client_id, token_url)?
+            .with_auth_url(auth_url)?
+            .with_grant_type(OAuth2GrantType::AuthorizationCode);
+        builder.redirect_url = Some(redirect_url);
+        builder.login = Some(login);
+        Ok(builder)
+    }
+
+    /// Creates a builder for the Resource Owner Password Credentials flow.
+    ///
+    /// This flow is deprecated by OAuth 2.1, but still used by legacy identity
+    /// providers. A client secret is only needed for confidential clients.
+    pub fn password(
+        client_id: impl Into<String>,
+        token_url: impl AsRef<str>,
+        username: impl Into<String>,
+        password: impl Into<SecureString>,
+    ) -> Result<OAuth2ConfigBuilder, OAuth2Error> {
+        let mut builder = OAuth2ConfigBuilder::new(client_id, token_url)?
+            .with_grant_type(OAuth2GrantType::Password);
+        builder.username = Some(username.into());
+        builder.password = Some(password.into());
+        Ok(builder)
+    }
+
+    /// Returns the security scheme documenting this configuration, if any.
+    ///
+    /// Only the Authorization Code flow is documented, as
+    /// [`OAuth2Flows::authorization_code`](crate::OAuth2Flows::authorization_code).
+    pub fn security_scheme(&self) -> Option<SecurityScheme> {
+        if self.grant_type != OAuth2GrantType::AuthorizationCode {
+            return None;
+        }
+        self.flows().map(|flows| SecurityScheme::OAuth2 {
+            flows: Box::new(flows),
+            description: None,
+        })
+    }
+
+    /// Returns `true` if the token is pre-acquired, and cannot be requested with scopes.
+    pub(crate) fn is_pre_acquired(&self) -> bool {
+        matches!(self.grant_type, OAuth2GrantType::PreAcquired)
+    }
+
+    /// Returns the OpenAPI flows of the grant, `None` for a pre-acquired token.
+    pub(crate) fn flows(&self) -> Option<OAuth2Flows> {
+        let token_url = self.token_url.as_str();
+        let scopes = self.scopes.iter().map(|scope| (scope.as_str(), ""));
+        match self.grant_type {
+            OAuth2GrantType::AuthorizationCode => {
+                let auth_url = self.auth_url.as_ref()?;
+                Some(OAuth2Flows::authorization_code(
+                    auth_url.as_str(),
+                    token_url,
+                    scopes,
+                ))
+            }
+            OAuth2GrantType::ClientCredentials => {
+                Some(OAuth2Flows::client_credentials(token_url, scopes))
+            }
+            OAuth2GrantType::Password => Some(OAuth2Flows {
+                password: Some(OAuth2Flow {
+                    authorization_url: None,
+                    token_url: token_url.to_string(),
+                    refresh_url: None,
+                    scopes: scopes
+                        .map(|(scope, description)| (scope.to_string(), description.to_string()))
+                        .collect(),
+                }),
+                ..Default::default()
+            }),
+            OAuth2GrantType::PreAcquired => None,
+        }
+    }
+
+    /// Checks if a new token should be acquired for the configured scopes.
     pub async fn needs_token(&self) -> bool {
         self.token_cache
-            .should_refresh(self.refresh_threshold)
+            .should_refresh(&self.scopes, self.refresh_threshold)
             .await
     }
 
-    /// Gets the cached token if available and not expired.
+    /// Gets the cached token for the configured scopes if available and not expired.
     pub async fn get_token(&self) -> Option<OAuth2Token> {
-        self.token_cache.get().await
+        self.token_cache.get(&self.scopes).await
     }
 
-    /// Stores a token in the cache.
+    /// Stores a token for the configured scopes in the cache.
     pub async fn set_token(&self, token: OAuth2Token) {
-        self.token_cache.set(token).await;
+        self.token_cache.set(&self.scopes, token).await;
     }
 }
 
@@ -99,6 +222,10 @@ impl fmt::Debug for OAuth2Config {
             )
             .field("token_url", &self.token_url)
             .field("auth_url", &self.auth_url)
+            .field("redirect_url", &self.redirect_url)
+            .field("login", &self.login)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "[REDACTED]"))
             .field("scopes", &self.scopes)
             .field("grant_type", &self.grant_type)
             .field("auto_refresh", &self.auto_refresh)
@@ -114,6 +241,10 @@ pub struct OAuth2ConfigBuilder {
     client_secret: Option<SecureString>,
     token_url: Url,
     auth_url: Option<Url>,
+    redirect_url: Option<Url>,
+    login: Option<OAuth2Login>,
+    username: Option<String>,
+    password: Option<SecureString>,
     scopes: Vec<String>,
     grant_type: OAuth2GrantType,
     auto_refresh: bool,
@@ -138,6 +269,10 @@ impl OAuth2ConfigBuilder {
             client_secret: None,
             token_url,
             auth_url: None,
+            redirect_url: None,
+            login: None,
+            username: None,
+            password: None,
             scopes: Vec::new(),
             grant_type: OAuth2GrantType::ClientCredentials,
             auto_refresh: true,
@@ -153,7 +288,7 @@ impl OAuth2ConfigBuilder {
         self
     }
 
-    /// Sets the authorization URL (optional, for documentation).
+    /// Sets the authorization URL (required for authorization_code).
     pub fn with_auth_url(mut self, auth_url: impl AsRef<str>) -> Result<Self, OAuth2Error> {
         let url = Url::parse(auth_url.as_ref()).map_err(|e| OAuth2Error::ConfigurationError {
             reason: format!("Invalid authorization URL: {e}"),
@@ -184,6 +319,9 @@ impl OAuth2ConfigBuilder {
     }
 
     /// Sets whether to automatically refresh tokens.
+    ///
+    /// When enabled (the default), a cached token about to expire is exchanged for a
+    /// new one with its refresh token, if any. Otherwise a new token is acquired.
     #[must_use]
     pub fn with_auto_refresh(mut self, auto_refresh: bool) -> Self {
         self.auto_refresh = auto_refresh;
@@ -213,8 +351,14 @@ impl OAuth2ConfigBuilder {
             });
         }
 
+        if self.grant_type == OAuth2GrantType::AuthorizationCode && self.auth_url.is_none() {
+            return Err(OAuth2Error::ConfigurationError {
+                reason: "Authorization code flow requires an authorization URL".to_string(),
+            });
+        }
+
         let token_cache = if let Some(token) = self.pre_acquired_token {
-            TokenCache::with_token(token)
+            TokenCache::with_token(&self.scopes, token)
         } else {
             TokenCache::new()
         };
@@ -224,6 +368,10 @@ impl OAuth2ConfigBuilder {
             client_secret: self.client_secret,
             token_url: self.token_url,
             auth_url: self.auth_url,
+            redirect_url: self.redirect_url,
+            login: self.login,
+            username: self.username,
+            password: self.password,
             scopes: self.scopes,
             grant_type: self.grant_type,
             auto_refresh: self.auto_refresh,
@@ -305,6 +453,99 @@ mod tests {
         assert_eq!(config.grant_type, OAuth2GrantType::PreAcquired);
     }
 
+    #[test]
+    fn should_create_authorization_code_config() {
+        let config = OAuth2Config::authorization_code(
+            "client-id",
+            "https://auth.example.com/authorize",
+            "https://auth.example.com/token",
+            "http://localhost:3000/callback",
+            OAuth2Login::form([("username", "alice"), ("password", "secret")]),
+        )
+        .expect("Should create builder")
+        .build()
+        .expect("Should build config without client secret");
+
+        assert_eq!(config.grant_type, OAuth2GrantType::AuthorizationCode);
+        assert_eq!(
+            config.redirect_url.as_ref().map(Url::as_str),
+            Some("http://localhost:3000/callback")
+        );
+        assert!(config.login.is_some());
+    }
+
+    #[test]
+    fn should_only_document_authorization_code_scheme() {
+        let authorization_code = OAuth2Config::authorization_code(
+            "client-id",
+            "https://auth.example.com/authorize",
+            "https://auth.example.com/token",
+            "http://localhost:3000/callback",
+            OAuth2Login::callback(|url| async move { Ok(url) }),
+        )
+        .expect("Should create builder")
+        .add_scope("read:users")
+        .build()
+        .expect("Should build config");
+        let client_credentials = OAuth2Config::client_credentials(
+            "client-id",
+            "secret",
+            "https://auth.example.com/token",
+        )
+        .expect("Should create builder")
+        .build()
+        .expect("Should build config");
+
+        let scheme = authorization_code
+            .security_scheme()
+            .expect("Should document the scheme");
+
+        let SecurityScheme::OAuth2 { flows, .. } = scheme else {
+            panic!("Expected an OAuth2 scheme");
+        };
+        let flow = flows.authorization_code.expect("Should have the flow");
+        assert_eq!(
+            flow.authorization_url.as_deref(),
+            Some("https://auth.example.com/authorize")
+        );
+        assert!(flow.scopes.contains_key("read:users"));
+        assert!(client_credentials.security_scheme().is_none());
+    }
+
+    #[test]
+    fn should_reject_invalid_redirect_url() {
+        let result = OAuth2Config::authorization_code(
+            "client-id",
+            "https://auth.example.com/authorize",
+            "https://auth.example.com/token",
+            "not-a-url",
+            OAuth2Login::form([("username", "alice")]),
+        );
+
+        assert!(matches!(
+            result,
+            Err(OAuth2Error::ConfigurationError { .. })
+        ));
+    }
+
+    #[test]
+    fn should_create_password_config() {
+        let config = OAuth2Config::password(
+            "client-id",
+            "https://auth.example.com/token",
+            "alice",
+            "super-secret",
+        )
+        .expect("Should create builder")
+        .build()
+        .expect("Should build config without client secret");
+
+        assert_eq!(config.grant_type, OAuth2GrantType::Password);
+        assert_eq!(config.username.as_deref(), Some("alice"));
+        let debug_str = format!("{config:?}");
+        assert!(!debug_str.contains("super-secret"));
+    }
+
     #[test]
     fn should_reject_invalid_token_url() {
         let result = OAuth2ConfigBuilder::new("client-id", "not-a-url");
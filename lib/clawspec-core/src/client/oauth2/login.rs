@@ -0,0 +1,372 @@
+//! Headless login step of the OAuth2 Authorization Code flow.
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use indexmap::IndexMap;
+use oauth2::reqwest::{Client, RequestBuilder, header};
+use url::Url;
+
+use super::error::OAuth2Error;
+use crate::client::SecureString;
+
+/// Maximum number of redirects followed by a single navigation.
+const MAX_REDIRECTS: usize = 10;
+
+/// The future returned by an [`OAuth2Login::Callback`] hook.
+pub type OAuth2LoginFuture = Pin<Box<dyn Future<Output = Result<Url, OAuth2Error>> + Send>>;
+
+/// The hook of an [`OAuth2Login::Callback`] login step.
+pub type OAuth2LoginHook = Arc<dyn Fn(Url) -> OAuth2LoginFuture + Send + Sync>;
+
+/// How the user logs in during the Authorization Code flow.
+///
+/// Tests have no browser, so the login step is performed headlessly, usually
+/// against a local identity provider stub.
+///
+/// # Example
+///
+/// ```rust
+/// use clawspec_core::OAuth2Login;
+///
+/// // Post the login form displayed by the identity provider
+/// let form = OAuth2Login::form([("username", "alice"), ("password", "secret")]);
+///
+/// // Or let a hook turn the authorization URL into the redirect URL holding the code
+/// let callback = OAuth2Login::callback(|authorize_url| async move {
+///     let state = authorize_url
+///         .query_pairs()
+///         .find(|(key, _)| key == "state")
+///         .map(|(_, value)| value.into_owned())
+///         .unwrap_or_default();
+///     let redirect = format!("http://localhost/callback?code=test-code&state={state}");
+///     Ok(redirect.parse().expect("valid URL"))
+/// });
+/// ```
+#[derive(Clone)]
+#[non_exhaustive]
+pub enum OAuth2Login {
+    /// Posts a login form to the identity provider.
+    ///
+    /// The authorization URL is followed until the identity provider displays its
+    /// login page. The fields are then posted, as `application/x-www-form-urlencoded`,
+    /// to the form action, and the redirects are followed up to the redirect URL.
+    /// Cookies set by the identity provider are sent back during the navigation.
+    Form {
+        /// The URL the form is posted to, defaults to the login page URL.
+        action: Option<Url>,
+        /// The form fields, e.g. username and password.
+        fields: Vec<(String, SecureString)>,
+    },
+
+    /// Calls a hook with the authorization URL.
+    ///
+    /// The hook performs the login and returns the redirect URL, holding the
+    /// authorization `code` and the `state` parameters.
+    Callback(OAuth2LoginHook),
+}
+
+impl OAuth2Login {
+    /// Creates a form login step, posting the fields to the login page URL.
+    pub fn form(
+        fields: impl IntoIterator<Item = (impl Into<String>, impl Into<SecureString>)>,
+    ) -> Self {
+        Self::Form {
+            action: None,
+            fields: fields
+                .into_iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+        }
+    }
+
+    /// Creates a callback login step from an async hook.
+    pub fn callback<F, Fut>(hook: F) -> Self
+    where
+        F: Fn(Url) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Url, OAuth2Error>> + Send + 'static,
+    {
+        Self::Callback(Arc::new(move |url| Box::pin(hook(url))))
+    }
+
+    /// Sets the URL the login form is posted to.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL is invalid, or if this is not a form login step.
+    pub fn with_action(self, action: impl AsRef<str>) -> Result<Self, OAuth2Error> {
+        let Self::Form { fields, .. } = self else {
+            return Err(OAuth2Error::ConfigurationError {
+                reason: "Only a form login step has an action".to_string(),
+            });
+        };
+        let action = Url::parse(action.as_ref()).map_err(|e| OAuth2Error::ConfigurationError {
+            reason: format!("Invalid login form action: {e}"),
+        })?;
+        Ok(Self::Form {
+            action: Some(action),
+            fields,
+        })
+    }
+
+    /// Performs the login, returning the redirect URL reached.
+    pub(super) async fn login(
+        &self,
+        http_client: &Client,
+        authorize_url: Url,
+        redirect_url: &Url,
+    ) -> Result<Url, OAuth2Error> {
+        match self {
+            Self::Form { action, fields } => {
+                let mut browser = Browser::new(http_client, redirect_url);
+                let login_page = match browser.get(authorize_url).await? {
+                    Landing::Redirect(url) => return Ok(url),
+                    Landing::Page(url) => url,
+                };
+                let form: Vec<_> = fields
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value.as_str()))
+                    .collect();
+                let action = action.clone().unwrap_or(login_page);
+                match browser.post_form(action, &form).await? {
+                    Landing::Redirect(url) => Ok(url),
+                    Landing::Page(url) => Err(OAuth2Error::TokenAcquisitionFailed {
+                        reason: format!(
+                            "Login did not redirect to '{redirect_url}', stopped at '{url}'"
+                        ),
+                    }),
+                }
+            }
+            Self::Callback(hook) => hook(authorize_url).await,
+        }
+    }
+}
+
+impl fmt::Debug for OAuth2Login {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Form { action, fields } => f
+                .debug_struct("Form")
+                .field("action", action)
+                .field(
+                    "fields",
+                    &fields.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+                )
+                .finish(),
+            Self::Callback(_) => f.debug_tuple("Callback").field(&"<hook>").finish(),
+        }
+    }
+}
+
+/// Where a navigation ended.
+#[derive(Debug)]
+enum Landing {
+    /// A redirect to the redirect URL.
+    Redirect(Url),
+    /// A page displayed by the identity provider.
+    Page(Url),
+}
+
+/// A minimal browser following the identity provider redirects.
+struct Browser<'a> {
+    client: &'a Client,
+    redirect_url: &'a Url,
+    cookies: IndexMap<String, String>,
+}
+
+impl<'a> Browser<'a> {
+    fn new(client: &'a Client, redirect_url: &'a Url) -> Self {
+        Self {
+            client,
+            redirect_url,
+            cookies: IndexMap::new(),
+        }
+    }
+
+    async fn get(&mut self, url: Url) -> Result<Landing, OAuth2Error> {
+        let request = self.client.get(url.clone());
+        self.navigate(url, request).await
+    }
+
+    async fn post_form(&mut self, url: Url, form: &[(&str, &str)]) -> Result<Landing, OAuth2Error> {
+        let request = self.client.post(url.clone()).form(form);
+        self.navigate(url, request).await
+    }
+
+    async fn navigate(
+        &mut self,
+        mut url: Url,
+        mut request: RequestBuilder,
+    ) -> Result<Landing, OAuth2Error> {
+        for _ in 0..=MAX_REDIRECTS {
+            if !self.cookies.is_empty() {
+                request = request.header(header::COOKIE, self.cookie_header());
+            }
+            let response = request
+                .send()
+                .await
+                .map_err(|e| OAuth2Error::NetworkError {
+                    reason: e.to_string(),
+                })?;
+            self.store_cookies(response.headers());
+
+            let status = response.status();
+            if status.is_redirection() {
+                let location = response
+                    .headers()
+                    .get(header::LOCATION)
+                    .and_then(|value| value.to_str().ok())
+                    .ok_or_else(|| OAuth2Error::TokenAcquisitionFailed {
+                        reason: format!("Redirect without location from '{url}'"),
+                    })?;
+                url = url
+                    .join(location)
+                    .map_err(|e| OAuth2Error::TokenAcquisitionFailed {
+                        reason: format!("Invalid redirect location '{location}': {e}"),
+                    })?;
+                if is_same_endpoint(&url, self.redirect_url) {
+                    return Ok(Landing::Redirect(url));
+                }
+                request = self.client.get(url.clone());
+            } else if status.is_success() {
+                return Ok(Landing::Page(url));
+            } else {
+                return Err(OAuth2Error::TokenAcquisitionFailed {
+                    reason: format!("Identity provider returned {status} for '{url}'"),
+                });
+            }
+        }
+
+        Err(OAuth2Error::TokenAcquisitionFailed {
+            reason: format!("Too many redirects, last one to '{url}'"),
+        })
+    }
+
+    fn store_cookies(&mut self, headers: &header::HeaderMap) {
+        let cookies = headers
+            .get_all(header::SET_COOKIE)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .filter_map(|value| value.split(';').next())
+            .filter_map(|pair| pair.split_once('='));
+        for (name, value) in cookies {
+            self.cookies
+                .insert(name.trim().to_string(), value.trim().to_string());
+        }
+    }
+
+    fn cookie_header(&self) -> String {
+        self.cookies
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// Checks whether the URL targets the endpoint, ignoring the query and fragment.
+fn is_same_endpoint(url: &Url, endpoint: &Url) -> bool {
+    url.scheme() == endpoint.scheme()
+        && url.host() == endpoint.host()
+        && url.port_or_known_default() == endpoint.port_or_known_default()
+        && url.path() == endpoint.path()
+}
+
+/// Extracts the authorization code from the redirect URL, checking the state.
+pub(super) fn authorization_code(redirect: &Url, state: &str) -> Result<String, OAuth2Error> {
+    let params: IndexMap<_, _> = redirect.query_pairs().collect();
+    if let Some(error) = params.get("error") {
+        let description = params
+            .get("error_description")
+            .map(|description| format!(": {description}"))
+            .unwrap_or_default();
+        return Err(OAuth2Error::TokenAcquisitionFailed {
+            reason: format!("Authorization denied with '{error}'{description}"),
+        });
+    }
+    if params.get("state").map(AsRef::as_ref) != Some(state) {
+        return Err(OAuth2Error::TokenAcquisitionFailed {
+            reason: "Authorization state mismatch".to_string(),
+        });
+    }
+    params
+        .get("code")
+        .map(|code| code.to_string())
+        .ok_or_else(|| OAuth2Error::TokenAcquisitionFailed {
+            reason: format!("No authorization code in '{redirect}'"),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(value: &str) -> Url {
+        Url::parse(value).expect("should be a valid URL")
+    }
+
+    #[test]
+    fn should_match_redirect_endpoint_ignoring_query() {
+        let endpoint = url("http://localhost:3000/callback");
+
+        assert!(is_same_endpoint(
+            &url("http://localhost:3000/callback?code=abc&state=xyz"),
+            &endpoint
+        ));
+        assert!(!is_same_endpoint(
+            &url("http://localhost:3001/callback"),
+            &endpoint
+        ));
+        assert!(!is_same_endpoint(
+            &url("http://localhost:3000/login"),
+            &endpoint
+        ));
+    }
+
+    #[test]
+    fn should_extract_authorization_code() {
+        let redirect = url("http://localhost/callback?code=abc&state=xyz");
+
+        let code = authorization_code(&redirect, "xyz").expect("should extract the code");
+
+        assert_eq!(code, "abc");
+    }
+
+    #[test]
+    fn should_reject_state_mismatch() {
+        let redirect = url("http://localhost/callback?code=abc&state=forged");
+
+        let error = authorization_code(&redirect, "xyz").expect_err("should reject the state");
+
+        insta::assert_snapshot!(error, @"Token acquisition failed: Authorization state mismatch");
+    }
+
+    #[test]
+    fn should_report_authorization_error() {
+        let redirect =
+            url("http://localhost/callback?error=access_denied&error_description=Nope&state=xyz");
+
+        let error = authorization_code(&redirect, "xyz").expect_err("should report the error");
+
+        insta::assert_snapshot!(error, @"Token acquisition failed: Authorization denied with 'access_denied': Nope");
+    }
+
+    #[test]
+    fn should_redact_form_fields_in_debug() {
+        let login = OAuth2Login::form([("username", "alice"), ("password", "secret")]);
+
+        let debug = format!("{login:?}");
+
+        assert!(debug.contains("password"));
+        assert!(!debug.contains("secret"));
+    }
+
+    #[test]
+    fn should_reject_action_on_callback() {
+        let login = OAuth2Login::callback(|url| async move { Ok(url) });
+
+        assert!(login.with_action("http://localhost/login").is_err());
+    }
+}
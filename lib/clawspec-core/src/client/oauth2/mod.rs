@@ -17,6 +17,12 @@
 //!
 //! - **Client Credentials**: Machine-to-machine authentication (most common for testing)
 //! - **Pre-Acquired Token**: Use externally obtained tokens (environment variables, etc.)
+//! - **Authorization Code with PKCE**: User tokens, the login being performed headlessly
+//!   against an identity provider stub (see [`OAuth2Login`])
+//! - **Password**: Resource owner credentials, for legacy identity providers
+//!
+//! Tokens about to expire are refreshed with their refresh token, when the
+//! token endpoint issued one.
 //!
 //! # Example
 //!
@@ -41,9 +47,14 @@
 
 mod config;
 mod error;
+mod login;
 mod provider;
 mod token;
 
 pub use self::config::{OAuth2Config, OAuth2ConfigBuilder, SharedOAuth2Config};
 pub use self::error::OAuth2Error;
+pub use self::login::{OAuth2Login, OAuth2LoginFuture, OAuth2LoginHook};
 pub use self::token::OAuth2Token;
+
+/// Default name of the OAuth2 security scheme.
+pub(crate) const OAUTH2_SCHEME_NAME: &str = "oauth2";
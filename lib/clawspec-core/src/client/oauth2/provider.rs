@@ -2,18 +2,26 @@
 
 use std::time::Duration;
 
-use oauth2::{AccessToken, TokenResponse};
+use oauth2::basic::{BasicClient, BasicTokenResponse};
+use oauth2::{AccessToken, ClientId, ClientSecret, EndpointNotSet, EndpointSet, TokenResponse};
 
 use super::config::{OAuth2Config, OAuth2GrantType};
 use super::error::OAuth2Error;
+use super::login::authorization_code;
 use super::token::OAuth2Token;
 
+/// An oauth2 client with only the token endpoint set.
+type TokenClient =
+    BasicClient<EndpointNotSet, EndpointNotSet, EndpointNotSet, EndpointNotSet, EndpointSet>;
+
 impl OAuth2Config {
     /// Acquires a new access token using the configured grant type.
     ///
     /// This method handles:
     /// - Client Credentials grant: fetches a new token from the token endpoint
     /// - Pre-Acquired token: returns the cached token if available
+    /// - Authorization Code grant: performs the login, then exchanges the code
+    /// - Password grant: exchanges the resource owner credentials
     ///
     /// # Errors
     ///
@@ -22,24 +30,76 @@ impl OAuth2Config {
     /// - Token endpoint returns an error
     /// - Response cannot be parsed
     pub async fn acquire_token(&self) -> Result<OAuth2Token, OAuth2Error> {
+        self.acquire_scoped_token(&self.scopes).await
+    }
+
+    /// Acquires a new access token with the scopes, using the configured grant type.
+    ///
+    /// A pre-acquired token is returned whatever the scopes.
+    async fn acquire_scoped_token(&self, scopes: &[String]) -> Result<OAuth2Token, OAuth2Error> {
         match self.grant_type {
-            OAuth2GrantType::ClientCredentials => self.acquire_client_credentials_token().await,
+            OAuth2GrantType::ClientCredentials => {
+                self.acquire_client_credentials_token(scopes).await
+            }
             OAuth2GrantType::PreAcquired => self.get_pre_acquired_token().await,
+            OAuth2GrantType::AuthorizationCode => {
+                self.acquire_authorization_code_token(scopes).await
+            }
+            OAuth2GrantType::Password => self.acquire_password_token(scopes).await,
         }
     }
 
-    /// Acquires a token using the Client Credentials grant.
-    async fn acquire_client_credentials_token(&self) -> Result<OAuth2Token, OAuth2Error> {
-        // Create HTTP client with redirect disabled for SSRF prevention
-        // Use oauth2::reqwest to ensure version compatibility
-        let http_client = oauth2::reqwest::ClientBuilder::new()
-            .redirect(oauth2::reqwest::redirect::Policy::none())
-            .build()
-            .map_err(|e| OAuth2Error::TokenAcquisitionFailed {
-                reason: format!("Failed to create HTTP client: {e}"),
+    /// Exchanges the refresh token of the cached token for a new token.
+    ///
+    /// The refresh token is kept when the token endpoint does not issue a new one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OAuth2Error::TokenExpired`] if no refresh token is cached, and
+    /// [`OAuth2Error::TokenRefreshFailed`] if the token endpoint rejects it.
+    pub async fn refresh_token(&self) -> Result<OAuth2Token, OAuth2Error> {
+        self.refresh_scoped_token(&self.scopes).await
+    }
+
+    /// Exchanges the refresh token of the cached token for the scopes.
+    async fn refresh_scoped_token(&self, scopes: &[String]) -> Result<OAuth2Token, OAuth2Error> {
+        use oauth2::{RefreshToken, Scope};
+
+        let refresh_token = self
+            .token_cache
+            .refresh_token(scopes)
+            .await
+            .ok_or(OAuth2Error::TokenExpired)?;
+
+        let http_client = Self::http_client()?;
+        let response = self
+            .token_client()
+            .exchange_refresh_token(&RefreshToken::new(refresh_token.clone()))
+            .add_scopes(scopes.iter().map(|s| Scope::new(s.clone())))
+            .request_async(&http_client)
+            .await
+            .map_err(|e| OAuth2Error::TokenRefreshFailed {
+                reason: format!("{e}"),
             })?;
 
-        self.acquire_client_credentials_token_with_client(&http_client)
+        let mut token = Self::convert_token(&response);
+        if token.refresh_token().is_none() {
+            token = token.with_refresh_token(refresh_token);
+        }
+
+        self.token_cache.set(scopes, token.clone()).await;
+
+        Ok(token)
+    }
+
+    /// Acquires a token using the Client Credentials grant.
+    async fn acquire_client_credentials_token(
+        &self,
+        scopes: &[String],
+    ) -> Result<OAuth2Token, OAuth2Error> {
+        let http_client = Self::http_client()?;
+
+        self.acquire_client_credentials_token_with_client(&http_client, scopes)
             .await
     }
 
@@ -50,45 +110,15 @@ impl OAuth2Config {
     pub(crate) async fn acquire_client_credentials_token_with_client(
         &self,
         http_client: &oauth2::reqwest::Client,
+        scopes: &[String],
     ) -> Result<OAuth2Token, OAuth2Error> {
-        use oauth2::basic::BasicClient;
-        use oauth2::{AuthUrl, ClientId, ClientSecret, Scope, TokenUrl};
-
-        let client_id = ClientId::new(self.client_id.clone());
-
-        // Use a dummy auth URL if not specified (client_credentials doesn't need it)
-        let auth_url_str = self
-            .auth_url
-            .as_ref()
-            .map(|u| u.to_string())
-            .unwrap_or_else(|| format!("{}/../authorize", self.token_url));
-
-        let auth_url = AuthUrl::new(auth_url_str).map_err(|e| OAuth2Error::ConfigurationError {
-            reason: format!("Invalid authorization URL: {e}"),
-        })?;
-
-        let token_url = TokenUrl::new(self.token_url.to_string()).map_err(|e| {
-            OAuth2Error::ConfigurationError {
-                reason: format!("Invalid token URL: {e}"),
-            }
-        })?;
-
-        // Build client using the new builder pattern (oauth2 5.x)
-        // The type-state pattern ensures exchange_client_credentials() is available
-        // only after set_token_uri() is called
-        let mut client = BasicClient::new(client_id)
-            .set_auth_uri(auth_url)
-            .set_token_uri(token_url);
-
-        // Set client secret if provided
-        if let Some(ref secret) = self.client_secret {
-            client = client.set_client_secret(ClientSecret::new(secret.as_str().to_string()));
-        }
+        use oauth2::Scope;
 
+        let client = self.token_client();
         let mut request = client.exchange_client_credentials();
 
         // Add scopes
-        for scope in self.scopes.iter().map(|s| Scope::new(s.clone())) {
+        for scope in scopes.iter().map(|s| Scope::new(s.clone())) {
             request = request.add_scope(scope);
         }
 
@@ -100,20 +130,136 @@ impl OAuth2Config {
         })?;
 
         // Convert to our token type
-        let token =
-            Self::convert_token_response(token_result.access_token(), token_result.expires_in());
+        let token = Self::convert_token(&token_result);
 
         // Cache the token
-        self.set_token(token.clone()).await;
+        self.token_cache.set(scopes, token.clone()).await;
 
         Ok(token)
     }
 
+    /// Acquires a token using the Authorization Code grant with PKCE.
+    async fn acquire_authorization_code_token(
+        &self,
+        scopes: &[String],
+    ) -> Result<OAuth2Token, OAuth2Error> {
+        use oauth2::{
+            AuthUrl, AuthorizationCode, CsrfToken, PkceCodeChallenge, RedirectUrl, Scope,
+        };
+
+        let (Some(auth_url), Some(redirect_url), Some(login)) =
+            (&self.auth_url, &self.redirect_url, &self.login)
+        else {
+            return Err(OAuth2Error::ConfigurationError {
+                reason: "Authorization code flow requires an authorization URL, a redirect URL and a login step".to_string(),
+            });
+        };
+
+        let http_client = Self::http_client()?;
+        let client = self
+            .token_client()
+            .set_auth_uri(AuthUrl::from_url(auth_url.clone()))
+            .set_redirect_uri(RedirectUrl::from_url(redirect_url.clone()));
+
+        let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
+        let (authorize_url, state) = client
+            .authorize_url(CsrfToken::new_random)
+            .add_scopes(scopes.iter().map(|s| Scope::new(s.clone())))
+            .set_pkce_challenge(pkce_challenge)
+            .url();
+
+        let redirect = login
+            .login(&http_client, authorize_url, redirect_url)
+            .await?;
+        let code = authorization_code(&redirect, state.secret())?;
+
+        let token_result = client
+            .exchange_code(AuthorizationCode::new(code))
+            .set_pkce_verifier(pkce_verifier)
+            .request_async(&http_client)
+            .await
+            .map_err(|e| OAuth2Error::TokenAcquisitionFailed {
+                reason: format!("{e}"),
+            })?;
+
+        let token = Self::convert_token(&token_result);
+
+        self.token_cache.set(scopes, token.clone()).await;
+
+        Ok(token)
+    }
+
+    /// Acquires a token using the Resource Owner Password Credentials grant.
+    async fn acquire_password_token(&self, scopes: &[String]) -> Result<OAuth2Token, OAuth2Error> {
+        use oauth2::{ResourceOwnerPassword, ResourceOwnerUsername, Scope};
+
+        let (Some(username), Some(password)) = (&self.username, &self.password) else {
+            return Err(OAuth2Error::ConfigurationError {
+                reason: "Password flow requires a username and a password".to_string(),
+            });
+        };
+        let username = ResourceOwnerUsername::new(username.clone());
+        let password = ResourceOwnerPassword::new(password.as_str().to_string());
+
+        let http_client = Self::http_client()?;
+        let client = self.token_client();
+        let token_result = client
+            .exchange_password(&username, &password)
+            .add_scopes(scopes.iter().map(|s| Scope::new(s.clone())))
+            .request_async(&http_client)
+            .await
+            .map_err(|e| OAuth2Error::TokenAcquisitionFailed {
+                reason: format!("{e}"),
+            })?;
+
+        let token = Self::convert_token(&token_result);
+
+        self.token_cache.set(scopes, token.clone()).await;
+
+        Ok(token)
+    }
+
+    /// Creates the HTTP client used to talk to the identity provider.
+    fn http_client() -> Result<oauth2::reqwest::Client, OAuth2Error> {
+        // Create HTTP client with redirect disabled for SSRF prevention
+        // Use oauth2::reqwest to ensure version compatibility
+        oauth2::reqwest::ClientBuilder::new()
+            .redirect(oauth2::reqwest::redirect::Policy::none())
+            .build()
+            .map_err(|e| OAuth2Error::TokenAcquisitionFailed {
+                reason: format!("Failed to create HTTP client: {e}"),
+            })
+    }
+
+    /// Creates an oauth2 client for the token endpoint, with the client secret if any.
+    fn token_client(&self) -> TokenClient {
+        use oauth2::TokenUrl;
+
+        let client = BasicClient::new(ClientId::new(self.client_id.clone()))
+            .set_token_uri(TokenUrl::from_url(self.token_url.clone()));
+
+        match self.client_secret {
+            Some(ref secret) => {
+                client.set_client_secret(ClientSecret::new(secret.as_str().to_string()))
+            }
+            None => client,
+        }
+    }
+
     /// Returns the pre-acquired token if available.
     async fn get_pre_acquired_token(&self) -> Result<OAuth2Token, OAuth2Error> {
         self.get_token().await.ok_or(OAuth2Error::TokenExpired)
     }
 
+    /// Converts an oauth2 token response, with its refresh token, to our token type.
+    fn convert_token(response: &BasicTokenResponse) -> OAuth2Token {
+        let token = Self::convert_token_response(response.access_token(), response.expires_in());
+        match response.refresh_token() {
+            Some(refresh_token) => token.with_refresh_token(refresh_token.secret().clone()),
+            None => token,
+        }
+    }
+
     /// Converts an oauth2 token response to our token type.
     fn convert_token_response(
         access_token: &AccessToken,
@@ -130,16 +276,44 @@ impl OAuth2Config {
     ///
     /// This is the main entry point for getting an access token.
     /// It checks the cache first and only acquires a new token if needed.
+    /// With auto-refresh, a token about to expire is refreshed with its refresh token.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OAuth2Error::TokenRefreshFailed`] if the refresh token is rejected.
     pub async fn get_valid_token(&self) -> Result<OAuth2Token, OAuth2Error> {
+        self.get_valid_token_with_scopes(&self.scopes).await
+    }
+
+    /// Gets a valid token with exactly these scopes, acquiring a new one if necessary.
+    ///
+    /// Tokens are cached per set of scopes, so a test can alternate calls requiring
+    /// different scopes without acquiring a token for each call.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OAuth2Error::TokenRefreshFailed`] if the refresh token is rejected.
+    pub async fn get_valid_token_with_scopes(
+        &self,
+        scopes: &[String],
+    ) -> Result<OAuth2Token, OAuth2Error> {
         // Check if we have a valid cached token
-        if !self.needs_token().await
-            && let Some(token) = self.get_token().await
+        if !self
+            .token_cache
+            .should_refresh(scopes, self.refresh_threshold)
+            .await
+            && let Some(token) = self.token_cache.get(scopes).await
         {
             return Ok(token);
         }
 
+        // Refresh the token when possible
+        if self.auto_refresh && self.token_cache.refresh_token(scopes).await.is_some() {
+            return self.refresh_scoped_token(scopes).await;
+        }
+
         // Need to acquire a new token
-        self.acquire_token().await
+        self.acquire_scoped_token(scopes).await
     }
 }
 
@@ -261,6 +435,7 @@ mod tests {
 
     mod mock_server_tests {
         use super::*;
+        use crate::client::oauth2::OAuth2Login;
         use wiremock::matchers::{method, path};
         use wiremock::{Mock, MockServer, ResponseTemplate};
 
@@ -438,6 +613,370 @@ mod tests {
             );
         }
 
+        async fn mount_refresh_endpoint(mock_server: &MockServer, response: ResponseTemplate) {
+            Mock::given(method("POST"))
+                .and(path("/oauth/token"))
+                .and(wiremock::matchers::body_string_contains(
+                    "grant_type=refresh_token",
+                ))
+                .and(wiremock::matchers::body_string_contains(
+                    "refresh_token=refresh-1",
+                ))
+                .respond_with(response)
+                .expect(1)
+                .mount(mock_server)
+                .await;
+        }
+
+        async fn config_with_expiring_token(
+            mock_server: &MockServer,
+            auto_refresh: bool,
+        ) -> OAuth2Config {
+            let token_url = format!("{}/oauth/token", mock_server.uri());
+            let config = OAuth2Config::client_credentials("test-client", "test-secret", &token_url)
+                .expect("Should create builder")
+                .with_auto_refresh(auto_refresh)
+                .build()
+                .expect("Should build config");
+            // Expires within the default refresh threshold
+            let token = OAuth2Token::with_expiry("expiring-token", Duration::from_secs(30))
+                .with_refresh_token("refresh-1");
+            config.set_token(token).await;
+            config
+        }
+
+        #[tokio::test]
+        async fn should_refresh_expiring_token() {
+            let mock_server = MockServer::start().await;
+            mount_refresh_endpoint(
+                &mock_server,
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "access_token": "refreshed-token",
+                    "token_type": "Bearer",
+                    "expires_in": 3600
+                })),
+            )
+            .await;
+            let config = config_with_expiring_token(&mock_server, true).await;
+
+            let token = config
+                .get_valid_token()
+                .await
+                .expect("Should refresh the token");
+
+            assert_eq!(token.access_token(), "refreshed-token");
+            assert_eq!(
+                token.refresh_token(),
+                Some("refresh-1"),
+                "The refresh token should be kept when no new one is issued"
+            );
+        }
+
+        #[tokio::test]
+        async fn should_rotate_refresh_token() {
+            let mock_server = MockServer::start().await;
+            mount_refresh_endpoint(
+                &mock_server,
+                ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "access_token": "refreshed-token",
+                    "token_type": "Bearer",
+                    "refresh_token": "refresh-2"
+                })),
+            )
+            .await;
+            let config = config_with_expiring_token(&mock_server, true).await;
+
+            let token = config
+                .refresh_token()
+                .await
+                .expect("Should refresh the token");
+
+            assert_eq!(token.refresh_token(), Some("refresh-2"));
+        }
+
+        #[tokio::test]
+        async fn should_surface_token_refresh_failure() {
+            let mock_server = MockServer::start().await;
+            mount_refresh_endpoint(
+                &mock_server,
+                ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                    "error": "invalid_grant",
+                    "error_description": "Refresh token revoked"
+                })),
+            )
+            .await;
+            let config = config_with_expiring_token(&mock_server, true).await;
+
+            let result = config.get_valid_token().await;
+
+            match result.expect_err("Should fail") {
+                OAuth2Error::TokenRefreshFailed { reason } => {
+                    assert!(
+                        reason.contains("invalid_grant"),
+                        "Error should contain the OAuth2 error: {reason}"
+                    );
+                }
+                other => panic!("Expected TokenRefreshFailed, got {other:?}"),
+            }
+        }
+
+        #[tokio::test]
+        async fn should_reacquire_token_without_auto_refresh() {
+            let mock_server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .and(path("/oauth/token"))
+                .and(wiremock::matchers::body_string_contains(
+                    "grant_type=client_credentials",
+                ))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "access_token": "new-token",
+                    "token_type": "Bearer",
+                    "expires_in": 3600
+                })))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+            let config = config_with_expiring_token(&mock_server, false).await;
+
+            let token = config
+                .get_valid_token()
+                .await
+                .expect("Should acquire token");
+
+            assert_eq!(token.access_token(), "new-token");
+        }
+
+        #[tokio::test]
+        async fn should_fail_refresh_without_refresh_token() {
+            let config = OAuth2Config::client_credentials(
+                "test-client",
+                "test-secret",
+                "https://auth.example.com/token",
+            )
+            .expect("Should create builder")
+            .build()
+            .expect("Should build config");
+
+            let result = config.refresh_token().await;
+
+            assert_eq!(result.expect_err("Should fail"), OAuth2Error::TokenExpired);
+        }
+
+        #[tokio::test]
+        async fn should_acquire_password_token() {
+            let mock_server = MockServer::start().await;
+
+            Mock::given(method("POST"))
+                .and(path("/oauth/token"))
+                .and(wiremock::matchers::body_string_contains(
+                    "grant_type=password",
+                ))
+                .and(wiremock::matchers::body_string_contains("username=alice"))
+                .and(wiremock::matchers::body_string_contains("password=s3cret"))
+                .and(wiremock::matchers::body_string_contains(
+                    "scope=read%3Ausers",
+                ))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "access_token": "password-token",
+                    "token_type": "Bearer",
+                    "expires_in": 3600,
+                    "refresh_token": "password-refresh"
+                })))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+
+            let token_url = format!("{}/oauth/token", mock_server.uri());
+            let config = OAuth2Config::password("legacy-client", &token_url, "alice", "s3cret")
+                .expect("Should create builder")
+                .add_scope("read:users")
+                .build()
+                .expect("Should build config");
+
+            let token = config
+                .get_valid_token()
+                .await
+                .expect("Should acquire token");
+
+            assert_eq!(token.access_token(), "password-token");
+            assert_eq!(token.refresh_token(), Some("password-refresh"));
+        }
+
+        #[tokio::test]
+        async fn should_handle_rejected_password() {
+            let mock_server = MockServer::start().await;
+
+            Mock::given(method("POST"))
+                .and(path("/oauth/token"))
+                .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                    "error": "invalid_grant",
+                    "error_description": "Bad credentials"
+                })))
+                .expect(1)
+                .mount(&mock_server)
+                .await;
+
+            let token_url = format!("{}/oauth/token", mock_server.uri());
+            let config = OAuth2Config::password("legacy-client", &token_url, "alice", "wrong")
+                .expect("Should create builder")
+                .build()
+                .expect("Should build config");
+
+            let result = config.acquire_token().await;
+
+            assert!(matches!(
+                result,
+                Err(OAuth2Error::TokenAcquisitionFailed { .. })
+            ));
+        }
+
+        /// An identity provider stub, redirecting to its login page and
+        /// accepting the login of `alice`.
+        struct IdentityProviderStub;
+
+        impl wiremock::Respond for IdentityProviderStub {
+            fn respond(&self, request: &wiremock::Request) -> ResponseTemplate {
+                let query = request.url.query().unwrap_or_default();
+                match (request.method.as_str(), request.url.path()) {
+                    ("GET", "/authorize") => ResponseTemplate::new(302)
+                        .insert_header("Location", format!("/login?{query}").as_str()),
+                    ("GET", "/login") => ResponseTemplate::new(200)
+                        .insert_header("Set-Cookie", "session=s3ss10n; HttpOnly")
+                        .set_body_string("<form method=\"post\">...</form>"),
+                    ("POST", "/login") => {
+                        let body = String::from_utf8_lossy(&request.body);
+                        let has_session = request
+                            .headers
+                            .get("cookie")
+                            .is_some_and(|cookie| cookie == "session=s3ss10n");
+                        if !has_session || !body.contains("username=alice") {
+                            return ResponseTemplate::new(401);
+                        }
+                        let params: std::collections::HashMap<_, _> =
+                            request.url.query_pairs().collect();
+                        let location = format!(
+                            "{}?code=auth-code&state={}",
+                            params["redirect_uri"], params["state"]
+                        );
+                        ResponseTemplate::new(302).insert_header("Location", location.as_str())
+                    }
+                    _ => ResponseTemplate::new(404),
+                }
+            }
+        }
+
+        async fn mount_identity_provider(mock_server: &MockServer) {
+            Mock::given(wiremock::matchers::path_regex("^/(authorize|login)$"))
+                .respond_with(IdentityProviderStub)
+                .mount(mock_server)
+                .await;
+            Mock::given(method("POST"))
+                .and(path("/token"))
+                .and(wiremock::matchers::body_string_contains(
+                    "grant_type=authorization_code",
+                ))
+                .and(wiremock::matchers::body_string_contains("code=auth-code"))
+                .and(wiremock::matchers::body_string_contains("code_verifier="))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "access_token": "user-access-token",
+                    "token_type": "Bearer",
+                    "expires_in": 3600,
+                    "refresh_token": "user-refresh-token"
+                })))
+                .expect(1)
+                .mount(mock_server)
+                .await;
+        }
+
+        #[tokio::test]
+        async fn should_acquire_authorization_code_token_with_form_login() {
+            let mock_server = MockServer::start().await;
+            mount_identity_provider(&mock_server).await;
+
+            let config = OAuth2Config::authorization_code(
+                "test-client",
+                format!("{}/authorize", mock_server.uri()),
+                format!("{}/token", mock_server.uri()),
+                "http://localhost:3000/callback",
+                OAuth2Login::form([("username", "alice"), ("password", "secret")]),
+            )
+            .expect("Should create builder")
+            .add_scope("read:users")
+            .build()
+            .expect("Should build config");
+
+            let token = config
+                .get_valid_token()
+                .await
+                .expect("Should acquire token");
+
+            assert_eq!(token.access_token(), "user-access-token");
+            assert_eq!(token.refresh_token(), Some("user-refresh-token"));
+            let cached = config.get_token().await.expect("Should cache the token");
+            assert_eq!(cached.access_token(), "user-access-token");
+        }
+
+        #[tokio::test]
+        async fn should_fail_authorization_code_with_rejected_login() {
+            let mock_server = MockServer::start().await;
+            mount_identity_provider(&mock_server).await;
+
+            let config = OAuth2Config::authorization_code(
+                "test-client",
+                format!("{}/authorize", mock_server.uri()),
+                format!("{}/token", mock_server.uri()),
+                "http://localhost:3000/callback",
+                OAuth2Login::form([("username", "mallory")]),
+            )
+            .expect("Should create builder")
+            .build()
+            .expect("Should build config");
+
+            let result = config.acquire_token().await;
+
+            match result.expect_err("Should fail") {
+                OAuth2Error::TokenAcquisitionFailed { reason } => {
+                    assert!(reason.contains("401"), "Unexpected reason: {reason}");
+                }
+                other => panic!("Expected TokenAcquisitionFailed, got {other:?}"),
+            }
+            // Do not verify the token endpoint expectation
+            mock_server.reset().await;
+        }
+
+        #[tokio::test]
+        async fn should_acquire_authorization_code_token_with_callback_login() {
+            let mock_server = MockServer::start().await;
+            mount_identity_provider(&mock_server).await;
+
+            let login = OAuth2Login::callback(|authorize_url| async move {
+                let params: std::collections::HashMap<_, _> =
+                    authorize_url.query_pairs().into_owned().collect();
+                assert_eq!(params["code_challenge_method"], "S256");
+                let redirect = format!(
+                    "{}?code=auth-code&state={}",
+                    params["redirect_uri"], params["state"]
+                );
+                url::Url::parse(&redirect).map_err(|e| OAuth2Error::TokenAcquisitionFailed {
+                    reason: e.to_string(),
+                })
+            });
+            let config = OAuth2Config::authorization_code(
+                "test-client",
+                format!("{}/authorize", mock_server.uri()),
+                format!("{}/token", mock_server.uri()),
+                "http://localhost:3000/callback",
+                login,
+            )
+            .expect("Should create builder")
+            .build()
+            .expect("Should build config");
+
+            let token = config.acquire_token().await.expect("Should acquire token");
+
+            assert_eq!(token.access_token(), "user-access-token");
+        }
+
         #[tokio::test]
         async fn should_cache_token_after_acquisition() {
             let mock_server = MockServer::start().await;
@@ -6,6 +6,7 @@
 // `is_expired()`, `should_refresh()`, and `time_until_expiry()` methods.
 #![allow(unused_assignments)]
 
+use std::collections::{BTreeSet, HashMap};
 use std::fmt;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -100,11 +101,12 @@ impl fmt::Debug for OAuth2Token {
 
 /// Thread-safe cache for OAuth2 tokens.
 ///
+/// Tokens are cached per set of scopes, the order of the scopes being ignored.
 /// This cache ensures that only one token refresh happens at a time,
 /// even when multiple requests are made concurrently.
 #[derive(Debug, Clone, Default)]
 pub struct TokenCache {
-    inner: Arc<RwLock<Option<OAuth2Token>>>,
+    inner: Arc<RwLock<HashMap<BTreeSet<String>, OAuth2Token>>>,
 }
 
 impl TokenCache {
@@ -113,44 +115,60 @@ impl TokenCache {
         Self::default()
     }
 
-    /// Creates a token cache with an initial token.
-    pub fn with_token(token: OAuth2Token) -> Self {
+    /// Creates a token cache with an initial token for the scopes.
+    pub fn with_token(scopes: &[String], token: OAuth2Token) -> Self {
+        let tokens = HashMap::from([(Self::key(scopes), token)]);
         Self {
-            inner: Arc::new(RwLock::new(Some(token))),
+            inner: Arc::new(RwLock::new(tokens)),
         }
     }
 
-    /// Returns the cached token if it exists and is not expired.
-    pub async fn get(&self) -> Option<OAuth2Token> {
+    /// Returns the cached token for the scopes if it exists and is not expired.
+    pub async fn get(&self, scopes: &[String]) -> Option<OAuth2Token> {
         let guard = self.inner.read().await;
-        guard.as_ref().filter(|t| !t.is_expired()).cloned()
+        guard
+            .get(&Self::key(scopes))
+            .filter(|t| !t.is_expired())
+            .cloned()
     }
 
-    /// Returns `true` if the token should be refreshed.
+    /// Returns the refresh token of the cached token for the scopes, even if it is expired.
+    pub async fn refresh_token(&self, scopes: &[String]) -> Option<String> {
+        let guard = self.inner.read().await;
+        guard
+            .get(&Self::key(scopes))
+            .and_then(|t| t.refresh_token().map(ToString::to_string))
+    }
+
+    /// Returns `true` if the token for the scopes should be refreshed.
     ///
     /// A token should be refreshed if:
     /// - No token is cached
     /// - The token is expired
     /// - The token will expire within the threshold
-    pub async fn should_refresh(&self, threshold: Duration) -> bool {
+    pub async fn should_refresh(&self, scopes: &[String], threshold: Duration) -> bool {
         let guard = self.inner.read().await;
-        match guard.as_ref() {
+        match guard.get(&Self::key(scopes)) {
             None => true,
             Some(token) => token.should_refresh(threshold),
         }
     }
 
-    /// Stores a new token in the cache.
-    pub async fn set(&self, token: OAuth2Token) {
+    /// Stores a new token for the scopes in the cache.
+    pub async fn set(&self, scopes: &[String], token: OAuth2Token) {
         let mut guard = self.inner.write().await;
-        *guard = Some(token);
+        guard.insert(Self::key(scopes), token);
     }
 
-    /// Clears the cached token.
+    /// Clears all the cached tokens.
     #[cfg_attr(not(test), allow(dead_code))]
     pub async fn clear(&self) {
         let mut guard = self.inner.write().await;
-        *guard = None;
+        guard.clear();
+    }
+
+    fn key(scopes: &[String]) -> BTreeSet<String> {
+        scopes.iter().cloned().collect()
     }
 }
 
@@ -210,12 +228,12 @@ mod tests {
     #[tokio::test]
     async fn should_cache_token() {
         let cache = TokenCache::new();
-        assert!(cache.get().await.is_none());
+        assert!(cache.get(&[]).await.is_none());
 
         let token = OAuth2Token::new("cached-token");
-        cache.set(token).await;
+        cache.set(&[], token).await;
 
-        let cached = cache.get().await.expect("Token should be cached");
+        let cached = cache.get(&[]).await.expect("Token should be cached");
         assert_eq!(cached.access_token(), "cached-token");
     }
 
@@ -223,20 +241,47 @@ mod tests {
     async fn should_not_return_expired_token() {
         let cache = TokenCache::new();
         let token = OAuth2Token::with_expiry("expired", Duration::ZERO);
-        cache.set(token).await;
+        cache.set(&[], token).await;
 
         // Should return None for expired token
-        assert!(cache.get().await.is_none());
+        assert!(cache.get(&[]).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn should_keep_refresh_token_of_expired_token() {
+        let cache = TokenCache::new();
+        let token =
+            OAuth2Token::with_expiry("expired", Duration::ZERO).with_refresh_token("refresh");
+        cache.set(&[], token).await;
+
+        assert!(cache.get(&[]).await.is_none());
+        assert_eq!(cache.refresh_token(&[]).await.as_deref(), Some("refresh"));
     }
 
     #[tokio::test]
     async fn should_clear_cache() {
         let cache = TokenCache::new();
-        cache.set(OAuth2Token::new("token")).await;
-        assert!(cache.get().await.is_some());
+        cache.set(&[], OAuth2Token::new("token")).await;
+        assert!(cache.get(&[]).await.is_some());
 
         cache.clear().await;
-        assert!(cache.get().await.is_none());
+        assert!(cache.get(&[]).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn should_cache_token_per_scope_set() {
+        let cache = TokenCache::new();
+        let read = ["read".to_string()];
+        let admin = ["admin".to_string(), "read".to_string()];
+        cache.set(&read, OAuth2Token::new("read-token")).await;
+        cache.set(&admin, OAuth2Token::new("admin-token")).await;
+
+        let reversed = ["read".to_string(), "admin".to_string()];
+        let cached = cache.get(&reversed).await.expect("Token should be cached");
+        assert_eq!(cached.access_token(), "admin-token");
+        let cached = cache.get(&read).await.expect("Token should be cached");
+        assert_eq!(cached.access_token(), "read-token");
+        assert!(cache.get(&[]).await.is_none());
     }
 
     #[tokio::test]
@@ -244,16 +289,16 @@ mod tests {
         let cache = TokenCache::new();
 
         // Empty cache needs refresh
-        assert!(cache.should_refresh(Duration::from_secs(60)).await);
+        assert!(cache.should_refresh(&[], Duration::from_secs(60)).await);
 
         // Token expiring soon needs refresh
         let token = OAuth2Token::with_expiry("token", Duration::from_secs(30));
-        cache.set(token).await;
-        assert!(cache.should_refresh(Duration::from_secs(60)).await);
+        cache.set(&[], token).await;
+        assert!(cache.should_refresh(&[], Duration::from_secs(60)).await);
 
         // Token with plenty of time doesn't need refresh
         let token = OAuth2Token::with_expiry("token", Duration::from_secs(3600));
-        cache.set(token).await;
-        assert!(!cache.should_refresh(Duration::from_secs(60)).await);
+        cache.set(&[], token).await;
+        assert!(!cache.should_refresh(&[], Duration::from_secs(60)).await);
     }
 }
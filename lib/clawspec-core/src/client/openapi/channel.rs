@@ -28,7 +28,7 @@ pub(in crate::client) enum CollectorMessage {
     },
 
     /// Register a complete operation after HTTP call.
-    RegisterOperation(CalledOperation),
+    RegisterOperation(Box<CalledOperation>),
 
     /// Register a response for an operation.
     RegisterResponse {
@@ -130,7 +130,7 @@ async fn collector_task(mut receiver: mpsc::Receiver<CollectorMessage>) {
                     .add_example_by_id(type_id, type_name, example);
             }
             CollectorMessage::RegisterOperation(operation) => {
-                collectors.collect_operation(operation);
+                collectors.collect_operation(*operation);
             }
             CollectorMessage::RegisterResponse {
                 operation_id,
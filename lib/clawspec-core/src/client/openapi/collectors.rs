@@ -4,8 +4,10 @@ use indexmap::IndexMap;
 use tracing::warn;
 use utoipa::openapi::{Content, PathItem, RefOr, Response, ResponseBuilder, Schema};
 
+use super::naming::SchemaNamingStrategy;
 use super::operation::{CalledOperation, merge_operation};
-use super::schema::Schemas;
+use super::schema::{NamedSchemas, Schemas};
+use crate::client::ApiClientError;
 
 /// Builds an OpenAPI response with optional schema and example.
 ///
@@ -121,6 +123,14 @@ impl Collectors {
         self.schemas.schema_vec()
     }
 
+    /// Returns the collected schemas named with a naming strategy.
+    pub(in crate::client) fn named_schemas(
+        &self,
+        naming: &SchemaNamingStrategy,
+    ) -> Result<NamedSchemas, ApiClientError> {
+        self.schemas.named_schema_vec(naming)
+    }
+
     /// Returns an iterator over collected operations.
     ///
     /// This method provides access to all operations that have been collected
@@ -148,7 +158,8 @@ impl Collectors {
             return;
         };
 
-        let response = build_response(description, content_type, schema, None);
+        let mut response = build_response(description, content_type, schema, None);
+        operation.document_set_cookie(status, &mut response);
 
         operation
             .operation
@@ -182,7 +193,8 @@ impl Collectors {
             .clone()
             .unwrap_or_else(|| format!("Status code {}", status.as_u16()));
 
-        let response = build_response(description, content_type, Some(schema), Some(example));
+        let mut response = build_response(description, content_type, Some(schema), Some(example));
+        operation.document_set_cookie(status, &mut response);
 
         operation
             .operation
@@ -191,6 +203,37 @@ impl Collectors {
             .insert(status.as_u16().to_string(), RefOr::T(response));
     }
 
+    /// Adds the actual response bodies of the recorded calls to a mock server.
+    ///
+    /// Examples already present in the specification take precedence.
+    #[cfg(feature = "mock-server")]
+    pub(in crate::client) fn add_recorded_examples(
+        &self,
+        base_path: &str,
+        mut server: crate::mock::MockServer,
+    ) -> crate::mock::MockServer {
+        for call in self.operations() {
+            let Some(result) = &call.result else {
+                continue;
+            };
+            let Ok(Some(example)) = result.output().as_example_value() else {
+                continue;
+            };
+            let content_type = result
+                .content_type()
+                .map_or_else(|| "application/json".to_string(), normalize_content_type);
+            let path = format!("{base_path}/{}", call.path.trim_start_matches('/'));
+            server = server.with_example(
+                call.method.clone(),
+                &path,
+                result.status(),
+                &content_type,
+                example,
+            );
+        }
+        server
+    }
+
     pub(in crate::client) fn as_map(&mut self, base_path: &str) -> IndexMap<String, PathItem> {
         /// Merges an operation into the appropriate field of a PathItem based on HTTP method.
         macro_rules! merge_into {
@@ -0,0 +1,571 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+use http::StatusCode;
+use serde_json::Value;
+use utoipa::openapi::extensions::Extensions;
+use utoipa::openapi::path::{Operation, Parameter};
+use utoipa::openapi::{Components, OpenApi, Ref, RefOr, Response};
+
+use crate::operations::iter_operations;
+
+const PARAMETERS: &str = "parameters";
+const PARAMETER_PREFIX: &str = "#/components/parameters/";
+
+/// A response or a parameter repeated across operations, given to naming hooks.
+///
+/// See [`ReusableComponents::with_naming`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum ReusableComponent<'a> {
+    /// A response, with its status code.
+    Response {
+        /// The status code, e.g. `404`.
+        status: &'a str,
+        /// The response.
+        response: &'a Response,
+    },
+    /// A parameter.
+    Parameter(&'a Parameter),
+}
+
+impl ReusableComponent<'_> {
+    /// Returns the default component name.
+    ///
+    /// Responses are named after the reason of their status code, e.g. `NotFound`, and
+    /// parameters after their name, e.g. `X-Request-Id`.
+    pub fn default_name(&self) -> String {
+        match self {
+            Self::Response { status, .. } => {
+                let reason = status
+                    .parse::<StatusCode>()
+                    .ok()
+                    .and_then(|status| status.canonical_reason());
+                match reason {
+                    Some(reason) => reason
+                        .split(|c: char| !c.is_ascii_alphanumeric())
+                        .collect::<String>(),
+                    None => format!("Response{status}"),
+                }
+            }
+            Self::Parameter(parameter) => parameter.name.clone(),
+        }
+    }
+}
+
+type NameFn = Arc<dyn Fn(ReusableComponent<'_>) -> String + Send + Sync>;
+
+/// Hoists the responses and parameters repeated across operations into components.
+///
+/// Error responses and common headers are usually identical for every operation. With
+/// this configuration, the responses and parameters found in at least
+/// [`threshold`](Self::with_threshold) operations are moved to `components/responses` and
+/// `components/parameters`, and replaced by `$ref`s in the operations.
+///
+/// Responses are compared by status code and content, examples included: responses
+/// with different examples are kept in their operations. Parameters are compared by
+/// content.
+///
+/// utoipa has no `components/parameters`, nor parameter references: the hoisted
+/// parameters, and the parameters of the operations using them, are stored as extensions,
+/// written in place of the `parameters` fields once the specification is serialized.
+/// Such a specification cannot be deserialized back into an [`OpenApi`]: read it as JSON,
+/// e.g. with [`bundle`](crate::split::bundle).
+///
+/// # Example
+///
+/// ```rust
+/// use clawspec_core::{ApiClient, ReusableComponent, ReusableComponents};
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = ApiClient::builder()
+///     .with_reusable_components(
+///         ReusableComponents::new()
+///             .with_threshold(3)
+///             .with_naming(|component| match component {
+///                 ReusableComponent::Parameter(parameter) => {
+///                     format!("{}Header", parameter.name.replace('-', ""))
+///                 }
+///                 _ => format!("{}Error", component.default_name()),
+///             }),
+///     )
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ReusableComponents {
+    threshold: usize,
+    naming: Option<NameFn>,
+}
+
+impl Debug for ReusableComponents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReusableComponents")
+            .field("threshold", &self.threshold)
+            .field("naming", &self.naming.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+impl Default for ReusableComponents {
+    fn default() -> Self {
+        Self {
+            threshold: 2,
+            naming: None,
+        }
+    }
+}
+
+impl ReusableComponents {
+    /// Creates a new configuration, hoisting what is repeated in at least two operations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the minimum number of operations sharing a response or a parameter.
+    pub fn with_threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold.max(2);
+        self
+    }
+
+    /// Sets the naming hook of the components.
+    ///
+    /// Names already taken get a numeric suffix, e.g. `NotFound2`.
+    pub fn with_naming(
+        mut self,
+        naming: impl Fn(ReusableComponent<'_>) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.naming = Some(Arc::new(naming));
+        self
+    }
+
+    fn name(&self, component: ReusableComponent<'_>) -> String {
+        match &self.naming {
+            Some(naming) => naming(component),
+            None => component.default_name(),
+        }
+    }
+
+    /// Hoists the repeated responses and parameters of a specification.
+    pub(in crate::client) fn apply(&self, openapi: &mut OpenApi) {
+        let mut responses = Candidates::default();
+        let mut parameters = Candidates::default();
+        let operations = openapi
+            .paths
+            .paths
+            .values()
+            .flat_map(iter_operations)
+            .map(|(_, operation)| operation);
+        for operation in operations {
+            for (status, response) in &operation.responses.responses {
+                if let RefOr::T(response) = response {
+                    responses.add(response_key(status, response), (status, response));
+                }
+            }
+            for parameter in operation.parameters.iter().flatten() {
+                parameters.add(parameter_key(parameter), parameter);
+            }
+        }
+
+        let mut taken = existing_names(openapi.components.as_ref());
+        let mut response_names = HashMap::new();
+        let mut response_components = BTreeMap::new();
+        for (key, (status, response)) in responses.repeated(self.threshold) {
+            let component = ReusableComponent::Response { status, response };
+            let name = unique_name(self.name(component), &mut taken);
+            response_components.insert(name.clone(), RefOr::T(response.clone()));
+            response_names.insert(key, name);
+        }
+        let mut parameter_names = HashMap::new();
+        let mut parameter_components = serde_json::Map::new();
+        for (key, parameter) in parameters.repeated(self.threshold) {
+            let name = unique_name(
+                self.name(ReusableComponent::Parameter(parameter)),
+                &mut taken,
+            );
+            let value = serde_json::to_value(parameter).unwrap_or_default();
+            parameter_components.insert(name.clone(), value);
+            parameter_names.insert(key, name);
+        }
+        if response_names.is_empty() && parameter_names.is_empty() {
+            return;
+        }
+
+        for operation in operations_mut(openapi) {
+            replace_responses(operation, &response_names);
+            replace_parameters(operation, &parameter_names);
+        }
+
+        let components = openapi.components.get_or_insert_with(Components::new);
+        components.responses.extend(response_components);
+        if !parameter_components.is_empty() {
+            let extensions = components
+                .extensions
+                .get_or_insert_with(Extensions::default);
+            match extensions.get_mut(PARAMETERS) {
+                Some(Value::Object(existing)) => existing.extend(parameter_components),
+                _ => {
+                    extensions.insert(PARAMETERS.to_string(), Value::Object(parameter_components));
+                }
+            }
+        }
+    }
+}
+
+/// The distinct candidates, in order of first appearance, with their occurrence count.
+struct Candidates<T> {
+    entries: Vec<(String, T, usize)>,
+    index: HashMap<String, usize>,
+}
+
+impl<T> Default for Candidates<T> {
+    fn default() -> Self {
+        Self {
+            entries: vec![],
+            index: HashMap::new(),
+        }
+    }
+}
+
+impl<T> Candidates<T> {
+    fn add(&mut self, key: String, candidate: T) {
+        match self.index.get(&key) {
+            Some(&index) => self.entries[index].2 += 1,
+            None => {
+                self.index.insert(key.clone(), self.entries.len());
+                self.entries.push((key, candidate, 1));
+            }
+        }
+    }
+
+    fn repeated(self, threshold: usize) -> impl Iterator<Item = (String, T)> {
+        self.entries
+            .into_iter()
+            .filter(move |(_, _, count)| *count >= threshold)
+            .map(|(key, candidate, _)| (key, candidate))
+    }
+}
+
+fn operations_mut(openapi: &mut OpenApi) -> impl Iterator<Item = &mut Operation> {
+    openapi.paths.paths.values_mut().flat_map(|item| {
+        [
+            &mut item.get,
+            &mut item.put,
+            &mut item.post,
+            &mut item.delete,
+            &mut item.options,
+            &mut item.head,
+            &mut item.patch,
+            &mut item.trace,
+        ]
+        .into_iter()
+        .flatten()
+    })
+}
+
+/// Returns the comparison key of a response: its status code and its content.
+fn response_key(status: &str, response: &Response) -> String {
+    let value = serde_json::to_value(response).unwrap_or_default();
+    format!("{status} {value}")
+}
+
+/// Returns the comparison key of a parameter: its content.
+fn parameter_key(parameter: &Parameter) -> String {
+    serde_json::to_value(parameter)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn existing_names(components: Option<&Components>) -> HashSet<String> {
+    let Some(components) = components else {
+        return HashSet::new();
+    };
+    let parameters = components
+        .extensions
+        .as_ref()
+        .and_then(|extensions| extensions.get(PARAMETERS))
+        .and_then(Value::as_object)
+        .into_iter()
+        .flat_map(|parameters| parameters.keys());
+    components
+        .responses
+        .keys()
+        .chain(parameters)
+        .cloned()
+        .collect()
+}
+
+fn unique_name(name: String, taken: &mut HashSet<String>) -> String {
+    let mut unique = name.clone();
+    let mut suffix = 2;
+    while taken.contains(&unique) {
+        unique = format!("{name}{suffix}");
+        suffix += 1;
+    }
+    taken.insert(unique.clone());
+    unique
+}
+
+fn replace_responses(operation: &mut Operation, names: &HashMap<String, String>) {
+    for (status, response) in operation.responses.responses.iter_mut() {
+        let RefOr::T(content) = response else {
+            continue;
+        };
+        if let Some(name) = names.get(&response_key(status, content)) {
+            *response = RefOr::Ref(Ref::from_response_name(name));
+        }
+    }
+}
+
+/// Replaces the hoisted parameters by references.
+///
+/// Operations can't hold parameter references, so all the parameters of the operation
+/// are moved to the `parameters` extension, serialized in place of the field.
+fn replace_parameters(operation: &mut Operation, names: &HashMap<String, String>) {
+    let Some(parameters) = &operation.parameters else {
+        return;
+    };
+    let keys: Vec<_> = parameters.iter().map(parameter_key).collect();
+    if !keys.iter().any(|key| names.contains_key(key)) {
+        return;
+    }
+
+    let values = parameters
+        .iter()
+        .zip(keys)
+        .map(|(parameter, key)| match names.get(&key) {
+            Some(name) => serde_json::json!({"$ref": format!("{PARAMETER_PREFIX}{name}")}),
+            None => serde_json::to_value(parameter).unwrap_or_default(),
+        })
+        .collect();
+    operation.parameters = None;
+    operation
+        .extensions
+        .get_or_insert_with(Extensions::default)
+        .insert(PARAMETERS.to_string(), Value::Array(values));
+}
+
+/// Returns the parameters of an operation, including the hoisted ones.
+pub(crate) fn operation_parameters(
+    operation: &Operation,
+    components: Option<&Components>,
+) -> Vec<Parameter> {
+    if let Some(parameters) = &operation.parameters {
+        return parameters.clone();
+    }
+    let Some(Value::Array(values)) = operation
+        .extensions
+        .as_ref()
+        .and_then(|extensions| extensions.get(PARAMETERS))
+    else {
+        return vec![];
+    };
+    let shared = components
+        .and_then(|components| components.extensions.as_ref())
+        .and_then(|extensions| extensions.get(PARAMETERS));
+    values
+        .iter()
+        .filter_map(|value| {
+            let value = match value.get("$ref").and_then(Value::as_str) {
+                Some(reference) => shared?.get(reference.strip_prefix(PARAMETER_PREFIX)?)?,
+                None => value,
+            };
+            serde_json::from_value(value.clone()).ok()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn error_response(example: &str) -> Value {
+        json!({
+            "description": "Status code 404",
+            "content": {"application/json": {
+                "schema": {"$ref": "#/components/schemas/ErrorBody"},
+                "example": {"message": example}
+            }}
+        })
+    }
+
+    fn spec() -> OpenApi {
+        let request_id = json!({"name": "X-Request-Id", "in": "header", "required": true, "schema": {"type": "string"}});
+        let id =
+            json!({"name": "id", "in": "path", "required": true, "schema": {"type": "integer"}});
+        serde_json::from_value(json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {
+                "/users/{id}": {
+                    "get": {
+                        "parameters": [id, request_id],
+                        "responses": {"200": {"description": "Status code 200"}, "404": error_response("no user")}
+                    },
+                    "delete": {
+                        "parameters": [id],
+                        "responses": {"204": {"description": "Status code 204"}, "404": error_response("no user")}
+                    }
+                },
+                "/orders": {
+                    "get": {
+                        "parameters": [request_id],
+                        "responses": {"200": {"description": "Status code 200"}, "404": error_response("no order")}
+                    }
+                }
+            },
+            "components": {
+                "schemas": {"ErrorBody": {"type": "object"}}
+            }
+        }))
+        .expect("should be a valid spec")
+    }
+
+    #[test]
+    fn should_hoist_repeated_responses_and_parameters() {
+        let mut openapi = spec();
+
+        ReusableComponents::new().apply(&mut openapi);
+
+        let value = serde_json::to_value(&openapi).expect("should serialize");
+        let pretty = |value: &Value| serde_json::to_string_pretty(value).expect("should format");
+        insta::assert_snapshot!(pretty(&value["components"]["responses"]), @r##"
+        {
+          "NotFound": {
+            "content": {
+              "application/json": {
+                "example": {
+                  "message": "no user"
+                },
+                "schema": {
+                  "$ref": "#/components/schemas/ErrorBody"
+                }
+              }
+            },
+            "description": "Status code 404"
+          },
+          "OK": {
+            "description": "Status code 200"
+          }
+        }
+        "##);
+        insta::assert_snapshot!(pretty(&value["components"]["parameters"]), @r#"
+        {
+          "X-Request-Id": {
+            "in": "header",
+            "name": "X-Request-Id",
+            "required": true,
+            "schema": {
+              "type": "string"
+            }
+          },
+          "id": {
+            "in": "path",
+            "name": "id",
+            "required": true,
+            "schema": {
+              "type": "integer"
+            }
+          }
+        }
+        "#);
+        insta::assert_snapshot!(pretty(&value["paths"]["/orders"]["get"]), @r##"
+        {
+          "parameters": [
+            {
+              "$ref": "#/components/parameters/X-Request-Id"
+            }
+          ],
+          "responses": {
+            "200": {
+              "$ref": "#/components/responses/OK"
+            },
+            "404": {
+              "content": {
+                "application/json": {
+                  "example": {
+                    "message": "no order"
+                  },
+                  "schema": {
+                    "$ref": "#/components/schemas/ErrorBody"
+                  }
+                }
+              },
+              "description": "Status code 404"
+            }
+          }
+        }
+        "##);
+        insta::assert_snapshot!(pretty(&value["paths"]["/users/{id}"]["get"]["parameters"]), @r##"
+        [
+          {
+            "$ref": "#/components/parameters/id"
+          },
+          {
+            "$ref": "#/components/parameters/X-Request-Id"
+          }
+        ]
+        "##);
+    }
+
+    #[test]
+    fn should_respect_threshold_and_naming() {
+        let mut openapi = spec();
+        ReusableComponents::new()
+            .with_threshold(3)
+            .apply(&mut openapi);
+        assert_eq!(openapi, spec());
+
+        ReusableComponents::new()
+            .with_naming(|component| format!("Common{}", component.default_name()))
+            .apply(&mut openapi);
+
+        let value = serde_json::to_value(&openapi).expect("should serialize");
+        let names = |section: &str| -> Vec<String> {
+            value["components"][section]
+                .as_object()
+                .map(|entries| entries.keys().cloned().collect())
+                .unwrap_or_default()
+        };
+        assert_eq!(names("responses"), vec!["CommonNotFound", "CommonOK"]);
+        assert_eq!(names("parameters"), vec!["CommonX-Request-Id", "Commonid"]);
+    }
+
+    #[test]
+    fn should_read_the_hoisted_parameters() {
+        let mut openapi = spec();
+        ReusableComponents::new().apply(&mut openapi);
+
+        let item = &openapi.paths.paths["/users/{id}"];
+        let operation = item.get.as_ref().expect("should have an operation");
+        assert!(operation.parameters.is_none());
+
+        let parameters = operation_parameters(operation, openapi.components.as_ref());
+        let expected = spec().paths.paths["/users/{id}"]
+            .get
+            .as_ref()
+            .and_then(|operation| operation.parameters.clone())
+            .expect("should have parameters");
+        assert_eq!(parameters, expected);
+    }
+
+    #[test]
+    fn should_bundle_the_serialized_specification() {
+        let mut openapi = spec();
+        ReusableComponents::new().apply(&mut openapi);
+        let value = serde_json::to_value(&openapi).expect("should serialize");
+
+        let dir =
+            std::env::temp_dir().join(format!("clawspec-components-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).expect("should create the directory");
+        let file = dir.join("openapi.json");
+        std::fs::write(&file, value.to_string()).expect("should write");
+        let bundled = crate::split::bundle(&file);
+        std::fs::remove_dir_all(&dir).expect("should clean up");
+
+        assert_eq!(bundled.expect("should bundle"), value);
+    }
+}
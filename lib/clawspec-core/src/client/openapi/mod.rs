@@ -5,12 +5,22 @@
 //! - [`CallResult`] - Response wrapper for deserializing and collecting schemas
 //! - [`RawResult`] - Raw response access before schema collection
 //! - [`RawBody`] - Raw response body content
+//! - [`SchemaNamingStrategy`] - Naming of the collected component schemas
+//! - [`ReusableComponents`] - Hoisting of the repeated responses and parameters
 //!
 //! Internal types for schema collection are not exported.
 
 pub(in crate::client) mod channel;
 pub(in crate::client) mod schema;
 
+mod components;
+pub(crate) use self::components::operation_parameters;
+pub use self::components::{ReusableComponent, ReusableComponents};
+
+mod naming;
+pub(in crate::client) use self::naming::rename_schemas;
+pub use self::naming::{SchemaNamingStrategy, SchemaType};
+
 mod result;
 // CallResult, RawResult, and RawBody are public API
 pub use self::result::{CallResult, RawBody, RawResult};
@@ -19,5 +29,6 @@ mod operation;
 pub(in crate::client) use self::operation::CalledOperation;
 
 mod collectors;
+mod stubs;
 // Collectors is internal to the client module
 pub(in crate::client) use self::collectors::Collectors;
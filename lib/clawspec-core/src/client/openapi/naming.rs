@@ -0,0 +1,538 @@
+use std::any::type_name;
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+use indexmap::IndexMap;
+use utoipa::openapi::OpenApi;
+
+use crate::client::ApiClientError;
+
+const SCHEMA_PREFIX: &str = "#/components/schemas/";
+
+/// The default template of generic schema names, e.g. `PageOfUser`.
+pub(in crate::client) const DEFAULT_GENERIC_TEMPLATE: &str = "{name}Of{args}";
+
+/// A Rust type collected as a component schema, given to custom naming functions.
+///
+/// See [`SchemaNamingStrategy::custom`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SchemaType<'a> {
+    /// The schema name, from [`ToSchema::name`](utoipa::ToSchema::name).
+    pub name: &'a str,
+    /// The full Rust path of the type, e.g. `my_crate::models::User`.
+    ///
+    /// `None` for the schemas only discovered as fields of other types, utoipa only
+    /// exposes their name.
+    pub type_path: Option<&'a str>,
+}
+
+impl SchemaType<'_> {
+    /// Returns the modules of the type path, without the generic arguments.
+    fn modules(&self) -> Vec<&str> {
+        let Some(type_path) = self.type_path else {
+            return vec![];
+        };
+        let path = type_path.split('<').next().unwrap_or(type_path);
+        let mut segments: Vec<_> = path.split("::").filter(|s| !s.is_empty()).collect();
+        segments.pop();
+        segments
+    }
+}
+
+type NameFn = Arc<dyn Fn(SchemaType<'_>) -> String + Send + Sync>;
+
+#[derive(Clone, Default)]
+enum NamingMode {
+    #[default]
+    ShortName,
+    FullPath,
+    LastSegments(usize),
+    Custom(NameFn),
+}
+
+impl Debug for NamingMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ShortName => f.write_str("ShortName"),
+            Self::FullPath => f.write_str("FullPath"),
+            Self::LastSegments(count) => f.debug_tuple("LastSegments").field(count).finish(),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// Strategy for naming the component schemas of the collected OpenAPI specification.
+///
+/// By default, schemas are named after [`ToSchema::name`](utoipa::ToSchema::name). When
+/// two distinct types share that name, the parent module is prepended (`module_Type`),
+/// falling back to a hash suffix, and a warning is logged. These names change when
+/// types are moved or added, a naming strategy keeps them stable:
+///
+/// - [`full_path`](Self::full_path): the full Rust path, `my_crate_models_User`
+/// - [`last_segments`](Self::last_segments): the last path segments, `models_User`
+/// - [`custom`](Self::custom): a closure
+/// - [`rename`](Self::rename) and [`rename_type`](Self::rename_type): explicit names,
+///   taking precedence over the strategy
+///
+/// Generic type instantiations can be named after their type arguments, `PageOfUser` for
+/// `Page<User>`, see [`generic_names`](Self::generic_names). Their components keep the
+/// `$ref`s to the type arguments, so the argument types need to be collected too, e.g.
+/// with [`ApiClient::register_schema`](crate::ApiClient::register_schema).
+///
+/// With [`fail_on_conflict`](Self::fail_on_conflict), and always for the names of
+/// [`last_segments`](Self::last_segments), conflicts are reported as
+/// [`ApiClientError::SchemaNameConflict`] by
+/// [`ApiClient::try_collected_openapi`](crate::ApiClient::try_collected_openapi), and
+/// therefore by the [`TestClient`](crate::test_client::TestClient) when writing the
+/// specification.
+///
+/// The `$ref`s of the specification are updated with the new names. Note that references
+/// are produced from the schema name, so when two distinct types share a schema name, the
+/// references to them cannot be told apart: rename one of them with
+/// `#[schema(as = ...)]`, or use [`fail_on_conflict`](Self::fail_on_conflict) to detect it.
+///
+/// # Example
+///
+/// ```rust
+/// use clawspec_core::{ApiClient, SchemaNamingStrategy, ToSchema};
+/// # #[derive(ToSchema)] struct UserDto { name: String }
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = ApiClient::builder()
+///     .with_schema_naming(
+///         SchemaNamingStrategy::last_segments(2)
+///             .rename_type::<UserDto>("User")
+///             .fail_on_conflict(),
+///     )
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SchemaNamingStrategy {
+    mode: NamingMode,
+    renames: IndexMap<String, String>,
+    generic_template: Option<String>,
+    fail_on_conflict: bool,
+    lenient: bool,
+}
+
+impl SchemaNamingStrategy {
+    /// Names schemas after [`ToSchema::name`](utoipa::ToSchema::name), the default.
+    ///
+    /// Conflicting names are disambiguated with the parent module (`module_Type`).
+    pub fn short_name() -> Self {
+        Self::default()
+    }
+
+    /// Names schemas after the full Rust path of the type, e.g. `my_crate_models_User`.
+    pub fn full_path() -> Self {
+        Self {
+            mode: NamingMode::FullPath,
+            ..Self::default()
+        }
+    }
+
+    /// Names schemas after the last `count` segments of the Rust path, e.g. `models_User`
+    /// for `last_segments(2)`.
+    ///
+    /// The segments are the type name, preceded by its last `count - 1` modules. Dropped
+    /// are the crate and the outer modules, the generic arguments, and the test and
+    /// internal modules `tests`, `test`, `_test`, `testing`, `internal` and `private`
+    /// wherever they are: `my_crate::api::tests::models::User` is named `models_User` for
+    /// `last_segments(2)`, and `api_models_User` for `last_segments(3)`.
+    ///
+    /// Distinct types can therefore resolve to the same name. These conflicts are always
+    /// reported as [`ApiClientError::SchemaNameConflict`] by
+    /// [`ApiClient::try_collected_openapi`](crate::ApiClient::try_collected_openapi), even
+    /// without [`fail_on_conflict`](Self::fail_on_conflict): keep more segments, or
+    /// [`rename_type`](Self::rename_type) one of the types.
+    pub fn last_segments(count: usize) -> Self {
+        Self {
+            mode: NamingMode::LastSegments(count.max(1)),
+            ..Self::default()
+        }
+    }
+
+    /// Names schemas with a closure.
+    ///
+    /// The closure is also called for the schemas only discovered as fields of other types,
+    /// without [`type_path`](SchemaType::type_path).
+    pub fn custom(name: impl Fn(SchemaType<'_>) -> String + Send + Sync + 'static) -> Self {
+        Self {
+            mode: NamingMode::Custom(Arc::new(name)),
+            ..Self::default()
+        }
+    }
+
+    /// Renames a schema, identified by its schema name or by the full Rust path of the type.
+    pub fn rename(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.renames.insert(from.into(), to.into());
+        self
+    }
+
+    /// Renames the schema of a Rust type.
+    pub fn rename_type<T: ?Sized + 'static>(self, to: impl Into<String>) -> Self {
+        self.rename(type_name::<T>(), to)
+    }
+
+    /// Names generic type instantiations after their type arguments, e.g. `PageOfUser` for
+    /// `Page<User>`.
+    ///
+    /// By default, the instantiations are named after the generic type, `Page`, like
+    /// utoipa does, and the instantiations of the same generic type are disambiguated as
+    /// conflicting names. This is the [`generic_template`](Self::generic_template)
+    /// `{name}Of{args}`.
+    pub fn generic_names(self) -> Self {
+        self.generic_template(DEFAULT_GENERIC_TEMPLATE)
+    }
+
+    /// Names generic type instantiations with a template, see
+    /// [`generic_names`](Self::generic_names).
+    ///
+    /// `{name}` is replaced by the schema name of the generic type, and `{args}` by the
+    /// names of the type arguments joined with `And`: `Page<User>` is named `PageOfUser`,
+    /// and `Pair<User, Order>` is named `PairOfUserAndOrder`. Nested arguments use the same
+    /// template, `Page<Vec<User>>` is named `PageOfVecOfUser`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use clawspec_core::SchemaNamingStrategy;
+    ///
+    /// // `Page<User>` is named `UserPage`
+    /// let naming = SchemaNamingStrategy::short_name().generic_template("{args}{name}");
+    /// ```
+    pub fn generic_template(mut self, template: impl Into<String>) -> Self {
+        self.generic_template = Some(template.into());
+        self
+    }
+
+    /// Reports schema name conflicts as errors, instead of only logging a warning.
+    pub fn fail_on_conflict(mut self) -> Self {
+        self.fail_on_conflict = true;
+        self
+    }
+
+    pub(in crate::client) fn is_default(&self) -> bool {
+        matches!(self.mode, NamingMode::ShortName)
+            && self.renames.is_empty()
+            && self.generic_template.is_none()
+            && !self.fail_on_conflict
+    }
+
+    pub(in crate::client) fn fails_on_conflict(&self) -> bool {
+        self.fail_on_conflict
+    }
+
+    /// Checks if two types resolving to the same name is an error, see
+    /// [`last_segments`](Self::last_segments).
+    pub(in crate::client) fn fails_on_resolved_conflict(&self) -> bool {
+        self.fail_on_conflict || (!self.lenient && matches!(self.mode, NamingMode::LastSegments(_)))
+    }
+
+    /// Returns the same strategy, only warning on conflicts.
+    pub(in crate::client) fn lenient(&self) -> Self {
+        Self {
+            fail_on_conflict: false,
+            lenient: true,
+            ..self.clone()
+        }
+    }
+
+    pub(in crate::client) fn is_short_name(&self) -> bool {
+        matches!(self.mode, NamingMode::ShortName)
+    }
+
+    /// Returns the name of a generic type instantiation, when generic names are enabled.
+    pub(in crate::client) fn generic_name(&self, generic: &GenericType) -> Option<String> {
+        self.generic_template
+            .as_deref()
+            .map(|template| generic.render(template))
+    }
+
+    /// Returns the explicit name of a schema, if any.
+    pub(in crate::client) fn renamed(&self, schema: SchemaType<'_>) -> Option<String> {
+        schema
+            .type_path
+            .and_then(|path| self.renames.get(path))
+            .or_else(|| self.renames.get(schema.name))
+            .cloned()
+    }
+
+    /// Returns the name of a schema.
+    pub(in crate::client) fn name(&self, schema: SchemaType<'_>) -> String {
+        if let Some(name) = self.renamed(schema) {
+            return name;
+        }
+        match &self.mode {
+            NamingMode::ShortName => schema.name.to_string(),
+            NamingMode::FullPath => join(schema.modules(), schema.name),
+            NamingMode::LastSegments(count) => {
+                let modules: Vec<_> = schema
+                    .modules()
+                    .into_iter()
+                    .filter(|module| !is_filtered_module(module))
+                    .collect();
+                let skip = modules.len().saturating_sub(count - 1);
+                join(modules[skip..].to_vec(), schema.name)
+            }
+            NamingMode::Custom(name) => name(schema),
+        }
+    }
+}
+
+/// A generic type instantiation, parsed from the Rust type path.
+///
+/// utoipa names all the instantiations of a generic type alike, e.g. `Page` for both
+/// `Page<User>` and `Page<Order>`, so they are named after their type arguments instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(in crate::client) struct GenericType {
+    name: String,
+    args: Vec<GenericType>,
+}
+
+impl GenericType {
+    /// Returns the schema name of the generic type, e.g. `Page`.
+    pub(in crate::client) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Parses a generic type instantiation, `None` if the type is not generic.
+    ///
+    /// Wrapper types delegating their schema name to a type argument, like `Box<User>`
+    /// named `User`, are not considered generic.
+    pub(in crate::client) fn parse(schema_name: &str, type_path: &str) -> Option<Self> {
+        let (_, args) = type_path.split_once('<')?;
+        let args = args.strip_suffix('>')?;
+        let generic = Self {
+            name: schema_name.to_string(),
+            args: split_args(args).into_iter().map(Self::parse_arg).collect(),
+        };
+        let delegates = generic
+            .args
+            .iter()
+            .any(|arg| arg.name.eq_ignore_ascii_case(schema_name));
+        (!generic.args.is_empty() && !delegates).then_some(generic)
+    }
+
+    fn parse_arg(arg: &str) -> Self {
+        let arg = arg.trim().trim_start_matches('&');
+        let arg = arg.strip_prefix("mut ").unwrap_or(arg);
+        let (path, args) = match arg.split_once('<') {
+            Some((path, args)) => (path, args.strip_suffix('>').unwrap_or(args)),
+            None => (arg, ""),
+        };
+        let short_name = path.rsplit("::").next().unwrap_or(path);
+        let mut name: String = short_name
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .collect();
+        if let Some(first) = name.get_mut(..1) {
+            first.make_ascii_uppercase();
+        }
+        Self {
+            name,
+            args: split_args(args).into_iter().map(Self::parse_arg).collect(),
+        }
+    }
+
+    /// Renders the name with a template.
+    pub(in crate::client) fn render(&self, template: &str) -> String {
+        if self.args.is_empty() {
+            return self.name.clone();
+        }
+        let args: Vec<_> = self.args.iter().map(|arg| arg.render(template)).collect();
+        template
+            .replace("{name}", &self.name)
+            .replace("{args}", &args.join("And"))
+    }
+}
+
+/// Splits the top-level comma-separated type arguments.
+fn split_args(args: &str) -> Vec<&str> {
+    let mut result = vec![];
+    let mut depth = 0_usize;
+    let mut start = 0;
+    for (index, char) in args.char_indices() {
+        match char {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                result.push(&args[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    result.push(&args[start..]);
+    result.retain(|arg| !arg.trim().is_empty());
+    result
+}
+
+/// Checks if a module should be skipped from schema names.
+pub(in crate::client) fn is_filtered_module(module: &str) -> bool {
+    matches!(
+        module,
+        "tests" | "test" | "_test" | "testing" | "internal" | "private"
+    )
+}
+
+fn join<'a>(mut segments: Vec<&'a str>, name: &'a str) -> String {
+    segments.push(name);
+    segments.join("_")
+}
+
+/// Renames the schema references of a specification.
+///
+/// The specification is left unchanged on error.
+pub(in crate::client) fn rename_schemas(
+    openapi: &mut OpenApi,
+    renames: &HashMap<String, String>,
+) -> Result<(), ApiClientError> {
+    if renames.is_empty() {
+        return Ok(());
+    }
+    let mut value = serde_json::to_value(&*openapi)?;
+    rename_refs(&mut value, renames);
+    *openapi = serde_json::from_value(value)?;
+    Ok(())
+}
+
+fn rename_refs(value: &mut serde_json::Value, renames: &HashMap<String, String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            if let Some(serde_json::Value::String(reference)) = map.get_mut("$ref")
+                && let Some(renamed) = reference
+                    .strip_prefix(SCHEMA_PREFIX)
+                    .and_then(|name| renames.get(name))
+            {
+                *reference = format!("{SCHEMA_PREFIX}{renamed}");
+            }
+            for child in map.values_mut() {
+                rename_refs(child, renames);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                rename_refs(item, renames);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema<'a>(name: &'a str, type_path: &'a str) -> SchemaType<'a> {
+        SchemaType {
+            name,
+            type_path: Some(type_path),
+        }
+    }
+
+    #[test]
+    fn should_name_schemas() {
+        let user = schema("User", "my_crate::api::tests::models::User");
+
+        assert_eq!(SchemaNamingStrategy::short_name().name(user), "User");
+        assert_eq!(
+            SchemaNamingStrategy::full_path().name(user),
+            "my_crate_api_tests_models_User"
+        );
+        assert_eq!(
+            SchemaNamingStrategy::last_segments(3).name(user),
+            "api_models_User"
+        );
+        assert_eq!(SchemaNamingStrategy::last_segments(0).name(user), "User");
+        assert_eq!(
+            SchemaNamingStrategy::custom(|schema| format!("{}Dto", schema.name)).name(user),
+            "UserDto"
+        );
+    }
+
+    #[test]
+    fn should_name_generic_types_after_their_modules() {
+        let page = schema("Page", "my_crate::Page<my_crate::models::User>");
+
+        assert_eq!(
+            SchemaNamingStrategy::full_path().name(page),
+            "my_crate_Page"
+        );
+    }
+
+    #[test]
+    fn should_name_generic_instantiations() {
+        let parse = |name, type_path| GenericType::parse(name, type_path);
+
+        let page = parse("Page", "my_crate::Page<my_crate::models::User>").expect("generic");
+        assert_eq!(page.render(DEFAULT_GENERIC_TEMPLATE), "PageOfUser");
+        assert_eq!(page.render("{args}{name}"), "UserPage");
+
+        let pair = parse(
+            "Pair",
+            "my_crate::Pair<alloc::vec::Vec<my_crate::User>, u32>",
+        )
+        .expect("generic");
+        assert_eq!(
+            pair.render(DEFAULT_GENERIC_TEMPLATE),
+            "PairOfVecOfUserAndU32"
+        );
+
+        let slice = parse("Page", "my_crate::Page<&[my_crate::User]>").expect("generic");
+        assert_eq!(slice.render(DEFAULT_GENERIC_TEMPLATE), "PageOfUser");
+
+        assert_eq!(parse("User", "my_crate::User"), None);
+        assert_eq!(parse("User", "alloc::boxed::Box<my_crate::User>"), None);
+        assert_eq!(parse("i32", "my_crate::DisplayArg<i32>"), None);
+    }
+
+    #[test]
+    fn should_prefer_explicit_renames() {
+        struct Local;
+        let naming = SchemaNamingStrategy::full_path()
+            .rename("Address", "PostalAddress")
+            .rename_type::<Local>("LocalThing");
+
+        assert_eq!(
+            naming.name(schema("Address", "my_crate::Address")),
+            "PostalAddress"
+        );
+        assert_eq!(
+            naming.name(schema("Local", type_name::<Local>())),
+            "LocalThing"
+        );
+        let nested = SchemaType {
+            name: "Address",
+            type_path: None,
+        };
+        assert_eq!(naming.name(nested), "PostalAddress");
+    }
+
+    #[test]
+    fn should_rename_schema_references() {
+        let mut openapi: OpenApi = serde_json::from_value(serde_json::json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {"/users": {"get": {"responses": {"200": {
+                "description": "ok",
+                "content": {"application/json": {"schema": {"$ref": "#/components/schemas/User"}}}
+            }}}}},
+            "components": {"schemas": {"UserDto": {"type": "object"}}}
+        }))
+        .expect("should be a valid spec");
+        let renames = HashMap::from([("User".to_string(), "UserDto".to_string())]);
+
+        rename_schemas(&mut openapi, &renames).expect("should rename");
+
+        let value = serde_json::to_value(openapi).expect("should serialize");
+        assert_eq!(
+            value["paths"]["/users"]["get"]["responses"]["200"]["content"]["application/json"]["schema"]
+                ["$ref"],
+            "#/components/schemas/UserDto"
+        );
+    }
+}
@@ -1,15 +1,19 @@
 use headers::ContentType;
+use http::StatusCode;
 use indexmap::IndexMap;
 use tracing::error;
-use utoipa::openapi::Content;
+use utoipa::openapi::header::HeaderBuilder;
 use utoipa::openapi::path::{Operation, Parameter};
 use utoipa::openapi::request_body::RequestBody;
+use utoipa::openapi::schema::{ObjectBuilder, Type};
 use utoipa::openapi::security::SecurityRequirement as UtoipaSecurityRequirement;
+use utoipa::openapi::{Content, Response};
 
 use super::collectors::normalize_content_type;
 use super::result::CallResult;
 use crate::client::call_parameters::{CallParameters, OperationMetadata};
-use crate::client::security::SecurityRequirement;
+use crate::client::parameters::PathResolved;
+use crate::client::security::{SecurityRequirement, SecurityScheme};
 use crate::client::{CallBody, CallPath};
 
 /// Represents a called operation with its metadata and potential result.
@@ -25,8 +29,53 @@ pub(in crate::client) struct CalledOperation {
     pub(super) path: String,
     pub(super) operation: Operation,
     pub(super) result: Option<CallResult>,
+    pub(super) request: RecordedRequest,
+    pub(super) provider_states: Vec<String>,
     #[cfg(feature = "redaction")]
     pub(super) response_description: Option<String>,
+    /// The security scheme inferred from the call authentication, with its name
+    pub(in crate::client) inferred_security_scheme: Option<(String, SecurityScheme)>,
+    /// The names of the cookies set by the response, stored in the cookie jar
+    pub(in crate::client) set_cookies: Vec<String>,
+}
+
+/// The concrete values sent for a called operation.
+///
+/// The OpenAPI operation only keeps the parameter schemas; these values are kept
+/// to export the recorded calls as stubs.
+#[derive(Debug, Clone, Default)]
+pub(in crate::client) struct RecordedRequest {
+    pub(super) path: String,
+    pub(super) query: Vec<(String, String)>,
+    pub(super) headers: Vec<(String, String)>,
+    pub(super) body: Option<serde_json::Value>,
+}
+
+impl RecordedRequest {
+    fn new(path: &CallPath, parameters: &CallParameters, request_body: Option<&CallBody>) -> Self {
+        let path = PathResolved::try_from(path.clone())
+            .map_or_else(|_| path.path.clone(), |resolved| resolved.path);
+        let query = parameters
+            .query
+            .to_query_string()
+            .map(|query| {
+                url::form_urlencoded::parse(query.as_bytes())
+                    .into_owned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        let headers = parameters.to_http_headers().unwrap_or_default();
+        let body = request_body
+            .filter(|body| body.content_type == ContentType::json())
+            .and_then(|body| serde_json::from_slice(&body.data).ok());
+
+        Self {
+            path,
+            query,
+            headers,
+            body,
+        }
+    }
 }
 
 impl CalledOperation {
@@ -39,6 +88,8 @@ impl CalledOperation {
         metadata: OperationMetadata,
         security: Option<Vec<SecurityRequirement>>,
     ) -> Self {
+        let request = RecordedRequest::new(path, &parameters, request_body);
+
         // Build parameters from path and CallParameters
         let mut all_parameters: Vec<_> = path.to_parameters().collect();
         all_parameters.extend(parameters.to_parameters());
@@ -103,19 +154,80 @@ impl CalledOperation {
             path: path_name.to_string(),
             operation,
             result: None,
+            request,
+            provider_states: metadata.provider_states,
             #[cfg(feature = "redaction")]
             response_description: metadata.response_description,
+            inferred_security_scheme: None,
+            set_cookies: vec![],
         }
     }
 
+    /// Replaces the security requirements, no requirement meaning a public operation.
+    pub(in crate::client) fn set_security(&mut self, security: Vec<SecurityRequirement>) {
+        let security = security
+            .iter()
+            .map(SecurityRequirement::to_utoipa)
+            .collect();
+        self.operation.security = Some(security);
+    }
+
     pub(in crate::client) fn add_response(&mut self, call_result: CallResult) {
         self.result = Some(call_result);
     }
 
+    /// Documents the `Set-Cookie` header of the response with the status, if it set cookies.
+    pub(super) fn document_set_cookie(&self, status: StatusCode, response: &mut Response) {
+        if self.set_cookies.is_empty()
+            || self.result.as_ref().map(CallResult::status) != Some(status)
+        {
+            return;
+        }
+        let cookies = self
+            .set_cookies
+            .iter()
+            .map(|name| format!("`{name}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let description = if self.set_cookies.len() == 1 {
+            format!("Sets the {cookies} cookie")
+        } else {
+            format!("Sets the {cookies} cookies")
+        };
+        let header = HeaderBuilder::new()
+            .schema(ObjectBuilder::new().schema_type(Type::String))
+            .description(Some(description))
+            .build();
+        response.headers.insert("Set-Cookie".to_string(), header);
+    }
+
     /// Gets the tags associated with this operation.
     pub(in crate::client) fn tags(&self) -> Option<&Vec<String>> {
         self.operation.tags.as_ref()
     }
+
+    /// Gets the scopes of the security requirements, by security scheme name.
+    pub(in crate::client) fn security_scopes(&self) -> Vec<(String, Vec<String>)> {
+        // utoipa does not expose the content of its security requirements
+        let requirements = serde_json::to_value(&self.operation.security).unwrap_or_default();
+        requirements
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(serde_json::Value::as_object)
+            .flatten()
+            .map(|(name, scopes)| {
+                let scopes = scopes
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(serde_json::Value::as_str)
+                    .map(ToString::to_string)
+                    .collect();
+                (name.clone(), scopes)
+            })
+            .collect()
+    }
 }
 
 /// Merges two OpenAPI operations for the same endpoint, combining their metadata.
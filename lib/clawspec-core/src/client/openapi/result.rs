@@ -1,8 +1,8 @@
 use std::any::{TypeId, type_name};
 
 use headers::{ContentType, Header};
-use http::StatusCode;
 use http::header::CONTENT_TYPE;
+use http::{HeaderMap, StatusCode};
 use reqwest::Response;
 use serde::de::DeserializeOwned;
 use utoipa::ToSchema;
@@ -86,6 +86,7 @@ pub struct CallResult {
     operation_id: String,
     status: StatusCode,
     content_type: Option<ContentType>,
+    headers: HeaderMap,
     output: Output,
     pub(in crate::client) collector_sender: CollectorSender,
 }
@@ -198,16 +199,15 @@ impl RawResult {
 impl CallResult {
     /// Returns the HTTP status code of the response.
     ///
-    /// Used by the redaction feature to register response examples.
-    #[cfg(feature = "redaction")]
+    /// Used to register response examples and export recorded calls.
     pub(in crate::client) fn status(&self) -> StatusCode {
         self.status
     }
 
     /// Returns the content type of the response, if present.
     ///
-    /// Used by the redaction feature to register response examples.
-    #[cfg(feature = "redaction")]
+    /// Used by the redaction feature and the mock server to register response examples.
+    #[cfg(any(feature = "redaction", feature = "mock-server"))]
     pub(in crate::client) fn content_type(&self) -> Option<&ContentType> {
         self.content_type.as_ref()
     }
@@ -222,12 +222,18 @@ impl CallResult {
 
     /// Returns a reference to the output.
     ///
-    /// Used by the redaction feature to access the JSON output for redaction.
-    #[cfg(feature = "redaction")]
+    /// Used by the redaction feature and the exports to access the recorded output.
     pub(in crate::client) fn output(&self) -> &Output {
         &self.output
     }
 
+    /// Returns the response headers.
+    ///
+    /// Used to export recorded calls as stubs.
+    pub(in crate::client) fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
     /// Extracts and parses the Content-Type header from the HTTP response.
     fn extract_content_type(response: &Response) -> Result<Option<ContentType>, ApiClientError> {
         let content_type = response
@@ -278,12 +284,14 @@ impl CallResult {
     ) -> Result<Self, ApiClientError> {
         let status = response.status();
         let content_type = Self::extract_content_type(&response)?;
+        let headers = response.headers().clone();
         let output = Self::process_response_body(response, &content_type, status).await?;
 
         Ok(Self {
             operation_id,
             status,
             content_type,
+            headers,
             output,
             collector_sender,
         })
@@ -294,12 +302,14 @@ impl CallResult {
     ) -> Result<Self, ApiClientError> {
         let status = response.status();
         let content_type = Self::extract_content_type(&response)?;
+        let headers = response.headers().clone();
         let output = Self::process_response_body(response, &content_type, status).await?;
 
         Ok(Self {
             operation_id: String::new(), // Empty operation_id since it won't be used
             status,
             content_type,
+            headers,
             output,
             collector_sender: CollectorSender::dummy(),
         })
@@ -1,6 +1,6 @@
 use std::any::{TypeId, type_name};
-use std::collections::HashSet;
 use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::hash::{Hash, Hasher};
 use std::sync::LazyLock;
@@ -9,6 +9,11 @@ use indexmap::{IndexMap, IndexSet};
 use utoipa::ToSchema;
 use utoipa::openapi::{Ref, RefOr, Schema};
 
+use super::naming::{
+    DEFAULT_GENERIC_TEMPLATE, GenericType, SchemaNamingStrategy, SchemaType, is_filtered_module,
+};
+use crate::client::ApiClientError;
+
 /// Set of primitive type names that should be inlined rather than referenced
 static PRIMITIVE_TYPES: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
     HashSet::from([
@@ -28,11 +33,31 @@ pub(in crate::client) fn compute_schema_ref<T>() -> RefOr<Schema>
 where
     T: ToSchema + 'static,
 {
-    let name = T::name();
-    if PRIMITIVE_TYPES.contains(name.as_ref()) {
+    let (name, _) = schema_name::<T>();
+    if PRIMITIVE_TYPES.contains(name.as_str()) {
         T::schema()
     } else {
-        RefOr::Ref(Ref::from_schema_name(name.as_ref()))
+        RefOr::Ref(Ref::from_schema_name(name))
+    }
+}
+
+/// Returns the schema name of a type.
+///
+/// Generic type instantiations are named after their type arguments, e.g. `PageOfUser`
+/// for `Page<User>`, since utoipa names them all after the generic type. Unless the
+/// [`SchemaNamingStrategy`] enables generic names, they are renamed back to the name of
+/// the generic type when collecting the specification.
+fn schema_name<T>() -> (String, Option<GenericType>)
+where
+    T: ToSchema + 'static,
+{
+    let name = T::name();
+    if PRIMITIVE_TYPES.contains(name.as_ref()) {
+        return (name.into_owned(), None);
+    }
+    match GenericType::parse(&name, type_name::<T>()) {
+        Some(generic) => (generic.render(DEFAULT_GENERIC_TEMPLATE), Some(generic)),
+        None => (name.into_owned(), None),
     }
 }
 
@@ -45,6 +70,8 @@ pub(in crate::client) struct Schemas {
     /// than `TypeId` since utoipa's API only exposes the name for these. A `TypeId`-backed
     /// entry with the same name always takes precedence (see `schema_vec`).
     nested: IndexMap<String, RefOr<Schema>>,
+    /// Nested schema names discovered with different shapes, see `absorb_nested`.
+    nested_conflicts: IndexSet<String>,
 }
 
 impl Debug for Schemas {
@@ -75,6 +102,7 @@ impl Schemas {
                     vacant.insert(schema);
                 }
                 indexmap::map::Entry::Occupied(occupied) if *occupied.get() != schema => {
+                    self.nested_conflicts.insert(occupied.key().clone());
                     tracing::warn!(
                         schema_name = %occupied.key(),
                         "Two distinct nested types resolve to the same schema name with \
@@ -210,8 +238,7 @@ impl Schemas {
             base_name.clone()
         } else {
             // Conflict detected - generate unique name using type path
-            let type_parts: Vec<&str> = target_entry
-                .type_name
+            let type_parts: Vec<&str> = type_path(&target_entry.type_name)
                 .split("::")
                 .filter(|part| !part.is_empty() && !Self::is_filtered_path_part(part))
                 .collect();
@@ -311,6 +338,7 @@ impl Schemas {
         }
 
         self.absorb_nested(other.nested);
+        self.nested_conflicts.extend(other.nested_conflicts);
     }
 
     pub(in crate::client) fn schema_vec(&self) -> Vec<(String, RefOr<Schema>)> {
@@ -380,6 +408,171 @@ impl Schemas {
         result
     }
 
+    /// Returns the schemas named with a naming strategy, and the renamed schema names.
+    ///
+    /// With the default strategy, this is [`schema_vec`](Self::schema_vec) without renames.
+    ///
+    /// # Errors
+    ///
+    /// When the strategy fails on conflicts, returns an error if two distinct types share
+    /// a schema name, or resolve to the same name.
+    pub(in crate::client) fn named_schema_vec(
+        &self,
+        naming: &SchemaNamingStrategy,
+    ) -> Result<NamedSchemas, ApiClientError> {
+        let has_generics = self.entries.values().any(|entry| entry.generic.is_some());
+        if naming.is_default() && !has_generics {
+            return Ok(NamedSchemas {
+                schemas: self.schema_vec(),
+                renames: HashMap::new(),
+            });
+        }
+
+        let registered: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| !entry.should_inline_schema())
+            .collect();
+        // The generic instantiations are referenced with the default template
+        let base_names: Vec<_> = registered
+            .iter()
+            .map(|(_, entry)| match &entry.generic {
+                Some(generic) => naming
+                    .generic_name(generic)
+                    .unwrap_or_else(|| generic.name().to_string()),
+                None => entry.name.clone(),
+            })
+            .collect();
+        let mut name_counts = HashMap::<String, u32>::new();
+        for name in &base_names {
+            *name_counts.entry(name.clone()).or_insert(0) += 1;
+        }
+        let mut reference_counts = HashMap::<&str, u32>::new();
+        for (_, entry) in &registered {
+            *reference_counts.entry(entry.name.as_str()).or_insert(0) += 1;
+        }
+
+        // References only hold the schema name, so types sharing it cannot be told apart
+        if naming.fails_on_conflict() {
+            if let Some((name, _)) = name_counts.iter().find(|(_, count)| **count > 1) {
+                let types = registered
+                    .iter()
+                    .zip(&base_names)
+                    .filter(|(_, base_name)| *base_name == name)
+                    .map(|((_, entry), _)| entry.type_name.clone())
+                    .collect();
+                return Err(conflict(name, types));
+            }
+            if let Some(name) = self.nested_conflicts.first() {
+                return Err(conflict(name, vec![]));
+            }
+        }
+
+        let mut result = NamedSchemas::default();
+        let mut owners = HashMap::<String, &str>::new();
+        for ((type_id, entry), base_name) in registered.iter().zip(&base_names) {
+            let generic_name = entry
+                .generic
+                .as_ref()
+                .and_then(|generic| naming.generic_name(generic));
+            let schema_type = SchemaType {
+                name: base_name,
+                type_path: Some(&entry.type_name),
+            };
+            let mut name = match naming.renamed(schema_type) {
+                Some(name) => name,
+                // The cached names are the reference names of the generic instantiations
+                None if naming.is_short_name() && entry.generic.is_some() => {
+                    if generic_name.is_some() {
+                        base_name.clone()
+                    } else {
+                        disambiguate(**type_id, base_name, &entry.type_name, &name_counts)
+                    }
+                }
+                None if naming.is_short_name() => {
+                    self.resolve_schema_name(**type_id, base_name, &entry.type_name, &name_counts)
+                }
+                None => naming.name(schema_type),
+            };
+
+            if let Some(owner) = owners.get(&name) {
+                if naming.fails_on_resolved_conflict() {
+                    return Err(conflict(
+                        &name,
+                        vec![owner.to_string(), entry.type_name.clone()],
+                    ));
+                }
+                let fallback_name = format!("{name}_{:x}", hash_type_id(**type_id));
+                tracing::warn!(
+                    type_name = %entry.type_name,
+                    schema_name = %name,
+                    fallback_name = %fallback_name,
+                    "Two distinct types resolve to the same schema name; using a hash suffix."
+                );
+                name = fallback_name;
+            }
+            owners.insert(name.clone(), &entry.type_name);
+
+            if reference_counts
+                .get(entry.name.as_str())
+                .copied()
+                .unwrap_or(0)
+                == 1
+                && name != entry.name
+            {
+                result.renames.insert(entry.name.clone(), name.clone());
+            }
+            result.schemas.push((name, entry.schema.clone()));
+        }
+
+        for (nested_name, schema) in &self.nested {
+            if name_counts.get(nested_name).copied().unwrap_or(0) == 1 {
+                // A directly registered type owns the name, see `schema_vec`
+                let same_shape =
+                    registered
+                        .iter()
+                        .zip(&base_names)
+                        .any(|((_, entry), base_name)| {
+                            base_name == nested_name && &entry.schema == schema
+                        });
+                if !same_shape {
+                    if naming.fails_on_conflict() {
+                        return Err(conflict(nested_name, vec![]));
+                    }
+                    tracing::warn!(
+                        schema_name = %nested_name,
+                        "Nested schema name collides with a directly registered schema of \
+                         the same name but a different shape; keeping the directly \
+                         registered one."
+                    );
+                }
+                continue;
+            }
+
+            let name = naming.name(SchemaType {
+                name: nested_name,
+                type_path: None,
+            });
+            if owners.contains_key(&name) {
+                if naming.fails_on_resolved_conflict() {
+                    return Err(conflict(&name, vec![]));
+                }
+                tracing::warn!(
+                    schema_name = %name,
+                    "Nested schema resolves to the name of another schema; skipping it."
+                );
+                continue;
+            }
+            owners.insert(name.clone(), nested_name);
+            if &name != nested_name {
+                result.renames.insert(nested_name.clone(), name.clone());
+            }
+            result.schemas.push((name, schema.clone()));
+        }
+
+        Ok(result)
+    }
+
     /// Resolves schema name for a specific entry without requiring mutable access
     fn resolve_schema_name(
         &self,
@@ -393,52 +586,79 @@ impl Schemas {
             return cached_name.clone();
         }
 
-        // If no conflict, use original name
-        if name_counts.get(base_name).copied().unwrap_or(0) <= 1 {
-            return base_name.to_string();
-        }
+        disambiguate(type_id, base_name, type_name, name_counts)
+    }
 
-        // Conflict detected - generate unique name using type path
-        let type_parts: Vec<&str> = type_name
-            .split("::")
-            .filter(|part| !part.is_empty() && !Self::is_filtered_path_part(part))
-            .collect();
+    /// Checks if a path part should be filtered out from namespace generation
+    fn is_filtered_path_part(part: &str) -> bool {
+        is_filtered_module(part)
+    }
+}
 
-        if type_parts.len() >= 2 {
-            // Use the last two parts for namespace (e.g., "module::Type")
-            format!("{}_{}", type_parts[type_parts.len() - 2], base_name)
-        } else {
-            // Fallback: use a more readable hash-based suffix
-            use std::collections::hash_map::DefaultHasher;
-            use std::hash::{Hash, Hasher};
-            let mut hasher = DefaultHasher::new();
-            type_id.hash(&mut hasher);
-            let hash = hasher.finish();
-            let fallback_name = format!("{base_name}_{:x}", hash & 0xFFFF);
-
-            // Warn about fallback naming for debugging purposes
-            tracing::warn!(
-                type_name = %type_name,
-                base_name = %base_name,
-                fallback_name = %fallback_name,
-                "Schema conflict resolved using hash-based fallback naming. \
-                 Consider using more specific module structure for better naming."
-            );
+/// Resolves the name of a type, namespaced with its module when the name is shared.
+fn disambiguate(
+    type_id: TypeId,
+    base_name: &str,
+    type_name: &str,
+    name_counts: &HashMap<String, u32>,
+) -> String {
+    // If no conflict, use original name
+    if name_counts.get(base_name).copied().unwrap_or(0) <= 1 {
+        return base_name.to_string();
+    }
 
-            fallback_name
-        }
+    // Conflict detected - generate unique name using type path
+    let type_parts: Vec<&str> = type_path(type_name)
+        .split("::")
+        .filter(|part| !part.is_empty() && !Schemas::is_filtered_path_part(part))
+        .collect();
+
+    if type_parts.len() >= 2 {
+        // Use the last two parts for namespace (e.g., "module::Type")
+        format!("{}_{}", type_parts[type_parts.len() - 2], base_name)
+    } else {
+        // Fallback: use a more readable hash-based suffix
+        let fallback_name = format!("{base_name}_{:x}", hash_type_id(type_id));
+
+        // Warn about fallback naming for debugging purposes
+        tracing::warn!(
+            type_name = %type_name,
+            base_name = %base_name,
+            fallback_name = %fallback_name,
+            "Schema conflict resolved using hash-based fallback naming. \
+             Consider using more specific module structure for better naming."
+        );
+
+        fallback_name
     }
+}
 
-    /// Checks if a path part should be filtered out from namespace generation
-    fn is_filtered_path_part(part: &str) -> bool {
-        // Filter out common test-related and internal modules
-        matches!(
-            part,
-            "tests" | "test" | "_test" | "testing" | "internal" | "private"
-        )
+/// Returns the type path without the generic arguments.
+fn type_path(type_name: &str) -> &str {
+    type_name.split('<').next().unwrap_or(type_name)
+}
+
+/// Schemas named with a [`SchemaNamingStrategy`].
+#[derive(Debug, Default)]
+pub(in crate::client) struct NamedSchemas {
+    pub(in crate::client) schemas: Vec<(String, RefOr<Schema>)>,
+    /// The schema names replaced in the references.
+    pub(in crate::client) renames: HashMap<String, String>,
+}
+
+fn conflict(name: &str, types: Vec<String>) -> ApiClientError {
+    ApiClientError::SchemaNameConflict {
+        name: name.to_string(),
+        types,
     }
 }
 
+fn hash_type_id(type_id: TypeId) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    type_id.hash(&mut hasher);
+    hasher.finish() & 0xFFFF
+}
+
 #[derive(Clone, derive_more::Display, derive_more::Debug)]
 #[display("[{id:?}] {name}")]
 pub(in crate::client) struct SchemaEntry {
@@ -455,6 +675,9 @@ pub(in crate::client) struct SchemaEntry {
     /// `Schemas::absorb_nested`).
     #[debug(ignore)]
     pub(in crate::client) nested: Vec<(String, RefOr<Schema>)>,
+    /// The generic type instantiation, `name` is rendered with the default template.
+    #[debug(ignore)]
+    pub(in crate::client) generic: Option<GenericType>,
 }
 
 impl SchemaEntry {
@@ -463,17 +686,18 @@ impl SchemaEntry {
         T: ToSchema + 'static,
     {
         let id = TypeId::of::<T>();
-        let name = T::name();
+        let (name, generic) = schema_name::<T>();
         let type_name = type_name::<T>();
         let mut nested = Vec::new();
         T::schemas(&mut nested);
         Self {
             id,
             type_name: type_name.to_string(),
-            name: name.to_string(),
+            name,
             schema: T::schema(),
             examples: IndexSet::default(),
             nested,
+            generic,
         }
     }
 
@@ -504,6 +728,7 @@ impl SchemaEntry {
             schema,
             examples: IndexSet::default(),
             nested: Vec::new(),
+            generic: None,
         }
     }
 
@@ -1165,6 +1390,7 @@ mod tests {
             schema: RefOr::T(utoipa::openapi::Schema::Object(Default::default())),
             examples: IndexSet::default(),
             nested: Vec::new(),
+            generic: None,
         };
 
         // Add the same name from another "type" to force conflict
@@ -1175,6 +1401,7 @@ mod tests {
             schema: RefOr::T(utoipa::openapi::Schema::Object(Default::default())),
             examples: IndexSet::default(),
             nested: Vec::new(),
+            generic: None,
         };
 
         schemas.entries.insert(simple_entry.id, simple_entry);
@@ -1419,4 +1646,223 @@ mod tests {
         assert!(schemas1.entries[&v1_user_id].examples.contains(&example2));
         assert!(schemas1.entries[&v2_user_id].examples.contains(&example3));
     }
+
+    mod naming_models {
+        use super::*;
+
+        #[derive(Debug, ToSchema, Serialize)]
+        pub struct Address {
+            pub street: String,
+        }
+
+        #[derive(Debug, ToSchema, Serialize)]
+        pub struct User {
+            pub address: Address,
+        }
+
+        pub mod v2 {
+            use super::*;
+
+            #[derive(Debug, ToSchema, Serialize)]
+            pub struct User {
+                pub name: String,
+            }
+        }
+
+        pub mod internal {
+            use super::*;
+
+            #[derive(Debug, ToSchema, Serialize)]
+            pub struct User {
+                pub id: u32,
+            }
+        }
+    }
+
+    fn names(named: &NamedSchemas) -> Vec<&str> {
+        named
+            .schemas
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn should_name_schemas_with_last_segments() {
+        let mut schemas = Schemas::default();
+        schemas.add::<naming_models::User>();
+
+        let named = schemas
+            .named_schema_vec(&SchemaNamingStrategy::last_segments(2))
+            .expect("should name schemas");
+
+        // Nested schemas only expose their name
+        assert_eq!(names(&named), vec!["naming_models_User", "Address"]);
+        assert_eq!(named.renames["User"], "naming_models_User");
+    }
+
+    #[test]
+    fn should_report_conflicts_of_the_dropped_segments() {
+        let mut schemas = Schemas::default();
+        schemas.add::<naming_models::User>();
+        schemas.add::<naming_models::internal::User>();
+
+        let naming = SchemaNamingStrategy::last_segments(2);
+        let error = schemas
+            .named_schema_vec(&naming)
+            .expect_err("should report the conflict");
+        let ApiClientError::SchemaNameConflict { name, types } = error else {
+            panic!("unexpected error: {error}");
+        };
+        assert_eq!(name, "naming_models_User");
+        assert_eq!(types.len(), 2);
+
+        // The lenient naming of `collected_openapi` falls back to a hash suffix
+        let named = schemas
+            .named_schema_vec(&naming.lenient())
+            .expect("should name schemas");
+        assert_eq!(names(&named)[0], "naming_models_User");
+        assert!(names(&named)[1].starts_with("naming_models_User_"));
+    }
+
+    #[test]
+    fn should_apply_explicit_renames() {
+        let mut schemas = Schemas::default();
+        schemas.add::<naming_models::User>();
+
+        let naming = SchemaNamingStrategy::short_name()
+            .rename_type::<naming_models::User>("Account")
+            .rename("Address", "PostalAddress");
+        let named = schemas
+            .named_schema_vec(&naming)
+            .expect("should name schemas");
+
+        assert_eq!(names(&named), vec!["Account", "PostalAddress"]);
+        assert_eq!(named.renames.len(), 2);
+    }
+
+    #[test]
+    fn should_fail_on_schema_name_conflict() {
+        let mut schemas = Schemas::default();
+        schemas.add::<naming_models::User>();
+        schemas.add::<naming_models::v2::User>();
+
+        let lenient = schemas
+            .named_schema_vec(&SchemaNamingStrategy::short_name())
+            .expect("should name schemas");
+        assert_eq!(names(&lenient), vec!["User", "v2_User", "Address"]);
+
+        let error = schemas
+            .named_schema_vec(&SchemaNamingStrategy::short_name().fail_on_conflict())
+            .expect_err("should detect the conflict");
+        let ApiClientError::SchemaNameConflict { name, types } = error else {
+            panic!("unexpected error: {error}");
+        };
+        assert_eq!(name, "User");
+        assert_eq!(types.len(), 2);
+    }
+
+    #[test]
+    fn should_fail_on_resolved_name_conflict() {
+        let mut schemas = Schemas::default();
+        schemas.add::<naming_models::User>();
+        schemas.add::<naming_models::Address>();
+
+        let naming = SchemaNamingStrategy::custom(|_| "Same".to_string());
+        let named = schemas
+            .named_schema_vec(&naming)
+            .expect("should name schemas");
+        assert_eq!(named.schemas.len(), 2);
+        assert!(named.schemas[1].0.starts_with("Same_"));
+
+        let error = schemas
+            .named_schema_vec(&naming.fail_on_conflict())
+            .expect_err("should detect the conflict");
+        assert!(matches!(
+            error,
+            ApiClientError::SchemaNameConflict { ref name, .. } if name == "Same"
+        ));
+    }
+
+    #[derive(Debug, ToSchema, Serialize)]
+    struct Page<T> {
+        items: Vec<T>,
+        total: u32,
+    }
+
+    #[test]
+    fn should_name_generic_instantiations() {
+        let mut schemas = Schemas::default();
+        let users = schemas.add::<Page<naming_models::User>>();
+        schemas.add::<Page<naming_models::Address>>();
+
+        assert_eq!(users, compute_schema_ref::<Page<naming_models::User>>());
+        assert_eq!(users, RefOr::Ref(Ref::from_schema_name("PageOfUser")));
+
+        let schema_vec = schemas.schema_vec();
+        let names: Vec<_> = schema_vec.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["PageOfUser", "PageOfAddress", "Address"]);
+
+        // The component keeps the reference to the type argument
+        let page = serde_json::to_value(&schema_vec[0].1).expect("should serialize");
+        assert_eq!(
+            page["properties"]["items"]["items"]["$ref"],
+            "#/components/schemas/User"
+        );
+    }
+
+    #[test]
+    fn should_keep_the_generic_type_name_by_default() {
+        let mut schemas = Schemas::default();
+        schemas.add::<Page<naming_models::User>>();
+
+        let named = schemas
+            .named_schema_vec(&SchemaNamingStrategy::default())
+            .expect("should name schemas");
+
+        assert_eq!(names(&named), vec!["Page", "Address"]);
+        assert_eq!(named.renames["PageOfUser"], "Page");
+
+        schemas.add::<Page<naming_models::Address>>();
+        let named = schemas
+            .named_schema_vec(&SchemaNamingStrategy::default())
+            .expect("should name schemas");
+
+        assert_eq!(names(&named)[2], "Address");
+        assert!(names(&named)[0].starts_with("schema_Page"));
+        assert!(names(&named)[1].starts_with("schema_Page_"));
+        assert_ne!(named.renames["PageOfUser"], named.renames["PageOfAddress"]);
+    }
+
+    #[test]
+    fn should_name_generic_instantiations_when_enabled() {
+        let mut schemas = Schemas::default();
+        schemas.add::<Page<naming_models::User>>();
+        schemas.add::<Page<naming_models::Address>>();
+
+        let naming = SchemaNamingStrategy::short_name().generic_names();
+        let named = schemas
+            .named_schema_vec(&naming)
+            .expect("should name schemas");
+
+        assert_eq!(
+            names(&named),
+            vec!["PageOfUser", "PageOfAddress", "Address"]
+        );
+        assert!(named.renames.is_empty());
+    }
+
+    #[test]
+    fn should_name_generic_instantiations_with_template() {
+        let mut schemas = Schemas::default();
+        schemas.add::<Page<naming_models::User>>();
+
+        let naming = SchemaNamingStrategy::short_name().generic_template("{args}{name}");
+        let named = schemas
+            .named_schema_vec(&naming)
+            .expect("should name schemas");
+
+        assert_eq!(names(&named), vec!["UserPage", "Address"]);
+        assert_eq!(named.renames["PageOfUser"], "UserPage");
+    }
 }
@@ -0,0 +1,340 @@
+use std::collections::BTreeMap;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use http::header::{CONNECTION, CONTENT_LENGTH, CONTENT_TYPE, DATE, TRANSFER_ENCODING};
+use indexmap::map::Entry;
+use serde_json::Value;
+use tracing::warn;
+use utoipa::openapi::{Content, RefOr, Schema};
+
+use super::collectors::Collectors;
+use super::operation::CalledOperation;
+use crate::client::response::output::Output;
+use crate::export::pact::matching_rules;
+use crate::export::wiremock::url_path_pattern;
+use crate::export::{
+    PactInteraction, PactRequest, PactResponse, WireMockBodyPattern, WireMockMapping,
+    WireMockMappings, WireMockMatcher, WireMockRequest, WireMockResponse,
+};
+
+impl Collectors {
+    /// Exports the recorded calls as WireMock stub mappings.
+    ///
+    /// The values of the headers and query parameters matching [`is_sensitive`] are
+    /// never exported.
+    ///
+    /// Each call with a recorded response produces a mapping. When the same request was
+    /// sent several times with the same response, a single mapping is exported.
+    /// When the responses differ, the mappings are chained in a scenario replaying
+    /// the responses in the recorded order.
+    pub(in crate::client) fn wiremock_mappings(&self, base_path: &str) -> WireMockMappings {
+        let mut mappings: Vec<WireMockMapping> = vec![];
+
+        for call in self.operations() {
+            let Some(mut mapping) = wiremock_mapping(call, base_path) else {
+                continue;
+            };
+            let same_request: Vec<usize> = mappings
+                .iter()
+                .enumerate()
+                .filter(|(_, existing)| existing.request == mapping.request)
+                .map(|(index, _)| index)
+                .collect();
+            if same_request
+                .iter()
+                .any(|&index| mappings[index].response == mapping.response)
+            {
+                continue;
+            }
+
+            // Chain the different responses of the same request in a scenario
+            if let Some(&last) = same_request.last() {
+                let first = &mappings[same_request[0]];
+                let scenario = first.name.clone();
+                let state = format!("{scenario}-{}", same_request.len() + 1);
+                if same_request.len() == 1 {
+                    let first = &mut mappings[same_request[0]];
+                    first.scenario_name = Some(scenario.clone());
+                    first.required_scenario_state = Some(SCENARIO_STARTED.to_string());
+                }
+                mappings[last].new_scenario_state = Some(state.clone());
+                mapping.scenario_name = Some(scenario);
+                mapping.required_scenario_state = Some(state);
+            }
+
+            // Keep mapping names unique
+            mapping.name = unique_name(&mapping.name, |name| {
+                mappings.iter().any(|existing| existing.name == name)
+            });
+            mappings.push(mapping);
+        }
+
+        WireMockMappings { mappings }
+    }
+
+    /// Exports the recorded calls as Pact interactions.
+    ///
+    /// Matching rules are derived from the request and response schemas. When the same
+    /// request was sent several times with the same provider states, only the first
+    /// recorded response is exported, a warning is logged if the responses differ.
+    pub(in crate::client) fn pact_interactions(&self, base_path: &str) -> Vec<PactInteraction> {
+        let schemas: BTreeMap<_, _> = self.schemas().into_iter().collect();
+        let mut interactions: Vec<PactInteraction> = vec![];
+
+        for call in self.operations() {
+            let Some(interaction) = pact_interaction(call, base_path, &schemas) else {
+                continue;
+            };
+            if let Some(existing) = interactions.iter().find(|existing| {
+                existing.request == interaction.request
+                    && existing.provider_states == interaction.provider_states
+            }) {
+                if existing.response != interaction.response {
+                    warn!(
+                        description = %interaction.description,
+                        "the same request has different responses, only the first one is exported, use provider states to distinguish them"
+                    );
+                }
+                continue;
+            }
+
+            // Pact requires unique descriptions
+            let mut index = 1;
+            let mut description = interaction.description.clone();
+            while interactions
+                .iter()
+                .any(|existing| existing.description == description)
+            {
+                index += 1;
+                description = format!("{} ({index})", interaction.description);
+            }
+            interactions.push(PactInteraction {
+                description,
+                ..interaction
+            });
+        }
+
+        interactions
+    }
+}
+
+/// The initial state of a WireMock scenario.
+const SCENARIO_STARTED: &str = "Started";
+
+/// The replacement of the sensitive values in the exported stubs.
+const REDACTED: &str = "[REDACTED]";
+
+/// The names of the headers holding credentials.
+const SENSITIVE_HEADERS: [&str; 4] = [
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+];
+
+/// The parts of the header and parameter names usually holding credentials.
+const SENSITIVE_PARTS: [&str; 6] = [
+    "api-key", "api_key", "apikey", "token", "secret", "password",
+];
+
+/// Returns `true` if a header or query parameter probably holds credentials.
+fn is_sensitive(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SENSITIVE_HEADERS.contains(&name.as_str())
+        || SENSITIVE_PARTS.iter().any(|part| name.contains(part))
+}
+
+/// Redacts the value of a sensitive response header, keeping the `Set-Cookie` attributes.
+fn redact_header(name: &str, value: &str) -> String {
+    if !name.eq_ignore_ascii_case("set-cookie") {
+        return REDACTED.to_string();
+    }
+    let (cookie, attributes) = value.split_once(';').unwrap_or((value, ""));
+    let name = cookie.split_once('=').map_or(cookie, |(name, _)| name);
+    match attributes {
+        "" => format!("{name}={REDACTED}"),
+        attributes => format!("{name}={REDACTED};{attributes}"),
+    }
+}
+
+/// Returns the WireMock matcher of a recorded value, not exporting the credentials.
+fn matcher(name: &str, value: &str) -> WireMockMatcher {
+    if is_sensitive(name) {
+        WireMockMatcher::present()
+    } else {
+        WireMockMatcher::equal_to(value)
+    }
+}
+
+/// Returns the name, suffixed with an index if already used.
+fn unique_name(name: &str, is_used: impl Fn(&str) -> bool) -> String {
+    let mut index = 1;
+    let mut unique = name.to_string();
+    while is_used(&unique) {
+        index += 1;
+        unique = format!("{name}-{index}");
+    }
+    unique
+}
+
+fn pact_interaction(
+    call: &CalledOperation,
+    base_path: &str,
+    schemas: &BTreeMap<String, RefOr<Schema>>,
+) -> Option<PactInteraction> {
+    let result = call.result.as_ref()?;
+    let status = result.status();
+
+    let mut request = PactRequest {
+        method: call.method.to_string(),
+        path: format!("{base_path}/{}", call.request.path.trim_start_matches('/')),
+        body: call.request.body.clone(),
+        ..PactRequest::default()
+    };
+    for (name, value) in &call.request.query {
+        request
+            .query
+            .entry(name.clone())
+            .or_default()
+            .push(value.clone());
+    }
+    // The credentials are not exported, the provider verification sends its own
+    for (name, value) in &call.request.headers {
+        if !is_sensitive(name) {
+            request.headers.insert(name.clone(), value.clone());
+        }
+    }
+    if request.body.is_some()
+        && let Some((content_type, content)) = call
+            .operation
+            .request_body
+            .as_ref()
+            .and_then(|body| body.content.iter().next())
+    {
+        request
+            .headers
+            .insert("Content-Type".to_string(), content_type.clone());
+        if let Some(schema) = &content.schema {
+            request.matching_rules = matching_rules(schema, schemas);
+        }
+    }
+
+    let response_content = documented_content(call, status.as_u16());
+    let mut response = PactResponse {
+        status: status.as_u16(),
+        body: response_body(response_content, result.output()),
+        ..PactResponse::default()
+    };
+    if let Some(content_type) = result
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+    {
+        response
+            .headers
+            .insert("Content-Type".to_string(), content_type.to_string());
+    }
+    if response.body.is_some()
+        && let Some(schema) = response_content.and_then(|content| content.schema.as_ref())
+    {
+        response.matching_rules = matching_rules(schema, schemas);
+    }
+
+    let description = call
+        .operation
+        .description
+        .clone()
+        .unwrap_or_else(|| format!("{} {}", call.method, call.path));
+
+    Some(PactInteraction {
+        description,
+        provider_states: call.provider_states.clone(),
+        request,
+        response,
+    })
+}
+
+/// Returns the documented response content of a call for the given status.
+fn documented_content(call: &CalledOperation, status: u16) -> Option<&Content> {
+    match call
+        .operation
+        .responses
+        .responses
+        .get(&status.to_string())?
+    {
+        RefOr::T(response) => response.content.values().next(),
+        RefOr::Ref(_) => None,
+    }
+}
+
+/// Returns the response body, preferring the documented (redacted) example.
+fn response_body(content: Option<&Content>, output: &Output) -> Option<Value> {
+    match (content.and_then(|c| c.example.clone()), output) {
+        (Some(example), Output::Json(_)) => Some(example),
+        (_, Output::Json(json)) => serde_json::from_str(json).ok(),
+        (_, Output::Text(text) | Output::Other { body: text }) => Some(Value::String(text.clone())),
+        (_, Output::Bytes(_) | Output::Empty) => None,
+    }
+}
+
+fn wiremock_mapping(call: &CalledOperation, base_path: &str) -> Option<WireMockMapping> {
+    let result = call.result.as_ref()?;
+    let status = result.status();
+
+    let path = format!("{base_path}/{}", call.path.trim_start_matches('/'));
+    let mut request = WireMockRequest::new(call.method.as_str(), url_path_pattern(&path));
+    for (name, value) in &call.request.query {
+        request
+            .query_parameters
+            .insert(name.clone(), matcher(name, value));
+    }
+    for (name, value) in &call.request.headers {
+        request.headers.insert(name.clone(), matcher(name, value));
+    }
+    if let Some(body) = &call.request.body {
+        request
+            .body_patterns
+            .push(WireMockBodyPattern::equal_to_json(body.clone()));
+    }
+
+    let mut response = WireMockResponse::new(status.as_u16());
+    for (name, value) in result.headers() {
+        if [&CONNECTION, &CONTENT_LENGTH, &DATE, &TRANSFER_ENCODING].contains(&name) {
+            continue;
+        }
+        if let Ok(value) = value.to_str() {
+            let value = if is_sensitive(name.as_str()) {
+                redact_header(name.as_str(), value)
+            } else {
+                value.to_string()
+            };
+            match response.headers.entry(name.as_str().to_string()) {
+                Entry::Occupied(mut entry) => entry.get_mut().push(value),
+                Entry::Vacant(entry) => {
+                    entry.insert(value.into());
+                }
+            }
+        }
+    }
+
+    // The example registered in the operation is the redacted body, when available
+    let documented_example =
+        documented_content(call, status.as_u16()).and_then(|content| content.example.clone());
+
+    match (documented_example, result.output()) {
+        (Some(example), Output::Json(_)) => response.json_body = Some(example),
+        (_, Output::Json(json)) => response.json_body = serde_json::from_str(json).ok(),
+        (_, Output::Text(text) | Output::Other { body: text }) => {
+            response.body = Some(text.clone());
+        }
+        (_, Output::Bytes(bytes)) => response.base64_body = Some(STANDARD.encode(bytes)),
+        (_, Output::Empty) => {}
+    }
+
+    Some(WireMockMapping::new(
+        format!("{}-{}", call.operation_id, status.as_u16()),
+        request,
+        response,
+    ))
+}
@@ -447,6 +447,14 @@ mod tests {
             response_description: None,
             skip_collection: false,
             security: None,
+            infer_security: false,
+            authentication_removed: false,
+            document_digest_challenge: false,
+            cookie_jar: None,
+            #[cfg(feature = "oauth2")]
+            oauth2_scopes: None,
+            #[cfg(feature = "oauth2")]
+            oauth2_scheme: crate::client::oauth2::OAUTH2_SCHEME_NAME.to_string(),
         }
     }
 
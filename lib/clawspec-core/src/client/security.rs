@@ -101,6 +101,14 @@ pub enum SecurityScheme {
         description: Option<String>,
     },
 
+    /// HTTP Digest authentication (RFC 7616).
+    ///
+    /// Uses `Authorization: Digest <response>` header, answering the challenge of the server.
+    Digest {
+        /// Description for documentation
+        description: Option<String>,
+    },
+
     /// API Key authentication.
     ///
     /// The API key can be passed in a header, query parameter, or cookie.
@@ -134,6 +142,15 @@ pub enum SecurityScheme {
         /// Description for documentation
         description: Option<String>,
     },
+
+    /// Mutual TLS authentication.
+    ///
+    /// The client authenticates with a TLS client certificate.
+    /// This scheme is only available in OpenAPI 3.1.
+    MutualTls {
+        /// Description for documentation
+        description: Option<String>,
+    },
 }
 
 impl SecurityScheme {
@@ -186,6 +203,19 @@ impl SecurityScheme {
         Self::Basic { description: None }
     }
 
+    /// Creates an HTTP Digest authentication scheme.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use clawspec_core::SecurityScheme;
+    ///
+    /// let scheme = SecurityScheme::digest();
+    /// ```
+    pub fn digest() -> Self {
+        Self::Digest { description: None }
+    }
+
     /// Creates an API Key authentication scheme.
     ///
     /// # Arguments
@@ -228,6 +258,19 @@ impl SecurityScheme {
         }
     }
 
+    /// Creates a mutual TLS authentication scheme.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use clawspec_core::SecurityScheme;
+    ///
+    /// let scheme = SecurityScheme::mutual_tls();
+    /// ```
+    pub fn mutual_tls() -> Self {
+        Self::MutualTls { description: None }
+    }
+
     /// Adds a description to the security scheme.
     ///
     /// # Example
@@ -244,6 +287,7 @@ impl SecurityScheme {
                 description: desc, ..
             } => *desc = Some(description.into()),
             SecurityScheme::Basic { description: desc } => *desc = Some(description.into()),
+            SecurityScheme::Digest { description: desc } => *desc = Some(description.into()),
             SecurityScheme::ApiKey {
                 description: desc, ..
             } => *desc = Some(description.into()),
@@ -253,10 +297,22 @@ impl SecurityScheme {
             SecurityScheme::OpenIdConnect {
                 description: desc, ..
             } => *desc = Some(description.into()),
+            SecurityScheme::MutualTls { description: desc } => *desc = Some(description.into()),
         }
         self
     }
 
+    /// Adds the scopes missing from the OAuth2 flows, without description.
+    ///
+    /// Other security schemes have no scopes, and are left unchanged.
+    pub(crate) fn add_oauth2_scopes<'a>(&mut self, scopes: impl IntoIterator<Item = &'a String>) {
+        if let SecurityScheme::OAuth2 { flows, .. } = self {
+            for scope in scopes {
+                flows.add_scope(scope);
+            }
+        }
+    }
+
     /// Converts this security scheme to a utoipa SecurityScheme.
     pub(crate) fn to_utoipa(&self) -> UtoipaSecurityScheme {
         match self {
@@ -280,6 +336,13 @@ impl SecurityScheme {
                 }
                 UtoipaSecurityScheme::Http(http)
             }
+            SecurityScheme::Digest { description } => {
+                let mut http = Http::new(HttpAuthScheme::Digest);
+                if let Some(desc) = description {
+                    http.description = Some(desc.clone());
+                }
+                UtoipaSecurityScheme::Http(http)
+            }
             SecurityScheme::ApiKey {
                 name,
                 location,
@@ -314,6 +377,10 @@ impl SecurityScheme {
                 }
                 UtoipaSecurityScheme::OpenIdConnect(oidc)
             }
+            SecurityScheme::MutualTls { description } => UtoipaSecurityScheme::MutualTls {
+                description: description.clone(),
+                extensions: None,
+            },
         }
     }
 }
@@ -384,6 +451,25 @@ impl OAuth2Flows {
         }
     }
 
+    /// Adds the scope to all the flows missing it, without description.
+    fn add_scope(&mut self, scope: &str) {
+        let flows = [
+            self.authorization_code
+                .as_mut()
+                .map(|flow| &mut flow.scopes),
+            self.client_credentials
+                .as_mut()
+                .map(|flow| &mut flow.scopes),
+            self.implicit.as_mut().map(|flow| &mut flow.scopes),
+            self.password.as_mut().map(|flow| &mut flow.scopes),
+        ];
+        for scopes in flows.into_iter().flatten() {
+            if !scopes.contains_key(scope) {
+                scopes.insert(scope.to_string(), String::new());
+            }
+        }
+    }
+
     fn to_utoipa(&self) -> UtoipaOAuth2 {
         let mut flows: Vec<Flow> = Vec::new();
 
@@ -634,6 +720,15 @@ mod tests {
         assert!(matches!(utoipa_scheme, UtoipaSecurityScheme::Http(_)));
     }
 
+    #[test]
+    fn test_digest_to_utoipa() {
+        let scheme = SecurityScheme::digest().with_description("Device credentials");
+        let utoipa_scheme = scheme.to_utoipa();
+
+        let json = serde_json::to_string(&utoipa_scheme).expect("should serialize");
+        insta::assert_snapshot!(json, @r#"{"type":"http","scheme":"digest","description":"Device credentials"}"#);
+    }
+
     #[test]
     fn test_api_key_to_utoipa() {
         let scheme = SecurityScheme::api_key("X-API-Key", ApiKeyLocation::Header);
@@ -653,6 +748,15 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_mutual_tls_to_utoipa() {
+        let scheme = SecurityScheme::mutual_tls().with_description("Client certificate");
+        let utoipa_scheme = scheme.to_utoipa();
+
+        let json = serde_json::to_string(&utoipa_scheme).expect("should serialize");
+        insta::assert_snapshot!(json, @r#"{"type":"mutualTLS","description":"Client certificate"}"#);
+    }
+
     #[test]
     fn test_oauth2_authorization_code_flows() {
         let flows = OAuth2Flows::authorization_code(
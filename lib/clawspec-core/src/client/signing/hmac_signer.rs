@@ -0,0 +1,243 @@
+//! Generic HMAC-SHA256 request signature.
+
+use std::time::SystemTime;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use http::{HeaderName, HeaderValue};
+
+use super::{RequestSigner, SignableRequest, hmac_sha256, sha256_hex, signing_failed};
+use crate::client::{ApiKeyLocation, AuthenticationError, SecureString, SecurityScheme};
+
+/// The header holding the signature timestamp.
+const TIMESTAMP_HEADER: &str = "x-timestamp";
+
+/// Default header holding the signature.
+const DEFAULT_SIGNATURE_HEADER: &str = "X-Signature";
+
+/// Signs requests with an HMAC-SHA256 over the request.
+///
+/// The signer adds the `X-Timestamp` header, with the Unix time in seconds, and
+/// the signature header, `X-Signature` by default:
+///
+/// ```text
+/// keyId="<key id>",algorithm="hmac-sha256",headers="<signed headers>",signature="<signature>"
+/// ```
+///
+/// The signature is the Base64 encoded HMAC-SHA256 of the following lines,
+/// joined with `\n`:
+///
+/// 1. the uppercase HTTP method,
+/// 2. the path, with the query string,
+/// 3. the timestamp,
+/// 4. a `<name>:<value>` line for each signed header, the name in lowercase,
+///    the value empty when the header is missing,
+/// 5. the hex encoded SHA-256 digest of the body.
+///
+/// # Example
+///
+/// ```rust
+/// use clawspec_core::{Authentication, HmacSigner};
+///
+/// let signer = HmacSigner::new("partner-42", "shared-secret")
+///     .with_signed_headers(["content-type"])
+///     .with_signature_header("X-Partner-Signature");
+/// let auth = Authentication::signed(signer);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HmacSigner {
+    key_id: String,
+    secret: SecureString,
+    signed_headers: Vec<String>,
+    signature_header: String,
+    fixed_time: Option<SystemTime>,
+}
+
+impl HmacSigner {
+    /// Creates a signer with the identifier and the secret of the key.
+    pub fn new(key_id: impl Into<String>, secret: impl Into<SecureString>) -> Self {
+        Self {
+            key_id: key_id.into(),
+            secret: secret.into(),
+            signed_headers: Vec::new(),
+            signature_header: DEFAULT_SIGNATURE_HEADER.to_string(),
+            fixed_time: None,
+        }
+    }
+
+    /// Sets the headers included in the signature.
+    pub fn with_signed_headers(
+        mut self,
+        headers: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.signed_headers = headers
+            .into_iter()
+            .map(|name| name.into().to_ascii_lowercase())
+            .collect();
+        self
+    }
+
+    /// Sets the header holding the signature, `X-Signature` by default.
+    pub fn with_signature_header(mut self, header: impl Into<String>) -> Self {
+        self.signature_header = header.into();
+        self
+    }
+
+    /// Signs the requests at a fixed time, instead of the current time.
+    ///
+    /// This is useful for reproducible signatures, e.g. when checking them in tests.
+    pub fn with_fixed_time(mut self, time: SystemTime) -> Self {
+        self.fixed_time = Some(time);
+        self
+    }
+
+    /// Builds the string to sign.
+    fn string_to_sign(&self, request: &SignableRequest<'_>, timestamp: u64) -> String {
+        let url = request.url();
+        let target = match url.query() {
+            Some(query) => format!("{}?{query}", url.path()),
+            None => url.path().to_string(),
+        };
+        let mut lines = vec![request.method().to_string(), target, timestamp.to_string()];
+        for name in &self.signed_headers {
+            let value = if name == "host" {
+                request.host()
+            } else {
+                request
+                    .headers()
+                    .get(name)
+                    .map(|value| String::from_utf8_lossy(value.as_bytes()).trim().to_string())
+                    .unwrap_or_default()
+            };
+            lines.push(format!("{name}:{value}"));
+        }
+        lines.push(sha256_hex(request.body()));
+        lines.join("\n")
+    }
+}
+
+impl RequestSigner for HmacSigner {
+    fn sign(&self, request: &mut SignableRequest<'_>) -> Result<(), AuthenticationError> {
+        let signature_header =
+            HeaderName::from_bytes(self.signature_header.as_bytes()).map_err(signing_failed)?;
+        let now = self.fixed_time.unwrap_or_else(SystemTime::now);
+        let timestamp = now
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_err(signing_failed)?
+            .as_secs();
+
+        let string_to_sign = self.string_to_sign(request, timestamp);
+        let signature = STANDARD.encode(hmac_sha256(
+            self.secret.as_str().as_bytes(),
+            string_to_sign.as_bytes(),
+        )?);
+        let value = format!(
+            r#"keyId="{}",algorithm="hmac-sha256",headers="{}",signature="{signature}""#,
+            self.key_id,
+            self.signed_headers.join(" ")
+        );
+
+        let headers = request.headers_mut();
+        headers.insert(TIMESTAMP_HEADER, HeaderValue::from(timestamp));
+        headers.insert(
+            signature_header,
+            HeaderValue::from_str(&value).map_err(signing_failed)?,
+        );
+        Ok(())
+    }
+
+    fn security_scheme_name(&self) -> String {
+        "hmacSignature".to_string()
+    }
+
+    fn security_scheme(&self) -> SecurityScheme {
+        let headers = if self.signed_headers.is_empty() {
+            String::new()
+        } else {
+            format!(
+                ", the signed headers (`{}`)",
+                self.signed_headers.join("`, `")
+            )
+        };
+        SecurityScheme::api_key(&self.signature_header, ApiKeyLocation::Header).with_description(
+            format!(
+                "HMAC-SHA256 signature of the method, the path and query, the `X-Timestamp` header{headers} and the SHA-256 digest of the body"
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use http::{HeaderMap, Method};
+    use url::Url;
+
+    use super::*;
+
+    fn signer() -> HmacSigner {
+        HmacSigner::new("partner-42", "shared-secret")
+            .with_signed_headers(["Host", "Content-Type"])
+            .with_fixed_time(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+    }
+
+    #[test]
+    fn should_build_string_to_sign() {
+        let url = Url::parse("http://localhost:8080/orders?page=2").expect("valid URL");
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", HeaderValue::from_static("application/json"));
+        let request = SignableRequest::new(&Method::POST, &url, &mut headers, b"{}");
+
+        insta::assert_snapshot!(signer().string_to_sign(&request, 1_700_000_000), @r#"
+        POST
+        /orders?page=2
+        1700000000
+        host:localhost:8080
+        content-type:application/json
+        44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a
+        "#);
+    }
+
+    #[test]
+    fn should_sign_request() {
+        let url = Url::parse("http://localhost:8080/orders").expect("valid URL");
+        let mut headers = HeaderMap::new();
+        let mut request = SignableRequest::new(&Method::GET, &url, &mut headers, b"");
+
+        signer().sign(&mut request).expect("should sign");
+
+        assert_eq!(headers["x-timestamp"], "1700000000");
+        insta::assert_snapshot!(headers["x-signature"].to_str().expect("ASCII"), @r#"keyId="partner-42",algorithm="hmac-sha256",headers="host content-type",signature="IxedabdjBN7/Em8Govl2xeA50V9+1rDFSE5A8XSnoow=""#);
+    }
+
+    #[test]
+    fn should_describe_security_scheme() {
+        let scheme = signer()
+            .with_signature_header("X-Partner-Signature")
+            .security_scheme();
+
+        let SecurityScheme::ApiKey {
+            name, description, ..
+        } = scheme
+        else {
+            panic!("should be an API key scheme");
+        };
+        assert_eq!(name, "X-Partner-Signature");
+        insta::assert_snapshot!(description.unwrap_or_default(), @"HMAC-SHA256 signature of the method, the path and query, the `X-Timestamp` header, the signed headers (`host`, `content-type`) and the SHA-256 digest of the body");
+    }
+
+    #[test]
+    fn should_reject_invalid_signature_header() {
+        let url = Url::parse("http://localhost:8080/orders").expect("valid URL");
+        let mut headers = HeaderMap::new();
+        let mut request = SignableRequest::new(&Method::GET, &url, &mut headers, b"");
+
+        let error = signer()
+            .with_signature_header("X Signature")
+            .sign(&mut request)
+            .expect_err("should reject the header");
+
+        insta::assert_snapshot!(error, @"Request signing failed: invalid HTTP header name");
+    }
+}
@@ -0,0 +1,176 @@
+//! Request signing authentication.
+//!
+//! Some APIs require each request to be signed, with a signature computed over
+//! the method, the path, some headers and a digest of the body. A [`RequestSigner`]
+//! computes this signature once the request is complete, and adds it to the request,
+//! usually as headers.
+//!
+//! # Built-in Signers
+//!
+//! - [`SigV4Signer`]: AWS Signature Version 4
+//! - [`HmacSigner`]: a generic HMAC-SHA256 signature, for custom partner APIs
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use clawspec_core::{ApiClient, Authentication, SigV4Signer};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let signer = SigV4Signer::new("AKIDEXAMPLE", "secret-access-key", "eu-west-1", "execute-api");
+//!
+//! let client = ApiClient::builder()
+//!     .with_host("api.example.com")
+//!     .with_authentication(Authentication::signed(signer))
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+use hmac::{Hmac, Mac};
+use http::{HeaderMap, Method};
+use sha2::{Digest, Sha256};
+use url::Url;
+
+use super::{ApiKeyLocation, AuthenticationError, SecurityScheme};
+
+mod hmac_signer;
+mod sigv4;
+
+pub use self::hmac_signer::HmacSigner;
+pub use self::sigv4::SigV4Signer;
+
+/// Signs requests, for APIs requiring signed requests.
+///
+/// The signer is called when the request is complete, after its body is finalized.
+/// Implementations must not display their secrets in their `Debug` output,
+/// e.g. by holding them in a [`SecureString`](super::SecureString).
+///
+/// # Example
+///
+/// ```rust
+/// use clawspec_core::{AuthenticationError, RequestSigner, SignableRequest};
+/// use http::HeaderValue;
+///
+/// #[derive(Debug)]
+/// struct BodyLengthSigner;
+///
+/// impl RequestSigner for BodyLengthSigner {
+///     fn sign(&self, request: &mut SignableRequest<'_>) -> Result<(), AuthenticationError> {
+///         let signature = HeaderValue::from(request.body().len());
+///         request.headers_mut().insert("x-signature", signature);
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait RequestSigner: fmt::Debug + Send + Sync {
+    /// Signs the request, usually by adding headers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`AuthenticationError::SigningFailed`] if the request cannot be signed.
+    fn sign(&self, request: &mut SignableRequest<'_>) -> Result<(), AuthenticationError>;
+
+    /// Returns the name of the security scheme documenting the signature.
+    fn security_scheme_name(&self) -> String {
+        "signature".to_string()
+    }
+
+    /// Returns the security scheme documenting the signature.
+    ///
+    /// OpenAPI has no scheme for signed requests, they are documented as an API key
+    /// in the signature header, with a description explaining how it is computed.
+    fn security_scheme(&self) -> SecurityScheme {
+        SecurityScheme::api_key("Authorization", ApiKeyLocation::Header)
+            .with_description("Signed request")
+    }
+}
+
+/// A request about to be sent, for a [`RequestSigner`].
+#[derive(Debug)]
+pub struct SignableRequest<'a> {
+    method: &'a Method,
+    url: &'a Url,
+    headers: &'a mut HeaderMap,
+    body: &'a [u8],
+}
+
+impl<'a> SignableRequest<'a> {
+    pub(crate) fn new(
+        method: &'a Method,
+        url: &'a Url,
+        headers: &'a mut HeaderMap,
+        body: &'a [u8],
+    ) -> Self {
+        Self {
+            method,
+            url,
+            headers,
+            body,
+        }
+    }
+
+    /// Returns the HTTP method.
+    pub fn method(&self) -> &Method {
+        self.method
+    }
+
+    /// Returns the URL, with its query string.
+    pub fn url(&self) -> &Url {
+        self.url
+    }
+
+    /// Returns the headers, without the `Host` header added when sending the request.
+    pub fn headers(&self) -> &HeaderMap {
+        self.headers
+    }
+
+    /// Returns the headers, to add the signature.
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        self.headers
+    }
+
+    /// Returns the body, empty when the request has no body.
+    pub fn body(&self) -> &[u8] {
+        self.body
+    }
+
+    /// Returns the `Host` header value sent with the request.
+    pub fn host(&self) -> String {
+        let host = self.url.host_str().unwrap_or_default();
+        match self.url.port() {
+            Some(port) => format!("{host}:{port}"),
+            None => host.to_string(),
+        }
+    }
+}
+
+/// Computes the HMAC-SHA256 of the data.
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Result<Vec<u8>, AuthenticationError> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).map_err(signing_failed)?;
+    mac.update(data);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+/// Computes the hex-encoded SHA-256 digest of the data.
+fn sha256_hex(data: &[u8]) -> String {
+    hex(&Sha256::digest(data))
+}
+
+/// Encodes bytes as lowercase hexadecimal.
+pub(super) fn hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+
+    bytes.iter().fold(String::new(), |mut acc, byte| {
+        let _ = write!(acc, "{byte:02x}");
+        acc
+    })
+}
+
+/// Builds a signing error.
+fn signing_failed(message: impl fmt::Display) -> AuthenticationError {
+    AuthenticationError::SigningFailed {
+        message: message.to_string(),
+    }
+}
@@ -0,0 +1,349 @@
+//! AWS Signature Version 4.
+
+use std::time::SystemTime;
+
+use http::HeaderValue;
+use jiff::Timestamp;
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, percent_decode_str, utf8_percent_encode};
+
+use super::{RequestSigner, SignableRequest, hex, hmac_sha256, sha256_hex, signing_failed};
+use crate::client::{ApiKeyLocation, AuthenticationError, SecureString, SecurityScheme};
+
+/// The signing algorithm identifier.
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+/// Characters encoded by SigV4, all but the unreserved characters (RFC 3986).
+const SIGV4_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Signs requests with AWS Signature Version 4.
+///
+/// The signer adds the `X-Amz-Date` and `Authorization` headers, and the
+/// `X-Amz-Security-Token` header for temporary credentials. The `Host` and
+/// `Content-Type` headers are signed, with all the `X-Amz-*` headers.
+///
+/// For the `s3` service, the path is encoded once and the `X-Amz-Content-Sha256`
+/// header is added, as required by S3.
+///
+/// # Example
+///
+/// ```rust
+/// use clawspec_core::{Authentication, SigV4Signer};
+///
+/// let signer = SigV4Signer::new("AKIDEXAMPLE", "secret-access-key", "eu-west-1", "execute-api")
+///     .with_session_token("session-token");
+/// let auth = Authentication::signed(signer);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SigV4Signer {
+    access_key_id: String,
+    secret_access_key: SecureString,
+    session_token: Option<SecureString>,
+    region: String,
+    service: String,
+    fixed_time: Option<SystemTime>,
+}
+
+impl SigV4Signer {
+    /// Creates a signer for the service in the region.
+    pub fn new(
+        access_key_id: impl Into<String>,
+        secret_access_key: impl Into<SecureString>,
+        region: impl Into<String>,
+        service: impl Into<String>,
+    ) -> Self {
+        Self {
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            session_token: None,
+            region: region.into(),
+            service: service.into(),
+            fixed_time: None,
+        }
+    }
+
+    /// Sets the session token of temporary credentials.
+    pub fn with_session_token(mut self, session_token: impl Into<SecureString>) -> Self {
+        self.session_token = Some(session_token.into());
+        self
+    }
+
+    /// Signs the requests at a fixed time, instead of the current time.
+    ///
+    /// This is useful for reproducible signatures, e.g. when checking them in tests.
+    pub fn with_fixed_time(mut self, time: SystemTime) -> Self {
+        self.fixed_time = Some(time);
+        self
+    }
+
+    fn is_s3(&self) -> bool {
+        self.service == "s3"
+    }
+
+    /// Builds the canonical URI, encoding the path segments twice, except for S3.
+    fn canonical_uri(&self, request: &SignableRequest<'_>) -> String {
+        let path = request.url().path();
+        if path.is_empty() || path == "/" {
+            return "/".to_string();
+        }
+        path.split('/')
+            .map(|segment| {
+                let segment = percent_decode_str(segment).decode_utf8_lossy();
+                let encoded = utf8_percent_encode(&segment, SIGV4_ENCODE_SET).to_string();
+                if self.is_s3() {
+                    encoded
+                } else {
+                    utf8_percent_encode(&encoded, SIGV4_ENCODE_SET).to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Builds the signed headers list, and the canonical headers.
+    fn canonical_headers(request: &SignableRequest<'_>) -> (String, String) {
+        let mut headers = vec![("host".to_string(), request.host())];
+        for name in request.headers().keys() {
+            let name = name.as_str();
+            if name != "content-type" && !name.starts_with("x-amz-") {
+                continue;
+            }
+            let values: Vec<_> = request
+                .headers()
+                .get_all(name)
+                .iter()
+                .map(|value| {
+                    String::from_utf8_lossy(value.as_bytes())
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect();
+            headers.push((name.to_string(), values.join(",")));
+        }
+        headers.sort();
+
+        let signed_headers = headers
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>()
+            .join(";");
+        let canonical_headers = headers
+            .iter()
+            .map(|(name, value)| format!("{name}:{value}\n"))
+            .collect();
+        (signed_headers, canonical_headers)
+    }
+
+    /// Builds the canonical query string, with the parameters sorted.
+    fn canonical_query(request: &SignableRequest<'_>) -> String {
+        let mut params: Vec<_> = request
+            .url()
+            .query_pairs()
+            .map(|(name, value)| {
+                (
+                    utf8_percent_encode(&name, SIGV4_ENCODE_SET).to_string(),
+                    utf8_percent_encode(&value, SIGV4_ENCODE_SET).to_string(),
+                )
+            })
+            .collect();
+        params.sort();
+        params
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+}
+
+impl RequestSigner for SigV4Signer {
+    fn sign(&self, request: &mut SignableRequest<'_>) -> Result<(), AuthenticationError> {
+        let now = self.fixed_time.unwrap_or_else(SystemTime::now);
+        let now = Timestamp::try_from(now).map_err(signing_failed)?;
+        let amz_date = now.strftime("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.strftime("%Y%m%d").to_string();
+        let payload_hash = sha256_hex(request.body());
+
+        let headers = request.headers_mut();
+        headers.insert(
+            "x-amz-date",
+            HeaderValue::from_str(&amz_date).map_err(signing_failed)?,
+        );
+        if self.is_s3() {
+            headers.insert(
+                "x-amz-content-sha256",
+                HeaderValue::from_str(&payload_hash).map_err(signing_failed)?,
+            );
+        }
+        if let Some(token) = &self.session_token {
+            let mut value = HeaderValue::from_str(token.as_str()).map_err(signing_failed)?;
+            value.set_sensitive(true);
+            headers.insert("x-amz-security-token", value);
+        }
+
+        let (signed_headers, canonical_headers) = Self::canonical_headers(request);
+        let canonical_request = [
+            request.method().as_str(),
+            &self.canonical_uri(request),
+            &Self::canonical_query(request),
+            &canonical_headers,
+            &signed_headers,
+            &payload_hash,
+        ]
+        .join("\n");
+
+        let scope = format!("{date}/{}/{}/aws4_request", self.region, self.service);
+        let string_to_sign = format!(
+            "{ALGORITHM}\n{amz_date}\n{scope}\n{}",
+            sha256_hex(canonical_request.as_bytes())
+        );
+
+        let secret = format!("AWS4{}", self.secret_access_key.as_str());
+        let signing_key = [date.as_str(), &self.region, &self.service, "aws4_request"]
+            .iter()
+            .try_fold(secret.into_bytes(), |key, data| {
+                hmac_sha256(&key, data.as_bytes())
+            })?;
+        let signature = hex(&hmac_sha256(&signing_key, string_to_sign.as_bytes())?);
+
+        let authorization = format!(
+            "{ALGORITHM} Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+            self.access_key_id
+        );
+        request.headers_mut().insert(
+            http::header::AUTHORIZATION,
+            HeaderValue::from_str(&authorization).map_err(signing_failed)?,
+        );
+        Ok(())
+    }
+
+    fn security_scheme_name(&self) -> String {
+        "sigv4".to_string()
+    }
+
+    fn security_scheme(&self) -> SecurityScheme {
+        SecurityScheme::api_key("Authorization", ApiKeyLocation::Header).with_description(format!(
+            "AWS Signature Version 4 (`{ALGORITHM}`), for the `{}` service in the `{}` region",
+            self.service, self.region
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use http::{HeaderMap, Method};
+    use url::Url;
+
+    use super::*;
+
+    /// The `20150830T123600Z` date of the AWS Signature Version 4 test suite.
+    fn test_suite_time() -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(1_440_938_160)
+    }
+
+    fn test_suite_signer() -> SigV4Signer {
+        SigV4Signer::new(
+            "AKIDEXAMPLE",
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "us-east-1",
+            "service",
+        )
+        .with_fixed_time(test_suite_time())
+    }
+
+    fn sign(signer: &SigV4Signer, method: Method, url: &str, body: &[u8]) -> HeaderMap {
+        let url = Url::parse(url).expect("valid URL");
+        let mut headers = HeaderMap::new();
+        let mut request = SignableRequest::new(&method, &url, &mut headers, body);
+        signer.sign(&mut request).expect("should sign");
+        headers
+    }
+
+    #[test]
+    fn should_sign_get_vanilla() {
+        let headers = sign(
+            &test_suite_signer(),
+            Method::GET,
+            "https://example.amazonaws.com/",
+            b"",
+        );
+
+        insta::assert_snapshot!(headers["authorization"].to_str().expect("ASCII"), @"AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, SignedHeaders=host;x-amz-date, Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31");
+        assert_eq!(headers["x-amz-date"], "20150830T123600Z");
+    }
+
+    #[test]
+    fn should_sign_get_vanilla_query_order_key_case() {
+        let headers = sign(
+            &test_suite_signer(),
+            Method::GET,
+            "https://example.amazonaws.com/?Param2=value2&Param1=value1",
+            b"",
+        );
+
+        insta::assert_snapshot!(headers["authorization"].to_str().expect("ASCII"), @"AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, SignedHeaders=host;x-amz-date, Signature=b97d918cfa904a5beff61c982a1b6f458b799221646efd99d3219ec94cdf2500");
+    }
+
+    #[test]
+    fn should_sign_post_vanilla() {
+        let headers = sign(
+            &test_suite_signer(),
+            Method::POST,
+            "https://example.amazonaws.com/",
+            b"",
+        );
+
+        insta::assert_snapshot!(headers["authorization"].to_str().expect("ASCII"), @"AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, SignedHeaders=host;x-amz-date, Signature=5da7c1a2acd57cee7505fc6676e4e544621c30862966e37dddb68e92efbe5d6b");
+    }
+
+    #[test]
+    fn should_sign_session_token_and_s3_payload() {
+        let signer = SigV4Signer::new("AKIDEXAMPLE", "secret", "us-east-1", "s3")
+            .with_session_token("token")
+            .with_fixed_time(test_suite_time());
+
+        let headers = sign(
+            &signer,
+            Method::PUT,
+            "https://bucket.s3.amazonaws.com/my%20file.txt",
+            b"hello",
+        );
+
+        let authorization = headers["authorization"].to_str().expect("ASCII");
+        assert!(
+            authorization.contains(
+                "SignedHeaders=host;x-amz-content-sha256;x-amz-date;x-amz-security-token"
+            )
+        );
+        assert_eq!(
+            headers["x-amz-content-sha256"],
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert!(headers["x-amz-security-token"].is_sensitive());
+    }
+
+    #[test]
+    fn should_encode_path_segments_twice() {
+        let signer = test_suite_signer();
+        let url = Url::parse("https://example.amazonaws.com/users/john%20doe").expect("valid URL");
+        let mut headers = HeaderMap::new();
+        let request = SignableRequest::new(&Method::GET, &url, &mut headers, b"");
+
+        insta::assert_snapshot!(signer.canonical_uri(&request), @"/users/john%2520doe");
+    }
+
+    #[test]
+    fn should_not_display_secrets() {
+        let signer = test_suite_signer().with_session_token("session-token");
+
+        let debug = format!("{signer:?}");
+
+        assert!(!debug.contains("wJalrXUtnFEMI"));
+        assert!(!debug.contains("session-token"));
+    }
+}
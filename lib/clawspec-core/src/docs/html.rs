@@ -0,0 +1,653 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+use utoipa::openapi::{OpenApi, RefOr, Schema};
+
+use super::model::{
+    Body, DocOperation, DocTag, Field, SchemaResolver, TypePart, doc_tags, enum_values,
+    schema_description, schema_example,
+};
+
+/// The style sheet embedded in every page.
+const STYLE: &str = r#"
+body { margin: 0; font-family: system-ui, sans-serif; color: #1f2328; display: flex; }
+nav { width: 18rem; min-height: 100vh; padding: 1rem; background: #f6f8fa; border-right: 1px solid #d0d7de; box-sizing: border-box; }
+nav h2 { font-size: 0.85rem; text-transform: uppercase; color: #59636e; margin: 1.2rem 0 0.4rem; }
+nav ul { list-style: none; padding-left: 0.6rem; margin: 0; }
+nav a { color: #0969da; text-decoration: none; }
+main { flex: 1; max-width: 60rem; padding: 1rem 2rem; }
+table { border-collapse: collapse; margin: 0.8rem 0; }
+th, td { border: 1px solid #d0d7de; padding: 0.3rem 0.6rem; text-align: left; vertical-align: top; }
+pre { background: #f6f8fa; padding: 0.8rem; overflow-x: auto; }
+.method { font-family: monospace; font-weight: bold; padding: 0.1rem 0.4rem; border-radius: 0.2rem; background: #ddf4ff; }
+.deprecated { color: #cf222e; font-weight: bold; }
+"#;
+
+/// A page of the HTML documentation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlPage {
+    /// The file name of the page, relative to the documentation directory.
+    pub file_name: String,
+    /// The HTML content of the page.
+    pub content: String,
+}
+
+/// Renders an OpenAPI specification as a static HTML documentation site.
+///
+/// The site is self-contained: the pages only link to each other and embed their
+/// style, so it can be browsed offline or published as a CI artifact. It contains:
+/// - `index.html`, the API overview with the list of tags and schemas
+/// - a page per tag, listing its operations
+/// - a page per operation, with its parameters, bodies, responses and examples
+/// - a page per component schema
+///
+/// Every page has a navigation sidebar grouping the operations by tag.
+///
+/// # Example
+///
+/// ```rust
+/// use clawspec_core::OpenApi;
+/// use clawspec_core::docs::to_html_pages;
+///
+/// let openapi = OpenApi::builder()
+///     .info(clawspec_core::Info::new("Pets API", "1.0.0"))
+///     .build();
+///
+/// let pages = to_html_pages(&openapi);
+/// assert_eq!(pages[0].file_name, "index.html");
+/// ```
+pub fn to_html_pages(openapi: &OpenApi) -> Vec<HtmlPage> {
+    let site = Site::new(openapi);
+    let mut pages = vec![HtmlPage {
+        file_name: "index.html".to_string(),
+        content: site.page(&openapi.info.title, &site.index()),
+    }];
+
+    for tag in &site.tags {
+        pages.push(HtmlPage {
+            file_name: site.tag_file(tag).to_string(),
+            content: site.page(&tag.name, &site.tag(tag)),
+        });
+    }
+
+    // An operation with several tags has a single page
+    let mut written = BTreeSet::new();
+    for operation in site.tags.iter().flat_map(|tag| &tag.operations) {
+        let file_name = site.operation_file(operation);
+        if !written.insert(file_name) {
+            continue;
+        }
+        pages.push(HtmlPage {
+            file_name: file_name.to_string(),
+            content: site.page(&operation.title(), &site.operation(operation)),
+        });
+    }
+
+    for (name, schema) in site.resolver.schemas() {
+        if let Some(file_name) = site.schema_files.get(name) {
+            pages.push(HtmlPage {
+                file_name: file_name.clone(),
+                content: site.page(name, &site.schema(name, schema)),
+            });
+        }
+    }
+
+    pages
+}
+
+/// Writes the static HTML documentation of an OpenAPI specification into a directory.
+///
+/// The directory is created if needed. See [`to_html_pages`] for the generated pages.
+///
+/// # Errors
+///
+/// Returns an error if the directory or a page cannot be written.
+pub fn write_html(openapi: &OpenApi, dir: impl AsRef<Path>) -> Result<(), std::io::Error> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    for page in to_html_pages(openapi) {
+        fs::write(dir.join(&page.file_name), page.content)?;
+    }
+    Ok(())
+}
+
+/// The shared state used to render the pages.
+struct Site<'a> {
+    openapi: &'a OpenApi,
+    resolver: SchemaResolver<'a>,
+    tags: Vec<DocTag<'a>>,
+    schema_files: BTreeMap<&'a str, String>,
+    tag_files: BTreeMap<String, String>,
+    /// The operation pages, by operation title.
+    operation_files: BTreeMap<String, String>,
+    navigation: String,
+}
+
+impl<'a> Site<'a> {
+    fn new(openapi: &'a OpenApi) -> Self {
+        let resolver = SchemaResolver::new(openapi);
+        let tags = doc_tags(openapi);
+
+        // Names may differ only by case or punctuation
+        let mut taken = BTreeSet::new();
+        let mut schema_files = BTreeMap::new();
+        for name in resolver.schemas().into_keys() {
+            let file_name = unique_file(&format!("schema-{}", slug::slugify(name)), &mut taken);
+            schema_files.insert(name, file_name);
+        }
+        let mut tag_files = BTreeMap::new();
+        let mut operation_files = BTreeMap::new();
+        for tag in &tags {
+            let file_name = unique_file(&format!("tag-{}", slug::slugify(&tag.name)), &mut taken);
+            tag_files.insert(tag.name.clone(), file_name);
+            for operation in &tag.operations {
+                operation_files
+                    .entry(operation.title())
+                    .or_insert_with(|| unique_file(&operation_base(operation), &mut taken));
+            }
+        }
+
+        let mut site = Self {
+            openapi,
+            resolver,
+            tags,
+            schema_files,
+            tag_files,
+            operation_files,
+            navigation: String::new(),
+        };
+        site.navigation = site.navigation();
+        site
+    }
+
+    fn tag_file(&self, tag: &DocTag<'_>) -> &str {
+        self.tag_files.get(&tag.name).map_or("", String::as_str)
+    }
+
+    fn operation_file(&self, operation: &DocOperation<'_>) -> &str {
+        self.operation_files
+            .get(&operation.title())
+            .map_or("", String::as_str)
+    }
+
+    fn navigation(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "<nav>\n<a href=\"index.html\"><strong>{}</strong></a>",
+            escape(&self.openapi.info.title)
+        );
+        for tag in &self.tags {
+            let _ = writeln!(
+                out,
+                "<h2><a href=\"{}\">{}</a></h2>\n<ul>",
+                self.tag_file(tag),
+                escape(&tag.name)
+            );
+            for operation in &tag.operations {
+                let _ = writeln!(
+                    out,
+                    "<li><a href=\"{}\">{}</a></li>",
+                    self.operation_file(operation),
+                    escape(&operation.title())
+                );
+            }
+            let _ = writeln!(out, "</ul>");
+        }
+        if !self.schema_files.is_empty() {
+            let _ = writeln!(out, "<h2>Schemas</h2>\n<ul>");
+            for (name, file_name) in &self.schema_files {
+                let _ = writeln!(out, "<li><a href=\"{file_name}\">{}</a></li>", escape(name));
+            }
+            let _ = writeln!(out, "</ul>");
+        }
+        let _ = writeln!(out, "</nav>");
+        out
+    }
+
+    fn page(&self, title: &str, main: &str) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+             <title>{}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n{}<main>\n{main}</main>\n\
+             </body>\n</html>\n",
+            escape(title),
+            self.navigation,
+        )
+    }
+
+    fn index(&self) -> String {
+        let info = &self.openapi.info;
+        let mut out = String::new();
+        let _ = writeln!(out, "<h1>{}</h1>", escape(&info.title));
+        let _ = writeln!(out, "<p>Version <code>{}</code></p>", escape(&info.version));
+        if let Some(description) = &info.description {
+            let _ = writeln!(out, "<p>{}</p>", escape(description));
+        }
+        if !self.tags.is_empty() {
+            let _ = writeln!(out, "<h2>Tags</h2>\n<table>");
+            for tag in &self.tags {
+                let _ = writeln!(
+                    out,
+                    "<tr><td><a href=\"{}\">{}</a></td><td>{}</td></tr>",
+                    self.tag_file(tag),
+                    escape(&tag.name),
+                    escape(tag.description.unwrap_or_default())
+                );
+            }
+            let _ = writeln!(out, "</table>");
+        }
+        out
+    }
+
+    fn tag(&self, tag: &DocTag<'_>) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "<h1>{}</h1>", escape(&tag.name));
+        if let Some(description) = tag.description {
+            let _ = writeln!(out, "<p>{}</p>", escape(description));
+        }
+        let _ = writeln!(out, "<table>");
+        for operation in &tag.operations {
+            let summary = operation
+                .operation
+                .summary
+                .as_ref()
+                .or(operation.operation.description.as_ref());
+            let _ = writeln!(
+                out,
+                "<tr><td><span class=\"method\">{}</span></td><td><a href=\"{}\">{}</a></td><td>{}</td></tr>",
+                operation.method,
+                self.operation_file(operation),
+                escape(operation.path),
+                escape(summary.map(String::as_str).unwrap_or_default())
+            );
+        }
+        let _ = writeln!(out, "</table>");
+        out
+    }
+
+    fn operation(&self, doc: &DocOperation<'_>) -> String {
+        let operation = doc.operation;
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "<h1><span class=\"method\">{}</span> <code>{}</code></h1>",
+            doc.method,
+            escape(doc.path)
+        );
+        if let Some(operation_id) = &operation.operation_id {
+            let _ = writeln!(
+                out,
+                "<p>Operation ID: <code>{}</code></p>",
+                escape(operation_id)
+            );
+        }
+        if operation.deprecated.is_some() {
+            let _ = writeln!(out, "<p class=\"deprecated\">Deprecated</p>");
+        }
+        for text in [&operation.summary, &operation.description]
+            .into_iter()
+            .flatten()
+        {
+            let _ = writeln!(out, "<p>{}</p>", escape(text));
+        }
+
+        let parameters = self.resolver.parameters(operation);
+        if !parameters.is_empty() {
+            let _ = writeln!(
+                out,
+                "<h2>Parameters</h2>\n<table>\n<tr><th>Name</th><th>In</th><th>Type</th><th>Required</th><th>Description</th></tr>"
+            );
+            for parameter in parameters {
+                let _ = writeln!(
+                    out,
+                    "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    escape(&parameter.name),
+                    parameter.location,
+                    self.type_label(&parameter.type_label),
+                    yes_no(parameter.required),
+                    escape(parameter.description.as_deref().unwrap_or_default())
+                );
+            }
+            let _ = writeln!(out, "</table>");
+        }
+
+        let request_bodies = self.resolver.request_bodies(operation);
+        if !request_bodies.is_empty() {
+            let _ = writeln!(out, "<h2>Request body</h2>");
+            for body in &request_bodies {
+                self.write_body(&mut out, body);
+            }
+        }
+
+        let responses = self.resolver.responses(operation);
+        if !responses.is_empty() {
+            let _ = writeln!(out, "<h2>Responses</h2>");
+            for response in &responses {
+                let _ = writeln!(out, "<h3>{}</h3>", escape(&response.status));
+                if !response.description.is_empty() {
+                    let _ = writeln!(out, "<p>{}</p>", escape(&response.description));
+                }
+                for body in &response.bodies {
+                    self.write_body(&mut out, body);
+                }
+            }
+        }
+        out
+    }
+
+    fn schema(&self, name: &str, schema: &RefOr<Schema>) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "<h1>{}</h1>", escape(name));
+        let resolved = self.resolver.resolve(schema);
+        if let Some(description) = resolved.and_then(schema_description) {
+            let _ = writeln!(out, "<p>{}</p>", escape(&description));
+        }
+        let fields = self.resolver.fields(schema, 0);
+        if fields.is_empty() {
+            let _ = writeln!(
+                out,
+                "<p>Type: {}</p>",
+                self.type_label(&self.resolver.type_label(schema))
+            );
+        } else {
+            self.write_fields(&mut out, &fields);
+        }
+        let values = resolved.map(enum_values).unwrap_or_default();
+        if !values.is_empty() {
+            let _ = writeln!(out, "<p>Values: {}</p>", code_list(&values));
+        }
+        if let Some(example) = resolved.and_then(schema_example) {
+            write_example(&mut out, &example);
+        }
+        out
+    }
+
+    fn write_body(&self, out: &mut String, body: &Body) {
+        let _ = write!(
+            out,
+            "<p>Content type: <code>{}</code>",
+            escape(&body.content_type)
+        );
+        if !body.type_label.is_empty() {
+            let _ = write!(out, ", schema: {}", self.type_label(&body.type_label));
+        }
+        let _ = writeln!(out, "</p>");
+        self.write_fields(out, &body.fields);
+        if let Some(example) = &body.example {
+            write_example(out, example);
+        }
+    }
+
+    fn write_fields(&self, out: &mut String, fields: &[Field]) {
+        if fields.is_empty() {
+            return;
+        }
+        let _ = writeln!(
+            out,
+            "<table>\n<tr><th>Field</th><th>Type</th><th>Required</th><th>Description</th></tr>"
+        );
+        for field in fields {
+            let mut description = escape(field.description.as_deref().unwrap_or_default());
+            if !field.values.is_empty() {
+                if !description.is_empty() {
+                    description.push(' ');
+                }
+                let _ = write!(description, "Values: {}", code_list(&field.values));
+            }
+            let _ = writeln!(
+                out,
+                "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(&field.name),
+                self.type_label(&field.type_label),
+                yes_no(field.required),
+                description
+            );
+        }
+        let _ = writeln!(out, "</table>");
+    }
+
+    fn type_label(&self, parts: &[TypePart]) -> String {
+        parts
+            .iter()
+            .map(|part| match part {
+                TypePart::Text(text) => escape(text),
+                TypePart::Schema(name) => match self.schema_files.get(name.as_str()) {
+                    Some(file_name) => format!("<a href=\"{file_name}\">{}</a>", escape(name)),
+                    None => escape(name),
+                },
+            })
+            .collect()
+    }
+}
+
+/// Returns the `{base}.html` file name, with a numeric suffix if already taken.
+fn unique_file(base: &str, taken: &mut BTreeSet<String>) -> String {
+    let mut file_name = format!("{base}.html");
+    let mut index = 1;
+    while taken.contains(&file_name) {
+        index += 1;
+        file_name = format!("{base}-{index}.html");
+    }
+    taken.insert(file_name.clone());
+    file_name
+}
+
+fn operation_base(operation: &DocOperation<'_>) -> String {
+    let name = operation
+        .operation
+        .operation_id
+        .clone()
+        .unwrap_or_else(|| operation.title());
+    format!("operation-{}", slug::slugify(name))
+}
+
+fn write_example(out: &mut String, example: &Value) {
+    let json = serde_json::to_string_pretty(example).unwrap_or_else(|_| example.to_string());
+    let _ = writeln!(out, "<pre><code>{}</code></pre>", escape(&json));
+}
+
+fn code_list(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|value| format!("<code>{}</code>", escape(value)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value { "yes" } else { "no" }
+}
+
+/// Escapes a text for HTML content and attribute values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use utoipa::openapi::path::{OperationBuilder, PathItemBuilder};
+    use utoipa::openapi::schema::{ObjectBuilder, Type};
+    use utoipa::openapi::{
+        ComponentsBuilder, ContentBuilder, HttpMethod, Info, PathsBuilder, Ref, ResponseBuilder,
+    };
+
+    fn create_test_spec() -> OpenApi {
+        let user = ObjectBuilder::new()
+            .property("id", ObjectBuilder::new().schema_type(Type::Integer))
+            .property("name", ObjectBuilder::new().schema_type(Type::String))
+            .required("id")
+            .build();
+
+        let get_user = OperationBuilder::new()
+            .tags(Some(vec!["users".to_string()]))
+            .operation_id(Some("get-users-id"))
+            .description(Some("Get a <user>"))
+            .response(
+                "200",
+                ResponseBuilder::new().description("The user").content(
+                    "application/json",
+                    ContentBuilder::new()
+                        .schema(Some(Ref::from_schema_name("User")))
+                        .example(Some(json!({"id": 1, "name": "Alice & Bob"})))
+                        .build(),
+                ),
+            )
+            .build();
+
+        OpenApi::builder()
+            .info(Info::new("Users API", "1.0.0"))
+            .paths(
+                PathsBuilder::new().path(
+                    "/users/{id}",
+                    PathItemBuilder::new()
+                        .operation(HttpMethod::Get, get_user)
+                        .build(),
+                ),
+            )
+            .components(Some(ComponentsBuilder::new().schema("User", user).build()))
+            .build()
+    }
+
+    #[test]
+    fn should_escape_html() {
+        assert_eq!(
+            escape(r#"<a href="x">Tom & 'Jerry'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &#39;Jerry&#39;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn should_render_html_pages() {
+        let pages = to_html_pages(&create_test_spec());
+
+        let file_names: Vec<_> = pages.iter().map(|page| page.file_name.as_str()).collect();
+        assert_eq!(
+            file_names,
+            vec![
+                "index.html",
+                "tag-users.html",
+                "operation-get-users-id.html",
+                "schema-user.html"
+            ]
+        );
+    }
+
+    #[test]
+    fn should_render_operation_page() {
+        let openapi = create_test_spec();
+        let pages = to_html_pages(&openapi);
+        let site = Site::new(&openapi);
+        let operation = &pages[2].content;
+
+        assert!(operation.contains(&site.navigation));
+        assert!(!operation.contains("http://") && !operation.contains("https://"));
+        let main = operation
+            .split("<main>\n")
+            .nth(1)
+            .and_then(|main| main.split("</main>").next())
+            .expect("should have a main element");
+        insta::assert_snapshot!(main, @r#"
+        <h1><span class="method">GET</span> <code>/users/{id}</code></h1>
+        <p>Operation ID: <code>get-users-id</code></p>
+        <p>Get a &lt;user&gt;</p>
+        <h2>Responses</h2>
+        <h3>200</h3>
+        <p>The user</p>
+        <p>Content type: <code>application/json</code>, schema: <a href="schema-user.html">User</a></p>
+        <table>
+        <tr><th>Field</th><th>Type</th><th>Required</th><th>Description</th></tr>
+        <tr><td><code>id</code></td><td>integer</td><td>yes</td><td></td></tr>
+        <tr><td><code>name</code></td><td>string</td><td>no</td><td></td></tr>
+        </table>
+        <pre><code>{
+          &quot;id&quot;: 1,
+          &quot;name&quot;: &quot;Alice &amp; Bob&quot;
+        }</code></pre>
+        "#);
+    }
+
+    #[test]
+    fn should_deduplicate_tag_and_operation_file_names() {
+        let operation = |tag: &str, operation_id: &str| {
+            OperationBuilder::new()
+                .tags(Some(vec![tag.to_string()]))
+                .operation_id(Some(operation_id))
+                .response("200", ResponseBuilder::new().description("ok"))
+                .build()
+        };
+        let openapi = OpenApi::builder()
+            .paths(
+                PathsBuilder::new()
+                    .path(
+                        "/admins",
+                        PathItemBuilder::new()
+                            .operation(HttpMethod::Get, operation("User Admin", "list users"))
+                            .build(),
+                    )
+                    .path(
+                        "/users",
+                        PathItemBuilder::new()
+                            .operation(HttpMethod::Get, operation("user-admin", "list-users"))
+                            .build(),
+                    ),
+            )
+            .build();
+
+        let pages = to_html_pages(&openapi);
+
+        let file_names: Vec<_> = pages.iter().map(|page| page.file_name.as_str()).collect();
+        assert_eq!(
+            file_names,
+            vec![
+                "index.html",
+                "tag-user-admin.html",
+                "tag-user-admin-2.html",
+                "operation-list-users.html",
+                "operation-list-users-2.html",
+            ]
+        );
+        assert!(pages[3].content.contains("<code>/admins</code>"));
+        assert!(pages[4].content.contains("<code>/users</code>"));
+        assert!(
+            pages[2]
+                .content
+                .contains("href=\"operation-list-users-2.html\"")
+        );
+    }
+
+    #[test]
+    fn should_deduplicate_schema_file_names() {
+        let openapi = OpenApi::builder()
+            .components(Some(
+                ComponentsBuilder::new()
+                    .schema("user-dto", ObjectBuilder::new())
+                    .schema("user_dto", ObjectBuilder::new())
+                    .build(),
+            ))
+            .build();
+
+        let site = Site::new(&openapi);
+
+        assert_eq!(
+            site.schema_files.values().collect::<Vec<_>>(),
+            vec!["schema-user-dto.html", "schema-user-dto-2.html"]
+        );
+    }
+}
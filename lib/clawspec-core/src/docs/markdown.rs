@@ -0,0 +1,449 @@
+use std::fmt::Write;
+
+use serde_json::Value;
+use utoipa::openapi::OpenApi;
+
+use super::model::{
+    Body, DocOperation, Field, SchemaResolver, TypePart, doc_tags, enum_values, schema_description,
+    schema_example,
+};
+
+/// Renders an OpenAPI specification as a Markdown API reference.
+///
+/// The document contains:
+/// - the API title, version and description
+/// - a table of contents
+/// - one section per tag, with a subsection per operation: method and path, description,
+///   parameter table, request and response bodies as field tables, and their examples
+///   as fenced JSON blocks
+/// - a section per component schema, referenced by the field tables
+///
+/// Headings and links follow the GitHub anchor conventions, so the document can be
+/// browsed directly in most Markdown renderers and wikis.
+///
+/// # Example
+///
+/// ```rust
+/// use clawspec_core::OpenApi;
+/// use clawspec_core::docs::to_markdown;
+///
+/// let openapi = OpenApi::builder()
+///     .info(clawspec_core::Info::new("Pets API", "1.0.0"))
+///     .build();
+///
+/// let markdown = to_markdown(&openapi);
+/// assert!(markdown.starts_with("# Pets API"));
+/// ```
+pub fn to_markdown(openapi: &OpenApi) -> String {
+    let resolver = SchemaResolver::new(openapi);
+    let tags = doc_tags(openapi);
+    let schemas = resolver.schemas();
+    let mut out = String::new();
+
+    let _ = writeln!(out, "# {}\n", openapi.info.title);
+    let _ = writeln!(out, "Version: `{}`\n", openapi.info.version);
+    if let Some(description) = &openapi.info.description {
+        let _ = writeln!(out, "{description}\n");
+    }
+
+    // Table of contents
+    if !tags.is_empty() {
+        let _ = writeln!(out, "## Table of contents\n");
+        for tag in &tags {
+            let _ = writeln!(out, "- [{}](#{})", tag.name, anchor(&tag.name));
+            for operation in &tag.operations {
+                let title = operation.title();
+                let _ = writeln!(out, "  - [{title}](#{})", anchor(&title));
+            }
+        }
+        if !schemas.is_empty() {
+            let _ = writeln!(out, "- [Schemas](#schemas)");
+        }
+        out.push('\n');
+    }
+
+    for tag in &tags {
+        let _ = writeln!(out, "## {}\n", tag.name);
+        if let Some(description) = tag.description {
+            let _ = writeln!(out, "{description}\n");
+        }
+        for operation in &tag.operations {
+            write_operation(&mut out, &resolver, operation);
+        }
+    }
+
+    if !schemas.is_empty() {
+        let _ = writeln!(out, "## Schemas\n");
+        for (name, schema) in schemas {
+            let _ = writeln!(out, "### {name}\n");
+            let resolved = resolver.resolve(schema);
+            if let Some(description) = resolved.and_then(schema_description) {
+                let _ = writeln!(out, "{description}\n");
+            }
+            let fields = resolver.fields(schema, 0);
+            if fields.is_empty() {
+                let _ = writeln!(out, "Type: {}\n", type_label(&resolver.type_label(schema)));
+            } else {
+                write_fields(&mut out, &fields);
+            }
+            let values = resolved.map(enum_values).unwrap_or_default();
+            if !values.is_empty() {
+                let _ = writeln!(out, "Values: {}\n", code_list(&values));
+            }
+            if let Some(example) = resolved.and_then(schema_example) {
+                write_example(&mut out, &example);
+            }
+        }
+    }
+
+    let trimmed = out.trim_end().len();
+    out.truncate(trimmed);
+    out.push('\n');
+    out
+}
+
+fn write_operation(out: &mut String, resolver: &SchemaResolver<'_>, doc: &DocOperation<'_>) {
+    let operation = doc.operation;
+    let _ = writeln!(out, "### {}\n", doc.title());
+    if let Some(operation_id) = &operation.operation_id {
+        let _ = writeln!(out, "Operation ID: `{operation_id}`\n");
+    }
+    if operation.deprecated.is_some() {
+        let _ = writeln!(out, "> **Deprecated**\n");
+    }
+    if let Some(summary) = &operation.summary {
+        let _ = writeln!(out, "{summary}\n");
+    }
+    if let Some(description) = &operation.description {
+        let _ = writeln!(out, "{description}\n");
+    }
+
+    let parameters = resolver.parameters(operation);
+    if !parameters.is_empty() {
+        let _ = writeln!(out, "#### Parameters\n");
+        let _ = writeln!(out, "| Name | In | Type | Required | Description |");
+        let _ = writeln!(out, "| --- | --- | --- | --- | --- |");
+        for parameter in parameters {
+            let _ = writeln!(
+                out,
+                "| `{}` | {} | {} | {} | {} |",
+                parameter.name,
+                parameter.location,
+                type_label(&parameter.type_label),
+                yes_no(parameter.required),
+                cell(parameter.description.as_deref().unwrap_or_default()),
+            );
+        }
+        out.push('\n');
+    }
+
+    let request_bodies = resolver.request_bodies(operation);
+    if !request_bodies.is_empty() {
+        let _ = writeln!(out, "#### Request body\n");
+        for body in &request_bodies {
+            write_body(out, body);
+        }
+    }
+
+    let responses = resolver.responses(operation);
+    if !responses.is_empty() {
+        let _ = writeln!(out, "#### Responses\n");
+        for response in &responses {
+            let _ = writeln!(out, "##### {}\n", response.status);
+            if !response.description.is_empty() {
+                let _ = writeln!(out, "{}\n", response.description);
+            }
+            for body in &response.bodies {
+                write_body(out, body);
+            }
+        }
+    }
+}
+
+fn write_body(out: &mut String, body: &Body) {
+    if body.type_label.is_empty() {
+        let _ = writeln!(out, "Content type: `{}`\n", body.content_type);
+    } else {
+        let _ = writeln!(
+            out,
+            "Content type: `{}`, schema: {}\n",
+            body.content_type,
+            type_label(&body.type_label)
+        );
+    }
+    write_fields(out, &body.fields);
+    if let Some(example) = &body.example {
+        write_example(out, example);
+    }
+}
+
+fn write_fields(out: &mut String, fields: &[Field]) {
+    if fields.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "| Field | Type | Required | Description |");
+    let _ = writeln!(out, "| --- | --- | --- | --- |");
+    for field in fields {
+        let mut description = cell(field.description.as_deref().unwrap_or_default());
+        if !field.values.is_empty() {
+            if !description.is_empty() {
+                description.push(' ');
+            }
+            let _ = write!(description, "Values: {}", code_list(&field.values));
+        }
+        let _ = writeln!(
+            out,
+            "| `{}` | {} | {} | {} |",
+            field.name,
+            type_label(&field.type_label),
+            yes_no(field.required),
+            description,
+        );
+    }
+    out.push('\n');
+}
+
+fn write_example(out: &mut String, example: &Value) {
+    let json = serde_json::to_string_pretty(example).unwrap_or_else(|_| example.to_string());
+    let _ = writeln!(out, "```json\n{json}\n```\n");
+}
+
+fn type_label(parts: &[TypePart]) -> String {
+    parts
+        .iter()
+        .map(|part| match part {
+            TypePart::Text(text) => cell(text),
+            TypePart::Schema(name) => format!("[{name}](#{})", anchor(name)),
+        })
+        .collect()
+}
+
+fn code_list(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|value| format!("`{}`", cell(value)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value { "yes" } else { "no" }
+}
+
+/// Escapes a value for a table cell.
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+/// Computes the anchor of a heading, following the GitHub conventions.
+fn anchor(heading: &str) -> String {
+    heading
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use utoipa::openapi::path::{OperationBuilder, ParameterBuilder, ParameterIn, PathItemBuilder};
+    use utoipa::openapi::request_body::RequestBodyBuilder;
+    use utoipa::openapi::schema::{ArrayBuilder, ObjectBuilder, Type};
+    use utoipa::openapi::tag::TagBuilder;
+    use utoipa::openapi::{
+        ComponentsBuilder, ContentBuilder, HttpMethod, Info, PathsBuilder, Ref, Required,
+        ResponseBuilder,
+    };
+
+    fn create_test_spec() -> OpenApi {
+        let user = ObjectBuilder::new()
+            .description(Some("A registered user"))
+            .property("id", ObjectBuilder::new().schema_type(Type::Integer))
+            .property(
+                "name",
+                ObjectBuilder::new()
+                    .schema_type(Type::String)
+                    .description(Some("Display name | nickname")),
+            )
+            .property(
+                "role",
+                ObjectBuilder::new()
+                    .schema_type(Type::String)
+                    .enum_values(Some(["admin", "user"])),
+            )
+            .required("id")
+            .required("name")
+            .build();
+
+        let list_users = OperationBuilder::new()
+            .tags(Some(vec!["users".to_string()]))
+            .operation_id(Some("listUsers"))
+            .description(Some("List the users"))
+            .parameter(
+                ParameterBuilder::new()
+                    .name("limit")
+                    .parameter_in(ParameterIn::Query)
+                    .required(Required::False)
+                    .schema(Some(ObjectBuilder::new().schema_type(Type::Integer))),
+            )
+            .response(
+                "200",
+                ResponseBuilder::new().description("The users").content(
+                    "application/json",
+                    ContentBuilder::new()
+                        .schema(Some(
+                            ArrayBuilder::new().items(Ref::from_schema_name("User")),
+                        ))
+                        .example(Some(json!([{"id": 1, "name": "Alice", "role": "admin"}])))
+                        .build(),
+                ),
+            )
+            .build();
+
+        let create_user = OperationBuilder::new()
+            .tags(Some(vec!["users".to_string()]))
+            .operation_id(Some("createUser"))
+            .request_body(Some(
+                RequestBodyBuilder::new()
+                    .content(
+                        "application/json",
+                        ContentBuilder::new()
+                            .schema(Some(Ref::from_schema_name("User")))
+                            .example(Some(json!({"id": 2, "name": "Bob"})))
+                            .build(),
+                    )
+                    .build(),
+            ))
+            .response("201", ResponseBuilder::new().description("Created"))
+            .build();
+
+        OpenApi::builder()
+            .info(Info::new("Users API", "1.2.0"))
+            .tags(Some(vec![
+                TagBuilder::new()
+                    .name("users")
+                    .description(Some("User management"))
+                    .build(),
+            ]))
+            .paths(
+                PathsBuilder::new().path(
+                    "/users",
+                    PathItemBuilder::new()
+                        .operation(HttpMethod::Get, list_users)
+                        .operation(HttpMethod::Post, create_user)
+                        .build(),
+                ),
+            )
+            .components(Some(ComponentsBuilder::new().schema("User", user).build()))
+            .build()
+    }
+
+    #[test]
+    fn should_compute_github_anchors() {
+        assert_eq!(anchor("GET /users/{id}"), "get-usersid");
+        assert_eq!(anchor("Page_User"), "page_user");
+    }
+
+    #[test]
+    fn should_render_markdown_reference() {
+        let markdown = to_markdown(&create_test_spec());
+
+        insta::assert_snapshot!(markdown, @r#"
+        # Users API
+
+        Version: `1.2.0`
+
+        ## Table of contents
+
+        - [users](#users)
+          - [GET /users](#get-users)
+          - [POST /users](#post-users)
+        - [Schemas](#schemas)
+
+        ## users
+
+        User management
+
+        ### GET /users
+
+        Operation ID: `listUsers`
+
+        List the users
+
+        #### Parameters
+
+        | Name | In | Type | Required | Description |
+        | --- | --- | --- | --- | --- |
+        | `limit` | query | integer | no |  |
+
+        #### Responses
+
+        ##### 200
+
+        The users
+
+        Content type: `application/json`, schema: array of [User](#user)
+
+        | Field | Type | Required | Description |
+        | --- | --- | --- | --- |
+        | `id` | integer | yes |  |
+        | `name` | string | yes | Display name \| nickname |
+        | `role` | string | no | Values: `admin`, `user` |
+
+        ```json
+        [
+          {
+            "id": 1,
+            "name": "Alice",
+            "role": "admin"
+          }
+        ]
+        ```
+
+        ### POST /users
+
+        Operation ID: `createUser`
+
+        #### Request body
+
+        Content type: `application/json`, schema: [User](#user)
+
+        | Field | Type | Required | Description |
+        | --- | --- | --- | --- |
+        | `id` | integer | yes |  |
+        | `name` | string | yes | Display name \| nickname |
+        | `role` | string | no | Values: `admin`, `user` |
+
+        ```json
+        {
+          "id": 2,
+          "name": "Bob"
+        }
+        ```
+
+        #### Responses
+
+        ##### 201
+
+        Created
+
+        ## Schemas
+
+        ### User
+
+        A registered user
+
+        | Field | Type | Required | Description |
+        | --- | --- | --- | --- |
+        | `id` | integer | yes |  |
+        | `name` | string | yes | Display name \| nickname |
+        | `role` | string | no | Values: `admin`, `user` |
+        "#);
+    }
+}
@@ -0,0 +1,32 @@
+//! Human-readable documentation generated from the collected OpenAPI specification.
+//!
+//! The specification collected by an [`ApiClient`](crate::ApiClient) can be rendered as
+//! an API reference, ready to be published alongside the OpenAPI file.
+//!
+//! # Formats
+//!
+//! - [`to_markdown`] - a single Markdown document, e.g. for a wiki
+//! - [`to_html_pages`] / [`write_html`] - a static HTML site, browsable offline
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use clawspec_core::ApiClient;
+//! use clawspec_core::docs::to_markdown;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut client = ApiClient::builder().with_port(8080).build()?;
+//! client.get("/users")?.await?.as_empty().await?;
+//!
+//! let openapi = client.collected_openapi().await;
+//! std::fs::write("API.md", to_markdown(&openapi))?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod html;
+mod markdown;
+mod model;
+
+pub use self::html::{HtmlPage, to_html_pages, write_html};
+pub use self::markdown::to_markdown;
@@ -0,0 +1,549 @@
+use std::collections::BTreeMap;
+
+use http::Method;
+use serde_json::Value;
+use utoipa::openapi::path::{Operation, ParameterIn};
+use utoipa::openapi::schema::{AdditionalProperties, ArrayItems, SchemaType, Type};
+use utoipa::openapi::{Content, OpenApi, RefOr, Required, Response, Schema};
+
+use crate::client::operation_parameters;
+use crate::operations::iter_operations;
+
+/// Tag used for the operations without tags.
+const DEFAULT_TAG: &str = "default";
+
+/// Maximum depth when walking nested schemas, protects against reference cycles.
+const MAX_SCHEMA_DEPTH: usize = 16;
+
+/// A tag with its operations, in the documentation order.
+#[derive(Debug)]
+pub(crate) struct DocTag<'a> {
+    pub(crate) name: String,
+    pub(crate) description: Option<&'a str>,
+    pub(crate) operations: Vec<DocOperation<'a>>,
+}
+
+/// An operation with its path and HTTP method.
+#[derive(Debug, Clone)]
+pub(crate) struct DocOperation<'a> {
+    pub(crate) method: Method,
+    pub(crate) path: &'a str,
+    pub(crate) operation: &'a Operation,
+}
+
+impl DocOperation<'_> {
+    /// The operation title, e.g. `GET /users/{id}`.
+    pub(crate) fn title(&self) -> String {
+        format!("{} {}", self.method, self.path)
+    }
+}
+
+/// A part of a type label, either plain text or a reference to a component schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TypePart {
+    Text(String),
+    Schema(String),
+}
+
+/// A row of a schema field table.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Field {
+    pub(crate) name: String,
+    pub(crate) type_label: Vec<TypePart>,
+    pub(crate) required: bool,
+    pub(crate) description: Option<String>,
+    pub(crate) values: Vec<String>,
+}
+
+/// A row of a parameter table.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ParameterRow {
+    pub(crate) name: String,
+    pub(crate) location: &'static str,
+    pub(crate) type_label: Vec<TypePart>,
+    pub(crate) required: bool,
+    pub(crate) description: Option<String>,
+}
+
+/// A request or response body, for one content type.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Body {
+    pub(crate) content_type: String,
+    pub(crate) type_label: Vec<TypePart>,
+    pub(crate) fields: Vec<Field>,
+    pub(crate) example: Option<Value>,
+}
+
+/// A documented response.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ResponseDoc {
+    pub(crate) status: String,
+    pub(crate) description: String,
+    pub(crate) bodies: Vec<Body>,
+}
+
+/// Groups the operations of the specification by tag.
+///
+/// Declared tags come first, in their declaration order, then the other tags in
+/// order of first use. An operation with several tags appears in each of them.
+pub(crate) fn doc_tags(openapi: &OpenApi) -> Vec<DocTag<'_>> {
+    let mut tags: Vec<DocTag<'_>> = openapi
+        .tags
+        .iter()
+        .flatten()
+        .map(|tag| DocTag {
+            name: tag.name.clone(),
+            description: tag.description.as_deref(),
+            operations: vec![],
+        })
+        .collect();
+
+    for (path, path_item) in &openapi.paths.paths {
+        for (method, operation) in iter_operations(path_item) {
+            let doc_operation = DocOperation {
+                method,
+                path,
+                operation,
+            };
+            let names = match &operation.tags {
+                Some(names) if !names.is_empty() => names.clone(),
+                _ => vec![DEFAULT_TAG.to_string()],
+            };
+            for name in names {
+                let index = match tags.iter().position(|tag| tag.name == name) {
+                    Some(index) => index,
+                    None => {
+                        tags.push(DocTag {
+                            name,
+                            description: None,
+                            operations: vec![],
+                        });
+                        tags.len() - 1
+                    }
+                };
+                tags[index].operations.push(doc_operation.clone());
+            }
+        }
+    }
+
+    tags.retain(|tag| !tag.operations.is_empty());
+    tags
+}
+
+/// Resolves schemas against the component schemas of a specification.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SchemaResolver<'a> {
+    openapi: &'a OpenApi,
+}
+
+impl<'a> SchemaResolver<'a> {
+    pub(crate) fn new(openapi: &'a OpenApi) -> Self {
+        Self { openapi }
+    }
+
+    /// Returns the component schemas, sorted by name.
+    pub(crate) fn schemas(&self) -> BTreeMap<&'a str, &'a RefOr<Schema>> {
+        self.openapi
+            .components
+            .iter()
+            .flat_map(|components| &components.schemas)
+            .map(|(name, schema)| (name.as_str(), schema))
+            .collect()
+    }
+
+    /// Returns the parameter rows of an operation.
+    pub(crate) fn parameters(&self, operation: &Operation) -> Vec<ParameterRow> {
+        operation_parameters(operation, self.openapi.components.as_ref())
+            .into_iter()
+            .map(|parameter| ParameterRow {
+                name: parameter.name.clone(),
+                location: match parameter.parameter_in {
+                    ParameterIn::Path => "path",
+                    ParameterIn::Query => "query",
+                    ParameterIn::Header => "header",
+                    ParameterIn::Cookie => "cookie",
+                },
+                type_label: parameter
+                    .schema
+                    .as_ref()
+                    .map(|schema| self.type_label(schema))
+                    .unwrap_or_default(),
+                required: matches!(parameter.required, Required::True),
+                description: parameter.description.clone(),
+            })
+            .collect()
+    }
+
+    /// Returns the request bodies of an operation, one per content type.
+    pub(crate) fn request_bodies(&self, operation: &Operation) -> Vec<Body> {
+        operation
+            .request_body
+            .iter()
+            .flat_map(|body| &body.content)
+            .map(|(content_type, content)| self.body(content_type, content))
+            .collect()
+    }
+
+    /// Returns the documented responses of an operation.
+    pub(crate) fn responses(&self, operation: &Operation) -> Vec<ResponseDoc> {
+        operation
+            .responses
+            .responses
+            .iter()
+            .filter_map(|(status, response)| {
+                let response = self.resolve_response(response)?;
+                Some(ResponseDoc {
+                    status: status.clone(),
+                    description: response.description.clone(),
+                    bodies: response
+                        .content
+                        .iter()
+                        .map(|(content_type, content)| self.body(content_type, content))
+                        .collect(),
+                })
+            })
+            .collect()
+    }
+
+    fn resolve_response(&self, response: &'a RefOr<Response>) -> Option<&'a Response> {
+        match response {
+            RefOr::T(response) => Some(response),
+            RefOr::Ref(reference) => {
+                let name = reference
+                    .ref_location
+                    .strip_prefix("#/components/responses/")?;
+                match self.openapi.components.as_ref()?.responses.get(name)? {
+                    RefOr::T(response) => Some(response),
+                    RefOr::Ref(_) => None,
+                }
+            }
+        }
+    }
+
+    fn body(&self, content_type: &str, content: &Content) -> Body {
+        let example = content.example.clone().or_else(|| {
+            content.examples.values().find_map(|example| match example {
+                RefOr::T(example) => example.value.clone(),
+                RefOr::Ref(_) => None,
+            })
+        });
+        Body {
+            content_type: content_type.to_string(),
+            type_label: content
+                .schema
+                .as_ref()
+                .map(|schema| self.type_label(schema))
+                .unwrap_or_default(),
+            fields: content
+                .schema
+                .as_ref()
+                .map(|schema| self.body_fields(schema))
+                .unwrap_or_default(),
+            example,
+        }
+    }
+
+    /// Returns the fields of a body schema, looking through references and arrays.
+    pub(crate) fn body_fields(&self, schema: &RefOr<Schema>) -> Vec<Field> {
+        match self.resolve(schema) {
+            Some(Schema::Array(array)) => match &array.items {
+                ArrayItems::RefOrSchema(items) => self.fields(items, 0),
+                ArrayItems::False => vec![],
+            },
+            _ => self.fields(schema, 0),
+        }
+    }
+
+    /// Returns the fields of an object schema, merging the `allOf` members.
+    pub(crate) fn fields(&self, schema: &RefOr<Schema>, depth: usize) -> Vec<Field> {
+        if depth > MAX_SCHEMA_DEPTH {
+            return vec![];
+        }
+        match self.resolve(schema) {
+            Some(Schema::Object(object)) => object
+                .properties
+                .iter()
+                .map(|(name, property)| {
+                    let resolved = self.resolve(property);
+                    Field {
+                        name: name.clone(),
+                        type_label: self.type_label(property),
+                        required: object.required.contains(name),
+                        description: resolved.and_then(schema_description),
+                        values: resolved.map(enum_values).unwrap_or_default(),
+                    }
+                })
+                .collect(),
+            Some(Schema::AllOf(all_of)) => all_of
+                .items
+                .iter()
+                .flat_map(|item| self.fields(item, depth + 1))
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Resolves a local component schema reference.
+    pub(crate) fn resolve(&self, schema: &'a RefOr<Schema>) -> Option<&'a Schema> {
+        let mut current = schema;
+        for _ in 0..MAX_SCHEMA_DEPTH {
+            match current {
+                RefOr::T(schema) => return Some(schema),
+                RefOr::Ref(reference) => {
+                    let name = schema_name(&reference.ref_location)?;
+                    current = self.openapi.components.as_ref()?.schemas.get(name)?;
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns a short human-readable label of a schema type, e.g. `array of User`.
+    pub(crate) fn type_label(&self, schema: &RefOr<Schema>) -> Vec<TypePart> {
+        let mut parts = vec![];
+        push_type_label(&mut parts, schema, 0);
+        parts
+    }
+}
+
+fn push_type_label(parts: &mut Vec<TypePart>, schema: &RefOr<Schema>, depth: usize) {
+    if depth > MAX_SCHEMA_DEPTH {
+        parts.push(TypePart::Text("...".to_string()));
+        return;
+    }
+    let schema = match schema {
+        RefOr::Ref(reference) => {
+            match schema_name(&reference.ref_location) {
+                Some(name) => parts.push(TypePart::Schema(name.to_string())),
+                None => parts.push(TypePart::Text(reference.ref_location.clone())),
+            }
+            return;
+        }
+        RefOr::T(schema) => schema,
+    };
+
+    let push_all = |parts: &mut Vec<TypePart>, items: &[RefOr<Schema>], separator: &str| {
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 {
+                parts.push(TypePart::Text(separator.to_string()));
+            }
+            push_type_label(parts, item, depth + 1);
+        }
+    };
+
+    match schema {
+        Schema::Array(array) => {
+            parts.push(TypePart::Text("array of ".to_string()));
+            match &array.items {
+                ArrayItems::RefOrSchema(items) => push_type_label(parts, items, depth + 1),
+                ArrayItems::False => parts.push(TypePart::Text("nothing".to_string())),
+            }
+        }
+        Schema::Object(object) => {
+            let mut label = match &object.schema_type {
+                SchemaType::Type(schema_type) => type_name(schema_type).to_string(),
+                SchemaType::Array(types) => {
+                    types.iter().map(type_name).collect::<Vec<_>>().join(" or ")
+                }
+                SchemaType::AnyValue => "any".to_string(),
+            };
+            if let Some(format) = &object.format
+                && let Ok(Value::String(format)) = serde_json::to_value(format)
+            {
+                label.push_str(&format!(" ({format})"));
+            }
+            match object.additional_properties.as_deref() {
+                Some(AdditionalProperties::RefOr(values)) if object.properties.is_empty() => {
+                    parts.push(TypePart::Text("map of ".to_string()));
+                    push_type_label(parts, values, depth + 1);
+                }
+                _ => parts.push(TypePart::Text(label)),
+            }
+        }
+        Schema::OneOf(one_of) => push_all(parts, &one_of.items, " or "),
+        Schema::AnyOf(any_of) => push_all(parts, &any_of.items, " or "),
+        Schema::AllOf(all_of) => push_all(parts, &all_of.items, " and "),
+        _ => parts.push(TypePart::Text("any".to_string())),
+    }
+}
+
+fn type_name(schema_type: &Type) -> &'static str {
+    match schema_type {
+        Type::Object => "object",
+        Type::String => "string",
+        Type::Integer => "integer",
+        Type::Number => "number",
+        Type::Boolean => "boolean",
+        Type::Array => "array",
+        Type::Null => "null",
+    }
+}
+
+/// Returns the component name of a local schema reference.
+pub(crate) fn schema_name(reference: &str) -> Option<&str> {
+    reference.strip_prefix("#/components/schemas/")
+}
+
+/// Returns the description of a schema.
+pub(crate) fn schema_description(schema: &Schema) -> Option<String> {
+    match schema {
+        Schema::Object(object) => object.description.clone(),
+        Schema::Array(array) => array.description.clone(),
+        Schema::OneOf(one_of) => one_of.description.clone(),
+        Schema::AllOf(all_of) => all_of.description.clone(),
+        Schema::AnyOf(any_of) => any_of.description.clone(),
+        _ => None,
+    }
+}
+
+/// Returns the allowed values of an enumeration schema.
+pub(crate) fn enum_values(schema: &Schema) -> Vec<String> {
+    let Schema::Object(object) = schema else {
+        return vec![];
+    };
+    object
+        .enum_values
+        .iter()
+        .flatten()
+        .map(|value| match value {
+            Value::String(value) => value.clone(),
+            value => value.to_string(),
+        })
+        .collect()
+}
+
+/// Returns the example of a schema.
+pub(crate) fn schema_example(schema: &Schema) -> Option<Value> {
+    match schema {
+        Schema::Object(object) => object
+            .examples
+            .first()
+            .cloned()
+            .or_else(|| object.example.clone()),
+        Schema::OneOf(one_of) => one_of.example.clone(),
+        Schema::AllOf(all_of) => all_of.example.clone(),
+        Schema::AnyOf(any_of) => any_of
+            .examples
+            .first()
+            .cloned()
+            .or_else(|| any_of.example.clone()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utoipa::openapi::path::{OperationBuilder, PathItemBuilder};
+    use utoipa::openapi::schema::{ArrayBuilder, ObjectBuilder};
+    use utoipa::openapi::{ComponentsBuilder, HttpMethod, PathsBuilder, Ref};
+
+    fn create_test_spec() -> OpenApi {
+        let user = ObjectBuilder::new()
+            .property("id", ObjectBuilder::new().schema_type(Type::Integer))
+            .property(
+                "role",
+                ObjectBuilder::new()
+                    .schema_type(Type::String)
+                    .enum_values(Some(["admin", "user"])),
+            )
+            .property(
+                "friends",
+                ArrayBuilder::new().items(Ref::from_schema_name("User")),
+            )
+            .required("id")
+            .build();
+
+        let list_users = OperationBuilder::new()
+            .tags(Some(vec!["users".to_string()]))
+            .build();
+        let health = OperationBuilder::new().build();
+
+        OpenApi::builder()
+            .paths(
+                PathsBuilder::new()
+                    .path(
+                        "/users",
+                        PathItemBuilder::new()
+                            .operation(HttpMethod::Get, list_users.clone())
+                            .operation(HttpMethod::Post, list_users)
+                            .build(),
+                    )
+                    .path(
+                        "/health",
+                        PathItemBuilder::new()
+                            .operation(HttpMethod::Get, health)
+                            .build(),
+                    ),
+            )
+            .components(Some(ComponentsBuilder::new().schema("User", user).build()))
+            .build()
+    }
+
+    #[test]
+    fn should_group_operations_by_tag() {
+        let openapi = create_test_spec();
+
+        let tags = doc_tags(&openapi);
+
+        let summary: Vec<_> = tags
+            .iter()
+            .map(|tag| {
+                let titles: Vec<_> = tag.operations.iter().map(DocOperation::title).collect();
+                (tag.name.as_str(), titles)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("default", vec!["GET /health".to_string()]),
+                (
+                    "users",
+                    vec!["GET /users".to_string(), "POST /users".to_string()]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_resolve_fields_of_referenced_schema() {
+        let openapi = create_test_spec();
+        let resolver = SchemaResolver::new(&openapi);
+
+        let fields = resolver.body_fields(&RefOr::Ref(Ref::from_schema_name("User")));
+
+        assert_eq!(fields.len(), 3);
+        assert_eq!(fields[0].name, "friends");
+        assert_eq!(
+            fields[0].type_label,
+            vec![
+                TypePart::Text("array of ".to_string()),
+                TypePart::Schema("User".to_string())
+            ]
+        );
+        assert!(!fields[0].required);
+        assert_eq!(fields[1].name, "id");
+        assert!(fields[1].required);
+        assert_eq!(fields[2].values, vec!["admin", "user"]);
+    }
+
+    #[test]
+    fn should_resolve_fields_of_array_items() {
+        let openapi = create_test_spec();
+        let resolver = SchemaResolver::new(&openapi);
+        let schema = RefOr::T(Schema::Array(
+            ArrayBuilder::new()
+                .items(Ref::from_schema_name("User"))
+                .build(),
+        ));
+
+        assert_eq!(resolver.body_fields(&schema).len(), 3);
+        assert_eq!(
+            resolver.type_label(&schema),
+            vec![
+                TypePart::Text("array of ".to_string()),
+                TypePart::Schema("User".to_string())
+            ]
+        );
+    }
+}
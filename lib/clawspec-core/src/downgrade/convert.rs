@@ -0,0 +1,518 @@
+use indexmap::IndexMap;
+
+use super::DowngradeWarning;
+use super::node::Node;
+
+/// The HTTP methods of a path item.
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// JSON Schema keywords that OpenAPI 3.0 schemas do not support.
+const UNSUPPORTED_KEYWORDS: [&str; 17] = [
+    "$id",
+    "$schema",
+    "$anchor",
+    "$defs",
+    "$comment",
+    "patternProperties",
+    "propertyNames",
+    "unevaluatedProperties",
+    "unevaluatedItems",
+    "dependentSchemas",
+    "dependentRequired",
+    "if",
+    "then",
+    "else",
+    "contains",
+    "minContains",
+    "maxContains",
+];
+
+/// Downgrades an object of the document, given its JSON Pointer.
+type Downgrade = fn(&mut Downgrader, &mut Node, &str);
+
+/// Rewrites an OpenAPI 3.1 document into OpenAPI 3.0.3, collecting warnings.
+#[derive(Debug, Default)]
+pub(super) struct Downgrader {
+    pub(super) warnings: Vec<DowngradeWarning>,
+}
+
+impl Downgrader {
+    fn warn(&mut self, pointer: &str, message: impl Into<String>) {
+        self.warnings.push(DowngradeWarning {
+            pointer: pointer.to_string(),
+            message: message.into(),
+        });
+    }
+
+    /// Removes a key not supported by OpenAPI 3.0, with a warning.
+    fn remove_unsupported(&mut self, map: &mut IndexMap<String, Node>, pointer: &str, key: &str) {
+        if map.shift_remove(key).is_some() {
+            self.warn(
+                &child(pointer, key),
+                format!("`{key}` is not supported by OpenAPI 3.0, removed"),
+            );
+        }
+    }
+
+    pub(super) fn document(&mut self, document: &mut Node) {
+        let Some(map) = document.as_object_mut() else {
+            return;
+        };
+        if let Some(version) = map.get_mut("openapi") {
+            *version = Node::from("3.0.3");
+        }
+        for key in ["$schema", "jsonSchemaDialect", "webhooks"] {
+            self.remove_unsupported(map, "", key);
+        }
+
+        if let Some(info) = map.get_mut("info").and_then(Node::as_object_mut) {
+            self.remove_unsupported(info, "/info", "summary");
+            if let Some(license) = info.get_mut("license").and_then(Node::as_object_mut) {
+                self.remove_unsupported(license, "/info/license", "identifier");
+            }
+        }
+
+        if let Some(paths) = map.get_mut("paths").and_then(Node::as_object_mut) {
+            for (path, item) in paths {
+                self.path_item(item, &child("/paths", path));
+            }
+        }
+
+        let removed = match map.get_mut("components").and_then(Node::as_object_mut) {
+            Some(components) => self.components(components),
+            None => vec![],
+        };
+        if !removed.is_empty() {
+            self.security(map, "", &removed);
+            if let Some(paths) = map.get_mut("paths").and_then(Node::as_object_mut) {
+                for (path, item) in paths {
+                    self.path_item_security(item, &child("/paths", path), &removed);
+                }
+            }
+        }
+    }
+
+    /// Downgrades the components, returns the names of the removed security schemes.
+    fn components(&mut self, components: &mut IndexMap<String, Node>) -> Vec<String> {
+        self.remove_unsupported(components, "/components", "pathItems");
+
+        let sections: [(&str, Downgrade); 5] = [
+            ("schemas", Self::schema),
+            ("responses", Self::response),
+            ("parameters", Self::parameter),
+            ("requestBodies", Self::request_body),
+            ("headers", Self::header),
+        ];
+        for (section, downgrade) in sections {
+            let pointer = child("/components", section);
+            if let Some(items) = components.get_mut(section).and_then(Node::as_object_mut) {
+                for (name, item) in items {
+                    downgrade(self, item, &child(&pointer, name));
+                }
+            }
+        }
+
+        let Some(schemes) = components
+            .get_mut("securitySchemes")
+            .and_then(Node::as_object_mut)
+        else {
+            return vec![];
+        };
+        let mutual_tls: Vec<_> = schemes
+            .iter()
+            .filter(|(_, scheme)| {
+                matches!(scheme, Node::Object(scheme)
+                        if scheme.get("type").and_then(Node::as_str) == Some("mutualTLS"))
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in &mutual_tls {
+            schemes.shift_remove(name);
+            self.warn(
+                &child("/components/securitySchemes", name),
+                "`mutualTLS` security schemes are not supported by OpenAPI 3.0, removed",
+            );
+        }
+        mutual_tls
+    }
+
+    /// Removes the requirements of the removed security schemes from the operations.
+    fn path_item_security(&mut self, node: &mut Node, pointer: &str, removed: &[String]) {
+        let Some(map) = node.as_object_mut() else {
+            return;
+        };
+        for method in METHODS {
+            let Some(operation) = map.get_mut(method).and_then(Node::as_object_mut) else {
+                continue;
+            };
+            let pointer = child(pointer, method);
+            self.security(operation, &pointer, removed);
+            if let Some(callbacks) = operation.get_mut("callbacks").and_then(Node::as_object_mut) {
+                for (name, callback) in callbacks {
+                    let pointer = child(&child(&pointer, "callbacks"), name);
+                    if let Some(expressions) = callback.as_object_mut() {
+                        for (expression, item) in expressions {
+                            self.path_item_security(item, &child(&pointer, expression), removed);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Removes the requirements of the removed security schemes from a `security` list.
+    ///
+    /// A requirement object left empty is dropped, since an empty object makes the security
+    /// optional.
+    fn security(&mut self, map: &mut IndexMap<String, Node>, pointer: &str, removed: &[String]) {
+        let Some(Node::Array(requirements)) = map.get_mut("security") else {
+            return;
+        };
+        let pointer = child(pointer, "security");
+        let mut index = 0;
+        requirements.retain_mut(|requirement| {
+            let pointer = child(&pointer, &index.to_string());
+            index += 1;
+            let Some(requirement) = requirement.as_object_mut() else {
+                return true;
+            };
+            let before = requirement.len();
+            for name in removed {
+                if requirement.shift_remove(name).is_some() {
+                    self.warn(
+                        &child(&pointer, name),
+                        "requirement of a removed `mutualTLS` security scheme, removed",
+                    );
+                }
+            }
+            before == requirement.len() || !requirement.is_empty()
+        });
+    }
+
+    fn path_item(&mut self, node: &mut Node, pointer: &str) {
+        let Some(map) = node.as_object_mut() else {
+            return;
+        };
+        if let Some(Node::Array(parameters)) = map.get_mut("parameters") {
+            for (index, parameter) in parameters.iter_mut().enumerate() {
+                self.parameter(
+                    parameter,
+                    &child(&child(pointer, "parameters"), &index.to_string()),
+                );
+            }
+        }
+        for method in METHODS {
+            if let Some(operation) = map.get_mut(method) {
+                self.operation(operation, &child(pointer, method));
+            }
+        }
+    }
+
+    fn operation(&mut self, node: &mut Node, pointer: &str) {
+        let Some(map) = node.as_object_mut() else {
+            return;
+        };
+        if let Some(Node::Array(parameters)) = map.get_mut("parameters") {
+            for (index, parameter) in parameters.iter_mut().enumerate() {
+                self.parameter(
+                    parameter,
+                    &child(&child(pointer, "parameters"), &index.to_string()),
+                );
+            }
+        }
+        if let Some(body) = map.get_mut("requestBody") {
+            self.request_body(body, &child(pointer, "requestBody"));
+        }
+        if let Some(responses) = map.get_mut("responses").and_then(Node::as_object_mut) {
+            for (status, response) in responses {
+                self.response(response, &child(&child(pointer, "responses"), status));
+            }
+        }
+        if let Some(callbacks) = map.get_mut("callbacks").and_then(Node::as_object_mut) {
+            for (name, callback) in callbacks {
+                let pointer = child(&child(pointer, "callbacks"), name);
+                if let Some(expressions) = callback.as_object_mut() {
+                    for (expression, item) in expressions {
+                        self.path_item(item, &child(&pointer, expression));
+                    }
+                }
+            }
+        }
+    }
+
+    fn parameter(&mut self, node: &mut Node, pointer: &str) {
+        let Some(map) = node.as_object_mut() else {
+            return;
+        };
+        if reference_only(map) {
+            return;
+        }
+        if let Some(schema) = map.get_mut("schema") {
+            self.schema(schema, &child(pointer, "schema"));
+        }
+        self.content(map, pointer);
+    }
+
+    fn request_body(&mut self, node: &mut Node, pointer: &str) {
+        let Some(map) = node.as_object_mut() else {
+            return;
+        };
+        if reference_only(map) {
+            return;
+        }
+        self.content(map, pointer);
+    }
+
+    fn response(&mut self, node: &mut Node, pointer: &str) {
+        let Some(map) = node.as_object_mut() else {
+            return;
+        };
+        if reference_only(map) {
+            return;
+        }
+        if let Some(headers) = map.get_mut("headers").and_then(Node::as_object_mut) {
+            for (name, header) in headers {
+                self.header(header, &child(&child(pointer, "headers"), name));
+            }
+        }
+        self.content(map, pointer);
+    }
+
+    fn header(&mut self, node: &mut Node, pointer: &str) {
+        let Some(map) = node.as_object_mut() else {
+            return;
+        };
+        if reference_only(map) {
+            return;
+        }
+        if let Some(schema) = map.get_mut("schema") {
+            self.schema(schema, &child(pointer, "schema"));
+        }
+    }
+
+    /// Downgrades the schemas of the media types of a `content` map.
+    fn content(&mut self, map: &mut IndexMap<String, Node>, pointer: &str) {
+        let Some(content) = map.get_mut("content").and_then(Node::as_object_mut) else {
+            return;
+        };
+        let pointer = child(pointer, "content");
+        for (media_type, media) in content {
+            if let Some(schema) = media
+                .as_object_mut()
+                .and_then(|media| media.get_mut("schema"))
+            {
+                self.schema(schema, &child(&child(&pointer, media_type), "schema"));
+            }
+        }
+    }
+
+    fn schema(&mut self, node: &mut Node, pointer: &str) {
+        // Boolean schemas are JSON Schema only
+        match node {
+            Node::Bool(true) => {
+                *node = Node::Object(IndexMap::new());
+                return;
+            }
+            Node::Bool(false) => {
+                let mut not = IndexMap::new();
+                not.insert("not".to_string(), Node::Object(IndexMap::new()));
+                *node = Node::Object(not);
+                return;
+            }
+            _ => {}
+        }
+        let Some(map) = node.as_object_mut() else {
+            return;
+        };
+
+        // A reference with siblings is wrapped, siblings of `$ref` are ignored in 3.0
+        if let Some(reference) = map.get("$ref").cloned() {
+            if map.len() == 1 {
+                return;
+            }
+            map.shift_remove("$ref");
+            let mut wrapped = IndexMap::new();
+            wrapped.insert("$ref".to_string(), reference);
+            map.shift_insert(
+                0,
+                "allOf".to_string(),
+                Node::Array(vec![Node::Object(wrapped)]),
+            );
+        }
+
+        self.schema_type(map, pointer);
+        self.schema_keywords(map, pointer);
+
+        // Nested schemas
+        if let Some(properties) = map.get_mut("properties").and_then(Node::as_object_mut) {
+            let pointer = child(pointer, "properties");
+            for (name, property) in properties {
+                self.schema(property, &child(&pointer, name));
+            }
+        }
+        if let Some(items) = map.get_mut("items") {
+            self.schema(items, &child(pointer, "items"));
+        }
+        if let Some(additional) = map.get_mut("additionalProperties")
+            && matches!(additional, Node::Object(_))
+        {
+            self.schema(additional, &child(pointer, "additionalProperties"));
+        }
+        if let Some(not) = map.get_mut("not") {
+            self.schema(not, &child(pointer, "not"));
+        }
+        for keyword in ["allOf", "oneOf", "anyOf"] {
+            if let Some(Node::Array(items)) = map.get_mut(keyword) {
+                let pointer = child(pointer, keyword);
+                for (index, item) in items.iter_mut().enumerate() {
+                    self.schema(item, &child(&pointer, &index.to_string()));
+                }
+            }
+        }
+    }
+
+    /// Converts `type` arrays and `null` alternatives into `nullable`.
+    fn schema_type(&mut self, map: &mut IndexMap<String, Node>, pointer: &str) {
+        let mut nullable = false;
+
+        if let Some(index) = map.get_index_of("type") {
+            match &map[index] {
+                Node::Array(types) => {
+                    let (nulls, others): (Vec<_>, Vec<_>) =
+                        types.iter().partition(|t| t.as_str() == Some("null"));
+                    nullable = !nulls.is_empty();
+                    match others.as_slice() {
+                        [single] => map[index] = (*single).clone(),
+                        [] => {
+                            map.shift_remove_index(index);
+                        }
+                        _ => {
+                            self.warn(
+                                &child(pointer, "type"),
+                                "multiple types cannot be represented in OpenAPI 3.0, type removed",
+                            );
+                            map.shift_remove_index(index);
+                        }
+                    }
+                }
+                Node::String(schema_type) if schema_type == "null" => {
+                    self.warn(
+                        &child(pointer, "type"),
+                        "the `null` type cannot be represented in OpenAPI 3.0, replaced by `nullable`",
+                    );
+                    nullable = true;
+                    map.shift_remove_index(index);
+                }
+                _ => {}
+            }
+        }
+
+        // `Option<T>` of a component is a `oneOf` with a `null` type alternative
+        for keyword in ["oneOf", "anyOf"] {
+            if let Some(Node::Array(items)) = map.get_mut(keyword) {
+                let before = items.len();
+                items.retain(|item| !is_null_schema(item));
+                nullable |= items.len() != before;
+            }
+        }
+
+        if nullable {
+            let index = map
+                .get_index_of("type")
+                .map_or(map.len(), |index| index + 1);
+            map.shift_insert(index, "nullable".to_string(), Node::from(true));
+        }
+    }
+
+    /// Rewrites the JSON Schema keywords without OpenAPI 3.0 equivalent.
+    fn schema_keywords(&mut self, map: &mut IndexMap<String, Node>, pointer: &str) {
+        if let Some(Node::Array(examples)) = map.get("examples").cloned() {
+            map.shift_remove("examples");
+            if examples.len() > 1 {
+                self.warn(
+                    &child(pointer, "examples"),
+                    "OpenAPI 3.0 schemas have a single `example`, only the first one is kept",
+                );
+            }
+            if let Some(example) = examples.into_iter().next() {
+                map.entry("example".to_string()).or_insert(example);
+            }
+        }
+
+        if let Some(index) = map.get_index_of("const") {
+            let value = map[index].clone();
+            map.shift_remove_index(index);
+            map.shift_insert(index, "enum".to_string(), Node::Array(vec![value]));
+        }
+
+        let encoded = map.shift_remove("contentEncoding").is_some();
+        let media_type = map.shift_remove("contentMediaType").is_some();
+        if encoded || media_type {
+            map.entry("format".to_string())
+                .or_insert_with(|| Node::from("binary"));
+        }
+
+        // An exclusive bound is kept only when it is stricter than the inclusive bound
+        for (exclusive, bound, stricter) in [
+            (
+                "exclusiveMinimum",
+                "minimum",
+                f64::ge as fn(&f64, &f64) -> bool,
+            ),
+            ("exclusiveMaximum", "maximum", f64::le),
+        ] {
+            let Some(index) = map.get_index_of(exclusive) else {
+                continue;
+            };
+            let Node::Number(value) = &map[index] else {
+                continue;
+            };
+            let value = value.clone();
+            let inclusive = match map.get(bound) {
+                Some(Node::Number(inclusive)) => inclusive.as_f64(),
+                _ => None,
+            };
+            match (value.as_f64(), inclusive) {
+                (Some(value), Some(inclusive)) if !stricter(&value, &inclusive) => {
+                    map.shift_remove_index(index);
+                }
+                _ => {
+                    map[index] = Node::from(true);
+                    map.insert(bound.to_string(), Node::Number(value));
+                }
+            }
+        }
+
+        if let Some(Node::Bool(false)) = map.get("items") {
+            self.remove_unsupported(map, pointer, "items");
+        }
+        self.remove_unsupported(map, pointer, "prefixItems");
+        for keyword in UNSUPPORTED_KEYWORDS {
+            self.remove_unsupported(map, pointer, keyword);
+        }
+    }
+}
+
+/// Strips the siblings of a reference object, returns `true` if the object is a reference.
+fn reference_only(map: &mut IndexMap<String, Node>) -> bool {
+    if !map.contains_key("$ref") {
+        return false;
+    }
+    map.retain(|key, _| key == "$ref");
+    true
+}
+
+fn is_null_schema(node: &Node) -> bool {
+    matches!(node, Node::Object(map)
+        if map.len() == 1 && map.get("type").and_then(Node::as_str) == Some("null"))
+}
+
+/// Appends a segment to a JSON Pointer.
+fn child(pointer: &str, segment: &str) -> String {
+    format!(
+        "{pointer}/{}",
+        segment.replace('~', "~0").replace('/', "~1")
+    )
+}
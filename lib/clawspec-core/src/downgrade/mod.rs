@@ -0,0 +1,398 @@
+//! Conversion of the collected OpenAPI 3.1 specification to OpenAPI 3.0.3.
+//!
+//! Clawspec collects OpenAPI 3.1 specifications, but some gateways and code generators
+//! still only accept OpenAPI 3.0. [`downgrade_to_3_0`] rewrites a specification:
+//!
+//! | OpenAPI 3.1 | OpenAPI 3.0.3 |
+//! |-------------|---------------|
+//! | `type: [T, "null"]`, `oneOf: [{type: "null"}, ...]` | `type: T`, `nullable: true` |
+//! | `examples: [...]` in schemas | `example` (the first one) |
+//! | `const: v` | `enum: [v]` |
+//! | `contentEncoding`, `contentMediaType` | `format: binary` |
+//! | `exclusiveMaximum: n` | `maximum: n`, `exclusiveMaximum: true`, unless `maximum` is stricter |
+//! | `$ref` with sibling keywords | `allOf: [{$ref}]` with the siblings |
+//!
+//! Constructs that cannot be represented (multiple types, `prefixItems`, `webhooks`,
+//! `mutualTLS` security schemes and their requirements, ...) are removed and reported as
+//! [`DowngradeWarning`]s.
+//!
+//! # Example
+//!
+//! ```rust
+//! use clawspec_core::OpenApi;
+//! use clawspec_core::downgrade::downgrade_to_3_0;
+//!
+//! # fn example() -> Result<(), serde_json::Error> {
+//! let openapi = OpenApi::builder().build();
+//!
+//! let downgraded = downgrade_to_3_0(&openapi)?;
+//! for warning in downgraded.warnings() {
+//!     eprintln!("{warning}");
+//! }
+//! let json = serde_json::to_string_pretty(&downgraded)?;
+//! assert!(json.contains(r#""openapi": "3.0.3""#));
+//! # Ok(())
+//! # }
+//! # example().unwrap();
+//! ```
+//!
+//! With a [`TestClient`](crate::test_client::TestClient), use
+//! [`write_openapi_as`](crate::test_client::TestClient::write_openapi_as) with
+//! [`SpecVersion::V3_0`].
+
+mod convert;
+mod node;
+
+use serde::{Serialize, Serializer};
+use serde_json::{Map, Value};
+use utoipa::openapi::OpenApi;
+
+use self::convert::Downgrader;
+use self::node::Node;
+
+/// The OpenAPI version of a written specification.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SpecVersion {
+    /// OpenAPI 3.1, the version collected by clawspec.
+    #[default]
+    V3_1,
+    /// OpenAPI 3.0.3, see [`downgrade_to_3_0`].
+    V3_0,
+}
+
+/// A construct of the specification that could not be represented in OpenAPI 3.0.
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+#[display("{pointer}: {message}")]
+pub struct DowngradeWarning {
+    /// The JSON Pointer of the construct in the original specification.
+    pub pointer: String,
+    /// What was changed or removed.
+    pub message: String,
+}
+
+/// An OpenAPI 3.0.3 specification, with the warnings of the conversion.
+///
+/// Serializes to the specification document, in the usual OpenAPI key order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenApi30 {
+    document: Node,
+    warnings: Vec<DowngradeWarning>,
+}
+
+impl OpenApi30 {
+    /// Returns the constructs that could not be represented in OpenAPI 3.0.
+    pub fn warnings(&self) -> &[DowngradeWarning] {
+        &self.warnings
+    }
+
+    /// Returns the specification as a JSON value.
+    ///
+    /// Note that `serde_json::Value` does not keep the order of the keys.
+    pub fn to_value(&self) -> Value {
+        to_value(&self.document)
+    }
+}
+
+impl Serialize for OpenApi30 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.document.serialize(serializer)
+    }
+}
+
+/// Converts an OpenAPI 3.1 specification to OpenAPI 3.0.3.
+///
+/// See the [module documentation](self) for the conversion rules.
+///
+/// # Errors
+///
+/// Returns an error if the specification cannot be serialized.
+pub fn downgrade_to_3_0(openapi: &OpenApi) -> Result<OpenApi30, serde_json::Error> {
+    let mut document: Node = serde_json::from_str(&serde_json::to_string(openapi)?)?;
+
+    let mut downgrader = Downgrader::default();
+    downgrader.document(&mut document);
+
+    Ok(OpenApi30 {
+        document,
+        warnings: downgrader.warnings,
+    })
+}
+
+fn to_value(node: &Node) -> Value {
+    match node {
+        Node::Null => Value::Null,
+        Node::Bool(value) => Value::Bool(*value),
+        Node::Number(value) => Value::Number(value.clone()),
+        Node::String(value) => Value::String(value.clone()),
+        Node::Array(items) => Value::Array(items.iter().map(to_value).collect()),
+        Node::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), to_value(value)))
+                .collect::<Map<_, _>>(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn downgrade(spec: Value) -> OpenApi30 {
+        let mut document: Node = serde_json::from_value(spec).expect("valid document");
+        let mut downgrader = Downgrader::default();
+        downgrader.document(&mut document);
+        OpenApi30 {
+            document,
+            warnings: downgrader.warnings,
+        }
+    }
+
+    fn schema_spec(schema: Value) -> Value {
+        json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {},
+            "components": {"schemas": {"Test": schema}}
+        })
+    }
+
+    fn downgraded_schema(schema: Value) -> (Value, Vec<String>) {
+        let result = downgrade(schema_spec(schema));
+        let warnings = result.warnings().iter().map(ToString::to_string).collect();
+        (
+            result.to_value()["components"]["schemas"]["Test"].clone(),
+            warnings,
+        )
+    }
+
+    #[test]
+    fn should_keep_the_document_key_order() {
+        let openapi = OpenApi::builder()
+            .info(utoipa::openapi::Info::new("Test API", "1.0.0"))
+            .build();
+
+        let downgraded = downgrade_to_3_0(&openapi).expect("should downgrade");
+
+        let json = serde_json::to_string(&downgraded).expect("should serialize");
+        insta::assert_snapshot!(json, @r#"
+        {"openapi":"3.0.3","info":{"title":"Test API","version":"1.0.0"},"paths":{}}
+        "#);
+        assert!(downgraded.warnings().is_empty());
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn should_serialize_to_yaml() {
+        use crate::ToYaml;
+
+        let result = downgrade(schema_spec(
+            json!({"type": ["integer", "null"], "minimum": 1}),
+        ));
+
+        let yaml = result.to_yaml().expect("should serialize to YAML");
+        insta::assert_snapshot!(yaml, @r#"
+        components:
+          schemas:
+            Test:
+              minimum: 1
+              type: integer
+              nullable: true
+        info:
+          title: Test
+          version: "1.0"
+        openapi: 3.0.3
+        paths: {}
+        "#);
+    }
+
+    #[test]
+    fn should_convert_type_arrays_to_nullable() {
+        let (schema, warnings) =
+            downgraded_schema(json!({"type": ["string", "null"], "format": "date-time"}));
+
+        assert_eq!(
+            schema,
+            json!({"type": "string", "nullable": true, "format": "date-time"})
+        );
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn should_convert_null_alternatives_to_nullable() {
+        let (schema, _) = downgraded_schema(json!({
+            "oneOf": [{"type": "null"}, {"$ref": "#/components/schemas/User"}]
+        }));
+
+        assert_eq!(
+            schema,
+            json!({"oneOf": [{"$ref": "#/components/schemas/User"}], "nullable": true})
+        );
+    }
+
+    #[test]
+    fn should_warn_on_multiple_types() {
+        let (schema, warnings) = downgraded_schema(json!({"type": ["string", "integer"]}));
+
+        assert_eq!(schema, json!({}));
+        assert_eq!(
+            warnings,
+            vec![
+                "/components/schemas/Test/type: multiple types cannot be represented in OpenAPI 3.0, type removed"
+            ]
+        );
+    }
+
+    #[test]
+    fn should_convert_examples_const_and_content_encoding() {
+        let (schema, warnings) = downgraded_schema(json!({
+            "type": "object",
+            "properties": {
+                "kind": {"type": "string", "const": "user"},
+                "avatar": {"type": "string", "contentEncoding": "base64", "contentMediaType": "image/png"},
+                "age": {"type": "integer", "exclusiveMinimum": 0, "examples": [42, 43]}
+            }
+        }));
+
+        assert_eq!(
+            schema,
+            json!({
+                "type": "object",
+                "properties": {
+                    "kind": {"type": "string", "enum": ["user"]},
+                    "avatar": {"type": "string", "format": "binary"},
+                    "age": {"type": "integer", "exclusiveMinimum": true, "minimum": 0, "example": 42}
+                }
+            })
+        );
+        assert_eq!(
+            warnings,
+            vec![
+                "/components/schemas/Test/properties/age/examples: OpenAPI 3.0 schemas have a single `example`, only the first one is kept"
+            ]
+        );
+    }
+
+    #[test]
+    fn should_keep_the_stricter_bound() {
+        let (schema, _) = downgraded_schema(json!({
+            "type": "object",
+            "properties": {
+                "inclusive": {"type": "integer", "minimum": 10, "exclusiveMinimum": 5},
+                "exclusive": {"type": "integer", "minimum": 10, "exclusiveMinimum": 10},
+                "maximum": {"type": "number", "exclusiveMaximum": 1.5, "maximum": 1}
+            }
+        }));
+
+        assert_eq!(
+            schema,
+            json!({
+                "type": "object",
+                "properties": {
+                    "inclusive": {"type": "integer", "minimum": 10},
+                    "exclusive": {"type": "integer", "minimum": 10, "exclusiveMinimum": true},
+                    "maximum": {"type": "number", "maximum": 1}
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn should_wrap_references_with_siblings() {
+        let (schema, _) = downgraded_schema(json!({
+            "$ref": "#/components/schemas/User",
+            "description": "The owner"
+        }));
+
+        assert_eq!(
+            schema,
+            json!({"allOf": [{"$ref": "#/components/schemas/User"}], "description": "The owner"})
+        );
+    }
+
+    #[test]
+    fn should_downgrade_operation_schemas_and_remove_unsupported_constructs() {
+        let result = downgrade(json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Test", "version": "1.0", "license": {"name": "MIT", "identifier": "MIT"}},
+            "paths": {
+                "/users/{id}": {
+                    "get": {
+                        "parameters": [
+                            {"name": "id", "in": "path", "required": true, "schema": {"type": ["integer", "null"]}}
+                        ],
+                        "responses": {
+                            "200": {
+                                "description": "ok",
+                                "content": {"application/json": {"schema": {"type": "array", "prefixItems": [{"type": "string"}]}}}
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "securitySchemes": {"mtls": {"type": "mutualTLS"}, "bearer": {"type": "http", "scheme": "bearer"}}
+            },
+            "webhooks": {}
+        }));
+
+        let value = result.to_value();
+        assert_eq!(value["openapi"], "3.0.3");
+        assert_eq!(
+            value["paths"]["/users/{id}"]["get"]["parameters"][0]["schema"],
+            json!({"type": "integer", "nullable": true})
+        );
+        assert_eq!(
+            value["components"]["securitySchemes"],
+            json!({"bearer": {"type": "http", "scheme": "bearer"}})
+        );
+        let warnings: Vec<_> = result.warnings().iter().map(ToString::to_string).collect();
+        insta::assert_debug_snapshot!(warnings, @r#"
+        [
+            "/webhooks: `webhooks` is not supported by OpenAPI 3.0, removed",
+            "/info/license/identifier: `identifier` is not supported by OpenAPI 3.0, removed",
+            "/paths/~1users~1{id}/get/responses/200/content/application~1json/schema/prefixItems: `prefixItems` is not supported by OpenAPI 3.0, removed",
+            "/components/securitySchemes/mtls: `mutualTLS` security schemes are not supported by OpenAPI 3.0, removed",
+        ]
+        "#);
+    }
+
+    #[test]
+    fn should_remove_requirements_of_mutual_tls_schemes() {
+        let result = downgrade(json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {
+                "/users": {
+                    "get": {
+                        "security": [{"mutualTLS": []}, {"bearer": [], "mutualTLS": []}, {}],
+                        "responses": {"200": {"description": "ok"}}
+                    },
+                    "post": {
+                        "security": [{"mutualTLS": []}],
+                        "responses": {"201": {"description": "created"}}
+                    }
+                }
+            },
+            "components": {
+                "securitySchemes": {"mutualTLS": {"type": "mutualTLS"}, "bearer": {"type": "http", "scheme": "bearer"}}
+            },
+            "security": [{"mutualTLS": []}]
+        }));
+
+        let value = result.to_value();
+        insta::assert_snapshot!(value["security"], @"[]");
+        insta::assert_snapshot!(value["paths"]["/users"]["get"]["security"], @r#"[{"bearer":[]},{}]"#);
+        insta::assert_snapshot!(value["paths"]["/users"]["post"]["security"], @"[]");
+        let warnings: Vec<_> = result.warnings().iter().map(ToString::to_string).collect();
+        insta::assert_debug_snapshot!(warnings, @r#"
+        [
+            "/components/securitySchemes/mutualTLS: `mutualTLS` security schemes are not supported by OpenAPI 3.0, removed",
+            "/security/0/mutualTLS: requirement of a removed `mutualTLS` security scheme, removed",
+            "/paths/~1users/get/security/0/mutualTLS: requirement of a removed `mutualTLS` security scheme, removed",
+            "/paths/~1users/get/security/1/mutualTLS: requirement of a removed `mutualTLS` security scheme, removed",
+            "/paths/~1users/post/security/0/mutualTLS: requirement of a removed `mutualTLS` security scheme, removed",
+        ]
+        "#);
+    }
+}
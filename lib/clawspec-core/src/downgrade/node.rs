@@ -0,0 +1,46 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+/// A JSON value keeping the order of the object keys.
+///
+/// `serde_json::Value` sorts object keys, which would scramble the conventional order of an
+/// OpenAPI document (`openapi`, `info`, `paths`, ...). The specification is serialized
+/// once, then rewritten as a tree of nodes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum Node {
+    Null,
+    Bool(bool),
+    Number(serde_json::Number),
+    String(String),
+    Array(Vec<Node>),
+    Object(IndexMap<String, Node>),
+}
+
+impl Node {
+    pub(crate) fn as_object_mut(&mut self) -> Option<&mut IndexMap<String, Node>> {
+        match self {
+            Self::Object(map) => Some(map),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl From<&str> for Node {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<bool> for Node {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
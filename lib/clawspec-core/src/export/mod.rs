@@ -0,0 +1,37 @@
+//! Exports of the recorded calls to third-party formats.
+//!
+//! The calls made through an [`ApiClient`](crate::ApiClient) are not only turned into an
+//! OpenAPI specification: they can also be exported as contract-faithful stubs for the
+//! tools used by consumer teams.
+//!
+//! # Formats
+//!
+//! - [`WireMockMappings`] - [WireMock](https://wiremock.org/) JSON stub mappings
+//! - [`Pact`] - [Pact](https://docs.pact.io/) consumer-driven contract files (v3 and v4)
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use clawspec_core::ApiClient;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut client = ApiClient::builder().with_port(8080).build()?;
+//! client.get("/users")?.await?.as_empty().await?;
+//!
+//! let mappings = client.collected_wiremock_mappings().await;
+//! std::fs::write("mappings.json", serde_json::to_string_pretty(&mappings)?)?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub(crate) mod pact;
+pub(crate) mod wiremock;
+
+pub use self::pact::{
+    Pact, PactInteraction, PactMatcher, PactRequest, PactResponse, PactSpecification,
+};
+
+pub use self::wiremock::{
+    WireMockBodyPattern, WireMockHeaderValue, WireMockMapping, WireMockMappings, WireMockMatcher,
+    WireMockRequest, WireMockResponse,
+};
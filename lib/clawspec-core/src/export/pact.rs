@@ -0,0 +1,498 @@
+use std::collections::BTreeMap;
+
+use indexmap::IndexMap;
+use serde::{Serialize, Serializer};
+use serde_json::{Map, Value, json};
+use utoipa::openapi::schema::{ArrayItems, SchemaType, Type};
+use utoipa::openapi::{RefOr, Schema};
+
+/// Maximum depth when walking schemas, protecting against recursive types.
+const MAX_SCHEMA_DEPTH: usize = 16;
+
+/// The version of the Pact specification used to write a [`Pact`] file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PactSpecification {
+    /// Pact specification v3.0.0
+    #[default]
+    V3,
+    /// Pact specification v4.0
+    V4,
+}
+
+/// A consumer-driven contract in the [Pact](https://docs.pact.io/) format.
+///
+/// The pact contains one interaction per recorded call. Request and response bodies are
+/// recorded as examples, with matching rules derived from their `ToSchema` schemas so
+/// that the provider verification checks types rather than literal values.
+///
+/// Serializing a `Pact` produces the JSON pact file for the selected [`PactSpecification`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use clawspec_core::ApiClient;
+/// use clawspec_core::export::PactSpecification;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut client = ApiClient::builder().with_port(1234).build()?;
+///
+/// client
+///     .get("/users/42")?
+///     .with_provider_state("user 42 exists")
+///     .await?
+///     .as_empty()
+///     .await?;
+///
+/// let pact = client
+///     .collected_pact("frontend", "user-service")
+///     .await
+///     .with_specification(PactSpecification::V4);
+/// std::fs::write("pacts/frontend-user-service.json", serde_json::to_string_pretty(&pact)?)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pact {
+    /// The consumer name.
+    pub consumer: String,
+    /// The provider name.
+    pub provider: String,
+    /// The specification version used for serialization.
+    pub specification: PactSpecification,
+    /// The recorded interactions.
+    pub interactions: Vec<PactInteraction>,
+}
+
+impl Pact {
+    /// Creates an empty pact between a consumer and a provider.
+    pub fn new(consumer: impl Into<String>, provider: impl Into<String>) -> Self {
+        Self {
+            consumer: consumer.into(),
+            provider: provider.into(),
+            specification: PactSpecification::default(),
+            interactions: vec![],
+        }
+    }
+
+    /// Sets the specification version used for serialization.
+    #[must_use]
+    pub fn with_specification(mut self, specification: PactSpecification) -> Self {
+        self.specification = specification;
+        self
+    }
+
+    /// Returns the pact file content as JSON.
+    pub fn to_json(&self) -> Value {
+        let spec = self.specification;
+        let interactions: Vec<_> = self
+            .interactions
+            .iter()
+            .map(|interaction| interaction.to_json(spec))
+            .collect();
+        let version = match spec {
+            PactSpecification::V3 => "3.0.0",
+            PactSpecification::V4 => "4.0",
+        };
+
+        json!({
+            "consumer": { "name": self.consumer },
+            "provider": { "name": self.provider },
+            "interactions": interactions,
+            "metadata": {
+                "pactSpecification": { "version": version }
+            }
+        })
+    }
+}
+
+impl Serialize for Pact {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_json().serialize(serializer)
+    }
+}
+
+/// A recorded request/response pair of a [`Pact`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PactInteraction {
+    /// A unique description of the interaction.
+    pub description: String,
+    /// The provider states required by the interaction.
+    pub provider_states: Vec<String>,
+    /// The expected request.
+    pub request: PactRequest,
+    /// The expected response.
+    pub response: PactResponse,
+}
+
+impl PactInteraction {
+    fn to_json(&self, spec: PactSpecification) -> Value {
+        let mut result = Map::new();
+        if spec == PactSpecification::V4 {
+            result.insert("type".to_string(), json!("Synchronous/HTTP"));
+        }
+        result.insert("description".to_string(), json!(self.description));
+        if !self.provider_states.is_empty() {
+            let states: Vec<_> = self
+                .provider_states
+                .iter()
+                .map(|name| json!({ "name": name }))
+                .collect();
+            result.insert("providerStates".to_string(), Value::Array(states));
+        }
+
+        let mut request = Map::new();
+        request.insert("method".to_string(), json!(self.request.method));
+        request.insert("path".to_string(), json!(self.request.path));
+        if !self.request.query.is_empty() {
+            request.insert("query".to_string(), json!(self.request.query));
+        }
+        insert_message(
+            &mut request,
+            spec,
+            &self.request.headers,
+            self.request.body.as_ref(),
+            &self.request.matching_rules,
+        );
+
+        let mut response = Map::new();
+        response.insert("status".to_string(), json!(self.response.status));
+        insert_message(
+            &mut response,
+            spec,
+            &self.response.headers,
+            self.response.body.as_ref(),
+            &self.response.matching_rules,
+        );
+
+        result.insert("request".to_string(), Value::Object(request));
+        result.insert("response".to_string(), Value::Object(response));
+        Value::Object(result)
+    }
+}
+
+/// The expected request of a [`PactInteraction`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PactRequest {
+    /// The HTTP method.
+    pub method: String,
+    /// The concrete request path.
+    pub path: String,
+    /// The query parameters.
+    pub query: IndexMap<String, Vec<String>>,
+    /// The request headers.
+    pub headers: IndexMap<String, String>,
+    /// The request body.
+    pub body: Option<Value>,
+    /// Body matching rules, keyed by JSON path.
+    pub matching_rules: IndexMap<String, PactMatcher>,
+}
+
+/// The expected response of a [`PactInteraction`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PactResponse {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The response headers.
+    pub headers: IndexMap<String, String>,
+    /// The response body.
+    pub body: Option<Value>,
+    /// Body matching rules, keyed by JSON path.
+    pub matching_rules: IndexMap<String, PactMatcher>,
+}
+
+/// A Pact body matcher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PactMatcher {
+    /// The value must have the same type as the example.
+    Type,
+    /// The value must be an integer.
+    Integer,
+    /// The value must be a number.
+    Number,
+}
+
+impl PactMatcher {
+    fn to_json(self) -> Value {
+        let name = match self {
+            Self::Type => "type",
+            Self::Integer => "integer",
+            Self::Number => "number",
+        };
+        json!({ "match": name })
+    }
+}
+
+fn insert_message(
+    message: &mut Map<String, Value>,
+    spec: PactSpecification,
+    headers: &IndexMap<String, String>,
+    body: Option<&Value>,
+    matching_rules: &IndexMap<String, PactMatcher>,
+) {
+    let content_type = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-type"))
+        .map_or("application/json", |(_, value)| value.as_str());
+
+    if !headers.is_empty() {
+        let headers = match spec {
+            PactSpecification::V3 => json!(headers),
+            PactSpecification::V4 => headers
+                .iter()
+                .map(|(name, value)| (name.clone(), json!([value])))
+                .collect::<Map<_, _>>()
+                .into(),
+        };
+        message.insert("headers".to_string(), headers);
+    }
+
+    if let Some(body) = body {
+        let body = match spec {
+            PactSpecification::V3 => body.clone(),
+            PactSpecification::V4 => json!({
+                "content": body,
+                "contentType": content_type,
+                "encoded": false
+            }),
+        };
+        message.insert("body".to_string(), body);
+    }
+
+    if !matching_rules.is_empty() {
+        let rules: Map<_, _> = matching_rules
+            .iter()
+            .map(|(path, matcher)| {
+                let rule = json!({ "matchers": [matcher.to_json()], "combine": "AND" });
+                (path.clone(), rule)
+            })
+            .collect();
+        message.insert("matchingRules".to_string(), json!({ "body": rules }));
+    }
+}
+
+/// Derives body matching rules from a schema.
+///
+/// Leaf values get a type matcher (or an integer/number matcher), arrays get a type
+/// matcher and their items are described with `[*]`. Component references are resolved
+/// against `schemas`.
+pub(crate) fn matching_rules(
+    schema: &RefOr<Schema>,
+    schemas: &BTreeMap<String, RefOr<Schema>>,
+) -> IndexMap<String, PactMatcher> {
+    let mut rules = IndexMap::new();
+    collect_rules(schema, "$", schemas, &mut rules, 0);
+    rules
+}
+
+fn collect_rules(
+    schema: &RefOr<Schema>,
+    path: &str,
+    schemas: &BTreeMap<String, RefOr<Schema>>,
+    rules: &mut IndexMap<String, PactMatcher>,
+    depth: usize,
+) {
+    if depth > MAX_SCHEMA_DEPTH {
+        return;
+    }
+
+    let schema = match schema {
+        RefOr::T(schema) => schema,
+        RefOr::Ref(reference) => {
+            let name = reference
+                .ref_location
+                .trim_start_matches("#/components/schemas/");
+            if let Some(resolved) = schemas.get(name) {
+                collect_rules(resolved, path, schemas, rules, depth + 1);
+            }
+            return;
+        }
+    };
+
+    match schema {
+        Schema::Object(object) if !object.properties.is_empty() => {
+            for (name, property) in &object.properties {
+                let child = child_path(path, name);
+                collect_rules(property, &child, schemas, rules, depth + 1);
+            }
+        }
+        Schema::Object(object) => {
+            let matcher = match &object.schema_type {
+                SchemaType::Type(Type::Integer) => PactMatcher::Integer,
+                SchemaType::Type(Type::Number) => PactMatcher::Number,
+                _ => PactMatcher::Type,
+            };
+            rules.insert(path.to_string(), matcher);
+        }
+        Schema::Array(array) => {
+            rules.insert(path.to_string(), PactMatcher::Type);
+            if let ArrayItems::RefOrSchema(items) = &array.items {
+                collect_rules(items, &format!("{path}[*]"), schemas, rules, depth + 1);
+            }
+        }
+        Schema::AllOf(all_of) => {
+            for item in &all_of.items {
+                collect_rules(item, path, schemas, rules, depth + 1);
+            }
+        }
+        _ => {
+            rules.insert(path.to_string(), PactMatcher::Type);
+        }
+    }
+}
+
+fn child_path(path: &str, name: &str) -> String {
+    let is_identifier = name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if is_identifier {
+        format!("{path}.{name}")
+    } else {
+        format!("{path}['{}']", name.replace('\'', "\\'"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use utoipa::{PartialSchema, ToSchema};
+
+    #[derive(Serialize, Deserialize, ToSchema)]
+    struct Address {
+        city: String,
+    }
+
+    #[derive(Serialize, Deserialize, ToSchema)]
+    #[serde(rename_all = "kebab-case")]
+    struct User {
+        id: u64,
+        score: f64,
+        display_name: String,
+        tags: Vec<String>,
+        address: Address,
+    }
+
+    fn create_test_pact() -> Pact {
+        let mut pact = Pact::new("frontend", "user-service");
+        let mut response = PactResponse {
+            status: 200,
+            body: Some(json!({"id": 1})),
+            ..PactResponse::default()
+        };
+        response
+            .headers
+            .insert("Content-Type".to_string(), "application/json".to_string());
+        response
+            .matching_rules
+            .insert("$.id".to_string(), PactMatcher::Integer);
+        pact.interactions.push(PactInteraction {
+            description: "Retrieve user by ID".to_string(),
+            provider_states: vec!["user 1 exists".to_string()],
+            request: PactRequest {
+                method: "GET".to_string(),
+                path: "/users/1".to_string(),
+                ..PactRequest::default()
+            },
+            response,
+        });
+        pact
+    }
+
+    #[test]
+    fn should_derive_matching_rules_from_schema() {
+        let mut schemas = BTreeMap::new();
+        schemas.insert("Address".to_string(), Address::schema());
+
+        let rules = matching_rules(&User::schema(), &schemas);
+
+        let rules: Vec<_> = rules
+            .iter()
+            .map(|(path, matcher)| format!("{path}: {matcher:?}"))
+            .collect();
+        insta::assert_debug_snapshot!(rules, @r#"
+        [
+            "$.address.city: Type",
+            "$['display-name']: Type",
+            "$.id: Integer",
+            "$.score: Number",
+            "$.tags: Type",
+            "$.tags[*]: Type",
+        ]
+        "#);
+    }
+
+    #[test]
+    fn should_serialize_v3_pact() {
+        let pact = create_test_pact();
+
+        let json = serde_json::to_string_pretty(&pact).expect("should serialize");
+
+        insta::assert_snapshot!(json, @r#"
+        {
+          "consumer": {
+            "name": "frontend"
+          },
+          "interactions": [
+            {
+              "description": "Retrieve user by ID",
+              "providerStates": [
+                {
+                  "name": "user 1 exists"
+                }
+              ],
+              "request": {
+                "method": "GET",
+                "path": "/users/1"
+              },
+              "response": {
+                "body": {
+                  "id": 1
+                },
+                "headers": {
+                  "Content-Type": "application/json"
+                },
+                "matchingRules": {
+                  "body": {
+                    "$.id": {
+                      "combine": "AND",
+                      "matchers": [
+                        {
+                          "match": "integer"
+                        }
+                      ]
+                    }
+                  }
+                },
+                "status": 200
+              }
+            }
+          ],
+          "metadata": {
+            "pactSpecification": {
+              "version": "3.0.0"
+            }
+          },
+          "provider": {
+            "name": "user-service"
+          }
+        }
+        "#);
+    }
+
+    #[test]
+    fn should_serialize_v4_pact() {
+        let pact = create_test_pact().with_specification(PactSpecification::V4);
+
+        let json = pact.to_json();
+
+        let interaction = &json["interactions"][0];
+        assert_eq!(interaction["type"], "Synchronous/HTTP");
+        assert_eq!(
+            interaction["response"]["body"],
+            json!({"content": {"id": 1}, "contentType": "application/json", "encoded": false})
+        );
+        assert_eq!(
+            interaction["response"]["headers"]["Content-Type"],
+            json!(["application/json"])
+        );
+        assert_eq!(json["metadata"]["pactSpecification"]["version"], "4.0");
+    }
+}
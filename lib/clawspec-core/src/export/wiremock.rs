@@ -0,0 +1,295 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A set of [WireMock](https://wiremock.org/) stub mappings.
+///
+/// Serializes to the format accepted by the WireMock `mappings` directory and by the
+/// `POST /__admin/mappings/import` admin endpoint.
+///
+/// # Example
+///
+/// ```rust
+/// use clawspec_core::export::{WireMockMapping, WireMockMappings, WireMockRequest, WireMockResponse};
+///
+/// let mapping = WireMockMapping::new(
+///     "get-user-200",
+///     WireMockRequest::new("GET", "/users/[^/]+"),
+///     WireMockResponse::new(200),
+/// );
+/// let mappings = WireMockMappings { mappings: vec![mapping] };
+///
+/// let json = serde_json::to_value(&mappings).expect("serializable");
+/// assert_eq!(json["mappings"][0]["request"]["urlPathPattern"], "/users/[^/]+");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WireMockMappings {
+    /// The stub mappings.
+    pub mappings: Vec<WireMockMapping>,
+}
+
+impl WireMockMappings {
+    /// Returns `true` if there is no mapping.
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+
+    /// Returns the number of mappings.
+    pub fn len(&self) -> usize {
+        self.mappings.len()
+    }
+}
+
+/// A single WireMock stub mapping: a request matcher and the response to send.
+///
+/// When the same request was recorded with different responses, the mappings are
+/// chained in a [scenario](https://wiremock.org/docs/stateful-behaviour/), replaying the
+/// responses in the recorded order, the last one being repeated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WireMockMapping {
+    /// The mapping name, derived from the operation ID and the response status.
+    pub name: String,
+    /// The request matcher.
+    pub request: WireMockRequest,
+    /// The stubbed response.
+    pub response: WireMockResponse,
+    /// The scenario of the mapping, for the requests with several responses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scenario_name: Option<String>,
+    /// The scenario state required to match the mapping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_scenario_state: Option<String>,
+    /// The scenario state once the mapping is matched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_scenario_state: Option<String>,
+}
+
+impl WireMockMapping {
+    /// Creates a mapping, outside of any scenario.
+    pub fn new(
+        name: impl Into<String>,
+        request: WireMockRequest,
+        response: WireMockResponse,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            request,
+            response,
+            scenario_name: None,
+            required_scenario_state: None,
+            new_scenario_state: None,
+        }
+    }
+}
+
+/// The request matcher of a WireMock mapping.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WireMockRequest {
+    /// The HTTP method.
+    pub method: String,
+    /// A regular expression matching the URL path, built from the path template.
+    pub url_path_pattern: String,
+    /// Query parameter matchers.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub query_parameters: IndexMap<String, WireMockMatcher>,
+    /// Header matchers.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub headers: IndexMap<String, WireMockMatcher>,
+    /// Request body matchers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub body_patterns: Vec<WireMockBodyPattern>,
+}
+
+impl WireMockRequest {
+    /// Creates a request matcher on the method and a URL path pattern.
+    pub fn new(method: impl Into<String>, url_path_pattern: impl Into<String>) -> Self {
+        Self {
+            method: method.into(),
+            url_path_pattern: url_path_pattern.into(),
+            query_parameters: IndexMap::new(),
+            headers: IndexMap::new(),
+            body_patterns: vec![],
+        }
+    }
+}
+
+/// A WireMock value matcher.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WireMockMatcher {
+    /// The exact expected value.
+    EqualTo(String),
+    /// A regular expression matching the value.
+    Matches(String),
+    /// Whether the value must be absent.
+    Absent(bool),
+}
+
+impl WireMockMatcher {
+    /// Creates a matcher expecting exactly the given value.
+    pub fn equal_to(value: impl Into<String>) -> Self {
+        Self::EqualTo(value.into())
+    }
+
+    /// Creates a matcher expecting a value matching the regular expression.
+    pub fn matches(pattern: impl Into<String>) -> Self {
+        Self::Matches(pattern.into())
+    }
+
+    /// Creates a matcher expecting any non-empty value, e.g. for credentials.
+    pub fn present() -> Self {
+        Self::matches(".+")
+    }
+
+    /// Creates a matcher expecting no value.
+    pub fn absent() -> Self {
+        Self::Absent(true)
+    }
+}
+
+/// A WireMock request body matcher.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WireMockBodyPattern {
+    /// The expected JSON body.
+    pub equal_to_json: Value,
+    /// Whether extra elements in the actual body are ignored.
+    #[serde(default)]
+    pub ignore_extra_elements: bool,
+}
+
+impl WireMockBodyPattern {
+    /// Creates a matcher expecting the given JSON body, ignoring extra elements.
+    pub fn equal_to_json(json: Value) -> Self {
+        Self {
+            equal_to_json: json,
+            ignore_extra_elements: true,
+        }
+    }
+}
+
+/// The stubbed response of a WireMock mapping.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WireMockResponse {
+    /// The HTTP status code.
+    pub status: u16,
+    /// The response headers.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub headers: IndexMap<String, WireMockHeaderValue>,
+    /// A JSON body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_body: Option<Value>,
+    /// A text body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// A binary body, base64 encoded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base64_body: Option<String>,
+}
+
+impl WireMockResponse {
+    /// Creates a response with the given status and no body.
+    pub fn new(status: u16) -> Self {
+        Self {
+            status,
+            headers: IndexMap::new(),
+            json_body: None,
+            body: None,
+            base64_body: None,
+        }
+    }
+}
+
+/// The value of a WireMock response header, a repeated header having several values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum WireMockHeaderValue {
+    /// A single value.
+    Single(String),
+    /// The values of a repeated header, like `Set-Cookie`.
+    Multiple(Vec<String>),
+}
+
+impl WireMockHeaderValue {
+    /// Adds a value of the repeated header.
+    pub fn push(&mut self, value: impl Into<String>) {
+        let value = value.into();
+        match self {
+            Self::Single(first) => *self = Self::Multiple(vec![std::mem::take(first), value]),
+            Self::Multiple(values) => values.push(value),
+        }
+    }
+}
+
+impl From<String> for WireMockHeaderValue {
+    fn from(value: String) -> Self {
+        Self::Single(value)
+    }
+}
+
+impl From<&str> for WireMockHeaderValue {
+    fn from(value: &str) -> Self {
+        Self::Single(value.to_string())
+    }
+}
+
+/// Converts an OpenAPI path template to a WireMock `urlPathPattern`.
+///
+/// Path parameters (`{id}`) match any non-empty segment, literal parts are escaped.
+pub(crate) fn url_path_pattern(template: &str) -> String {
+    let mut pattern = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        pattern.push_str(&regex::escape(&rest[..start]));
+        pattern.push_str("[^/]+");
+        rest = &rest[start + end + 1..];
+    }
+    pattern.push_str(&regex::escape(rest));
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn should_build_url_path_pattern() {
+        assert_eq!(url_path_pattern("/users"), "/users");
+        assert_eq!(url_path_pattern("/users/{id}"), "/users/[^/]+");
+        assert_eq!(
+            url_path_pattern("/files/{name}.json/{version}"),
+            r"/files/[^/]+\.json/[^/]+"
+        );
+    }
+
+    #[test]
+    fn should_serialize_mapping() {
+        let mut request = WireMockRequest::new("POST", "/users");
+        request
+            .query_parameters
+            .insert("dry-run".to_string(), WireMockMatcher::equal_to("true"));
+        request
+            .headers
+            .insert("Authorization".to_string(), WireMockMatcher::present());
+        request
+            .headers
+            .insert("X-Debug".to_string(), WireMockMatcher::absent());
+        request
+            .body_patterns
+            .push(WireMockBodyPattern::equal_to_json(json!({"name": "Alice"})));
+        let mut response = WireMockResponse::new(201);
+        response.json_body = Some(json!({"id": 1}));
+
+        let mapping = WireMockMapping::new("post-users-201", request, response);
+
+        let json = serde_json::to_string(&mapping).expect("should serialize");
+        insta::assert_snapshot!(json, @r#"{"name":"post-users-201","request":{"method":"POST","urlPathPattern":"/users","queryParameters":{"dry-run":{"equalTo":"true"}},"headers":{"Authorization":{"matches":".+"},"X-Debug":{"absent":true}},"bodyPatterns":[{"equalToJson":{"name":"Alice"},"ignoreExtraElements":true}]},"response":{"status":201,"jsonBody":{"id":1}}}"#);
+    }
+}
@@ -504,6 +504,9 @@
 //! - [`ApiClient`] - HTTP client with OpenAPI collection
 //! - [`ApiCall`] - Request builder with parameter support
 //! - [`test_client`] - Test server integration module
+//! - [`export`] - Export of the recorded calls as WireMock stubs and Pact contracts
+//! - [`docs`] - Markdown and HTML API reference generated from the collected specification
+//! - [`downgrade`] - Conversion of the collected specification to OpenAPI 3.0.3
 //! - [`ExpectedStatusCodes`] - Status code validation
 #![cfg_attr(
     feature = "redaction",
@@ -537,6 +540,10 @@
     feature = "yaml",
     doc = "- [`YamlError`] - Error type for YAML serialization"
 )]
+#![cfg_attr(
+    feature = "mock-server",
+    doc = "- [`mock`] - Stub server replaying the recorded examples"
+)]
 //!
 //! ## Re-exports
 //!
@@ -548,8 +555,20 @@ pub mod _tutorial;
 
 mod client;
 
+pub mod docs;
+
+pub mod downgrade;
+
+pub mod export;
+
+#[cfg(feature = "mock-server")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mock-server")))]
+pub mod mock;
+
 pub mod split;
 
+mod operations;
+
 #[cfg(feature = "yaml")]
 #[cfg_attr(docsrs, doc(cfg(feature = "yaml")))]
 mod yaml;
@@ -560,8 +579,10 @@ pub mod test_client;
 pub use self::client::{
     ApiCall, ApiClient, ApiClientBuilder, ApiClientError, ApiKeyLocation, Authentication,
     AuthenticationError, CallBody, CallCookies, CallHeaders, CallPath, CallQuery, CallResult,
-    ExpectedStatusCodes, OAuth2Flow, OAuth2Flows, OAuth2ImplicitFlow, ParamStyle, ParamValue,
-    ParameterValue, RawBody, RawResult, SecureString, SecurityRequirement, SecurityScheme,
+    ExpectedStatusCodes, HmacSigner, OAuth2Flow, OAuth2Flows, OAuth2ImplicitFlow, ParamStyle,
+    ParamValue, ParameterValue, RawBody, RawResult, RequestSigner, ReusableComponent,
+    ReusableComponents, SchemaNamingStrategy, SchemaType, SecureString, SecurityRequirement,
+    SecurityScheme, SigV4Signer, SignableRequest,
 };
 
 // Re-export external types so users don't need to add these crates to their Cargo.toml.
@@ -575,6 +596,7 @@ pub use self::client::{
 //   use http::StatusCode;
 
 /// OpenAPI types re-exported from utoipa for convenience.
+pub use utoipa::openapi::tag::{Tag, TagBuilder};
 pub use utoipa::openapi::{Info, InfoBuilder, OpenApi, Paths, Server, ServerBuilder};
 
 /// The `ToSchema` derive macro for generating OpenAPI schemas.
@@ -591,7 +613,14 @@ pub use self::client::{
 };
 
 #[cfg(feature = "oauth2")]
-pub use self::client::{OAuth2Config, OAuth2ConfigBuilder, OAuth2Error, OAuth2Token};
+pub use self::client::{
+    OAuth2Config, OAuth2ConfigBuilder, OAuth2Error, OAuth2Login, OAuth2LoginFuture,
+    OAuth2LoginHook, OAuth2Token,
+};
+
+#[cfg(feature = "jwt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jwt")))]
+pub use self::client::JwtBuilder;
 
 #[cfg(feature = "yaml")]
 #[cfg_attr(docsrs, doc(cfg(feature = "yaml")))]
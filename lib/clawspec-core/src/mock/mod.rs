@@ -0,0 +1,54 @@
+//! Local stub server replaying recorded examples.
+//!
+//! This module turns an OpenAPI specification produced by your tests back into a running
+//! HTTP server. Each documented operation becomes a stub that answers with the example
+//! recorded for it, so frontend teams can develop against the API without running the
+//! real backend.
+//!
+//! # Matching
+//!
+//! An incoming request is routed to an operation when:
+//!
+//! - the HTTP method matches,
+//! - the path matches the path template (`/users/{id}` matches `/users/42`),
+//! - every *required* query and header parameter declared by the operation is present.
+//!
+//! When several templates match, the one with the most literal segments wins
+//! (`/users/me` is preferred over `/users/{id}`).
+//!
+//! # Response Selection
+//!
+//! The response is chosen among the documented status codes:
+//!
+//! 1. The status requested with a `Prefer: code=<status>` header, if documented
+//! 2. The lowest `2xx` status having an example
+//! 3. The lowest `2xx` status
+//! 4. The lowest documented status
+//!
+//! The body is the recorded example found on the response content (`example`, then `examples`),
+//! falling back to the `examples` of the response schema.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use clawspec_core::ApiClient;
+//! use clawspec_core::mock::MockServer;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut client = ApiClient::builder().with_port(8080).build()?;
+//! // ... run your tests with the client ...
+//!
+//! // Start a stub server from what the tests recorded
+//! let server = client.mock_server().await.start().await?;
+//! println!("Mock server listening on {}", server.base_url());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A server can also be built from any [`OpenApi`](utoipa::openapi::OpenApi) specification
+//! with [`MockServer::from_openapi`].
+
+mod route;
+mod server;
+
+pub use self::server::{MockServer, MockServerHandle};
@@ -0,0 +1,381 @@
+use http::{Method, StatusCode};
+use serde_json::Value;
+use utoipa::openapi::path::{Operation, ParameterIn};
+use utoipa::openapi::{Components, Content, RefOr, Required, Response, Schema};
+
+use crate::client::operation_parameters;
+
+/// A segment of a path template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Param,
+}
+
+/// A stubbed response for a given status code.
+#[derive(Debug, Clone)]
+pub(super) struct MockResponse {
+    pub(super) status: StatusCode,
+    pub(super) content_type: Option<String>,
+    pub(super) example: Option<Value>,
+}
+
+/// A stubbed operation: method, path template, required parameters, and responses.
+#[derive(Debug, Clone)]
+pub(super) struct MockRoute {
+    pub(super) method: Method,
+    pub(super) template: String,
+    segments: Vec<Segment>,
+    required_query: Vec<String>,
+    required_headers: Vec<String>,
+    pub(super) responses: Vec<MockResponse>,
+}
+
+impl MockRoute {
+    pub(super) fn new(
+        method: Method,
+        template: &str,
+        operation: &Operation,
+        components: Option<&Components>,
+    ) -> Self {
+        let segments = split_path(template)
+            .map(|segment| {
+                if segment.contains('{') {
+                    Segment::Param
+                } else {
+                    Segment::Literal(segment.to_string())
+                }
+            })
+            .collect();
+
+        let mut required_query = vec![];
+        let mut required_headers = vec![];
+        for parameter in operation_parameters(operation, components) {
+            if !matches!(parameter.required, Required::True) {
+                continue;
+            }
+            match parameter.parameter_in {
+                ParameterIn::Query => required_query.push(parameter.name.clone()),
+                ParameterIn::Header => required_headers.push(parameter.name.to_ascii_lowercase()),
+                ParameterIn::Path | ParameterIn::Cookie => {}
+            }
+        }
+
+        let mut responses: Vec<_> = operation
+            .responses
+            .responses
+            .iter()
+            .filter_map(|(status, response)| {
+                let status = status.parse::<u16>().ok()?;
+                let status = StatusCode::from_u16(status).ok()?;
+                let response = resolve_response(response, components)?;
+                Some(build_mock_response(status, response, components))
+            })
+            .collect();
+        responses.sort_by_key(|response| response.status);
+
+        Self {
+            method,
+            template: template.to_string(),
+            segments,
+            required_query,
+            required_headers,
+            responses,
+        }
+    }
+
+    /// Returns the number of literal segments if the path matches the template.
+    pub(super) fn match_path(&self, path: &str) -> Option<usize> {
+        let parts: Vec<_> = split_path(path).collect();
+        if parts.len() != self.segments.len() {
+            return None;
+        }
+
+        let mut literals = 0;
+        for (segment, part) in self.segments.iter().zip(parts) {
+            match segment {
+                Segment::Literal(literal) if literal == part => literals += 1,
+                Segment::Literal(_) => return None,
+                Segment::Param if part.is_empty() => return None,
+                Segment::Param => {}
+            }
+        }
+        Some(literals)
+    }
+
+    /// Returns the first required parameter missing from the request, if any.
+    pub(super) fn missing_parameter<'a>(
+        &'a self,
+        query: &[(String, String)],
+        header_names: &[String],
+    ) -> Option<&'a str> {
+        let missing_query = self
+            .required_query
+            .iter()
+            .find(|name| !query.iter().any(|(key, _)| key == *name));
+        let missing_header = self
+            .required_headers
+            .iter()
+            .find(|name| !header_names.contains(name));
+        missing_query.or(missing_header).map(String::as_str)
+    }
+
+    /// Selects the best response, honoring an optionally preferred status.
+    pub(super) fn select_response(&self, preferred: Option<StatusCode>) -> Option<&MockResponse> {
+        if let Some(preferred) = preferred
+            && let Some(response) = self.responses.iter().find(|r| r.status == preferred)
+        {
+            return Some(response);
+        }
+
+        self.responses
+            .iter()
+            .find(|r| r.status.is_success() && r.example.is_some())
+            .or_else(|| self.responses.iter().find(|r| r.status.is_success()))
+            .or_else(|| self.responses.first())
+    }
+
+    /// Stores an example for the given status, creating the response if needed.
+    ///
+    /// Existing examples are kept: the specification takes precedence.
+    pub(super) fn add_example(&mut self, status: StatusCode, content_type: &str, example: Value) {
+        if let Some(response) = self.responses.iter_mut().find(|r| r.status == status) {
+            if response.example.is_none() {
+                response.example = Some(example);
+                if response.content_type.is_none() {
+                    response.content_type = Some(content_type.to_string());
+                }
+            }
+            return;
+        }
+
+        self.responses.push(MockResponse {
+            status,
+            content_type: Some(content_type.to_string()),
+            example: Some(example),
+        });
+        self.responses.sort_by_key(|response| response.status);
+    }
+}
+
+fn split_path(path: &str) -> impl Iterator<Item = &str> {
+    path.trim_matches('/').split('/').filter(|s| !s.is_empty())
+}
+
+fn component_name<'a>(reference: &'a str, kind: &str) -> Option<&'a str> {
+    reference
+        .strip_prefix("#/components/")?
+        .strip_prefix(kind)?
+        .strip_prefix('/')
+}
+
+fn resolve_response<'a>(
+    response: &'a RefOr<Response>,
+    components: Option<&'a Components>,
+) -> Option<&'a Response> {
+    match response {
+        RefOr::T(response) => Some(response),
+        RefOr::Ref(reference) => {
+            let name = component_name(&reference.ref_location, "responses")?;
+            match components?.responses.get(name)? {
+                RefOr::T(response) => Some(response),
+                RefOr::Ref(_) => None,
+            }
+        }
+    }
+}
+
+fn build_mock_response(
+    status: StatusCode,
+    response: &Response,
+    components: Option<&Components>,
+) -> MockResponse {
+    // Prefer JSON content when several representations are documented
+    let content = response
+        .content
+        .iter()
+        .find(|(content_type, _)| content_type.contains("json"))
+        .or_else(|| response.content.first());
+
+    let Some((content_type, content)) = content else {
+        return MockResponse {
+            status,
+            content_type: None,
+            example: None,
+        };
+    };
+
+    MockResponse {
+        status,
+        content_type: Some(content_type.clone()),
+        example: content_example(content, components),
+    }
+}
+
+fn content_example(content: &Content, components: Option<&Components>) -> Option<Value> {
+    if let Some(example) = &content.example {
+        return Some(example.clone());
+    }
+
+    let from_examples = content.examples.values().find_map(|example| match example {
+        RefOr::T(example) => example.value.clone(),
+        RefOr::Ref(_) => None,
+    });
+    if from_examples.is_some() {
+        return from_examples;
+    }
+
+    let schema = match content.schema.as_ref()? {
+        RefOr::T(schema) => schema,
+        RefOr::Ref(reference) => {
+            let name = component_name(&reference.ref_location, "schemas")?;
+            match components?.schemas.get(name)? {
+                RefOr::T(schema) => schema,
+                RefOr::Ref(_) => return None,
+            }
+        }
+    };
+    schema_example(schema)
+}
+
+fn schema_example(schema: &Schema) -> Option<Value> {
+    match schema {
+        Schema::Object(object) => object.examples.first().cloned(),
+        Schema::Array(array) => array.examples.first().cloned(),
+        Schema::OneOf(one_of) => one_of.examples.first().cloned(),
+        Schema::AllOf(all_of) => all_of.examples.first().cloned(),
+        Schema::AnyOf(any_of) => any_of.examples.first().cloned(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use utoipa::openapi::path::{OperationBuilder, ParameterBuilder};
+    use utoipa::openapi::schema::ObjectBuilder;
+    use utoipa::openapi::{ComponentsBuilder, ContentBuilder, Ref, ResponseBuilder};
+
+    fn json_response(example: Option<Value>) -> Response {
+        ResponseBuilder::new()
+            .description("response")
+            .content(
+                "application/json",
+                ContentBuilder::new().example(example).build(),
+            )
+            .build()
+    }
+
+    #[test]
+    fn should_match_path_templates() {
+        let operation = OperationBuilder::new().build();
+        let route = MockRoute::new(Method::GET, "/users/{id}/posts", &operation, None);
+
+        assert_eq!(route.match_path("/users/42/posts"), Some(2));
+        assert_eq!(route.match_path("/users/42/posts/"), Some(2));
+        assert_eq!(route.match_path("/users/42"), None);
+        assert_eq!(route.match_path("/users/42/comments"), None);
+    }
+
+    #[test]
+    fn should_detect_missing_required_parameters() {
+        let operation = OperationBuilder::new()
+            .parameter(
+                ParameterBuilder::new()
+                    .name("limit")
+                    .parameter_in(ParameterIn::Query)
+                    .required(Required::True),
+            )
+            .parameter(
+                ParameterBuilder::new()
+                    .name("X-Request-Id")
+                    .parameter_in(ParameterIn::Header)
+                    .required(Required::True),
+            )
+            .parameter(
+                ParameterBuilder::new()
+                    .name("offset")
+                    .parameter_in(ParameterIn::Query)
+                    .required(Required::False),
+            )
+            .build();
+        let route = MockRoute::new(Method::GET, "/users", &operation, None);
+
+        let query = vec![("limit".to_string(), "10".to_string())];
+        assert_eq!(route.missing_parameter(&query, &[]), Some("x-request-id"));
+        assert_eq!(
+            route.missing_parameter(&query, &["x-request-id".to_string()]),
+            None
+        );
+        assert_eq!(route.missing_parameter(&[], &[]), Some("limit"));
+    }
+
+    #[test]
+    fn should_select_best_response() {
+        let operation = OperationBuilder::new()
+            .response("404", json_response(Some(json!({"error": "not found"}))))
+            .response("204", ResponseBuilder::new().description("empty").build())
+            .response("200", json_response(Some(json!({"id": 1}))))
+            .build();
+        let route = MockRoute::new(Method::GET, "/users/{id}", &operation, None);
+
+        let best = route.select_response(None).expect("should have a response");
+        assert_eq!(best.status, StatusCode::OK);
+        assert_eq!(best.example, Some(json!({"id": 1})));
+
+        let preferred = route
+            .select_response(Some(StatusCode::NOT_FOUND))
+            .expect("should have a response");
+        assert_eq!(preferred.example, Some(json!({"error": "not found"})));
+
+        let unknown = route
+            .select_response(Some(StatusCode::CONFLICT))
+            .expect("should fall back");
+        assert_eq!(unknown.status, StatusCode::OK);
+    }
+
+    #[test]
+    fn should_fall_back_to_schema_examples() {
+        let components = ComponentsBuilder::new()
+            .schema(
+                "User",
+                ObjectBuilder::new().examples([json!({"name": "Alice"})]),
+            )
+            .build();
+        let response = ResponseBuilder::new()
+            .description("user")
+            .content(
+                "application/json",
+                ContentBuilder::new()
+                    .schema(Some(Ref::from_schema_name("User")))
+                    .build(),
+            )
+            .build();
+        let operation = OperationBuilder::new().response("200", response).build();
+
+        let route = MockRoute::new(Method::GET, "/users/me", &operation, Some(&components));
+
+        let best = route.select_response(None).expect("should have a response");
+        assert_eq!(best.example, Some(json!({"name": "Alice"})));
+        assert_eq!(best.content_type.as_deref(), Some("application/json"));
+    }
+
+    #[test]
+    fn should_keep_specification_examples_over_recorded_ones() {
+        let operation = OperationBuilder::new()
+            .response("200", json_response(Some(json!("spec"))))
+            .build();
+        let mut route = MockRoute::new(Method::GET, "/items", &operation, None);
+
+        route.add_example(StatusCode::OK, "application/json", json!("recorded"));
+        route.add_example(StatusCode::CREATED, "application/json", json!("created"));
+
+        let statuses: Vec<_> = route.responses.iter().map(|r| r.status.as_u16()).collect();
+        assert_eq!(statuses, vec![200, 201]);
+        assert_eq!(
+            route.select_response(None).and_then(|r| r.example.clone()),
+            Some(json!("spec"))
+        );
+    }
+}
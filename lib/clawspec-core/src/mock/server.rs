@@ -0,0 +1,384 @@
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+
+use axum::Router;
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::response::Response;
+use http::header::CONTENT_TYPE;
+use http::{HeaderMap, HeaderValue, Method, StatusCode};
+use serde_json::{Value, json};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tracing::{debug, error};
+use utoipa::openapi::OpenApi;
+
+use super::route::MockRoute;
+use crate::operations::iter_operations;
+
+/// A stub server answering requests with the examples recorded in an OpenAPI specification.
+///
+/// See the [module documentation](crate::mock) for the matching and response selection rules.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use clawspec_core::OpenApi;
+/// use clawspec_core::mock::MockServer;
+///
+/// # async fn example(spec: OpenApi) -> Result<(), Box<dyn std::error::Error>> {
+/// let server = MockServer::from_openapi(&spec).start().await?;
+///
+/// let body = reqwest::get(format!("{}/users/42", server.base_url()))
+///     .await?
+///     .text()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockServer {
+    routes: Vec<MockRoute>,
+}
+
+impl MockServer {
+    /// Creates a mock server from an OpenAPI specification.
+    ///
+    /// Every operation of the specification becomes a stub. Response references
+    /// (`#/components/responses/...`) and schema references are resolved against
+    /// the specification components.
+    pub fn from_openapi(spec: &OpenApi) -> Self {
+        let components = spec.components.as_ref();
+        let routes = spec
+            .paths
+            .paths
+            .iter()
+            .flat_map(|(path, item)| {
+                iter_operations(item)
+                    .map(|(method, operation)| MockRoute::new(method, path, operation, components))
+            })
+            .collect();
+
+        Self { routes }
+    }
+
+    /// Adds an example for an operation and status code.
+    ///
+    /// The example is used only if the specification does not already provide one
+    /// for that status. Examples for unknown operations are ignored.
+    #[must_use]
+    pub fn with_example(
+        mut self,
+        method: Method,
+        path: &str,
+        status: StatusCode,
+        content_type: &str,
+        example: Value,
+    ) -> Self {
+        if let Some(route) = self
+            .routes
+            .iter_mut()
+            .find(|route| route.method == method && route.template == path)
+        {
+            route.add_example(status, content_type, example);
+        } else {
+            debug!(%method, path, "no operation found for example, ignoring");
+        }
+        self
+    }
+
+    /// Returns the number of stubbed operations.
+    pub fn route_count(&self) -> usize {
+        self.routes.len()
+    }
+
+    /// Starts the server on a random local port.
+    ///
+    /// The server runs in the background until the returned [`MockServerHandle`] is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the local port cannot be bound.
+    pub async fn start(self) -> Result<MockServerHandle, std::io::Error> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let local_addr = listener.local_addr()?;
+
+        let app = Router::new()
+            .fallback(handle_request)
+            .with_state(Arc::new(self));
+        let task = tokio::spawn(async move {
+            if let Err(err) = axum::serve(listener, app).await {
+                error!(?err, "mock server stopped");
+            }
+        });
+        debug!(%local_addr, "mock server started");
+
+        Ok(MockServerHandle { local_addr, task })
+    }
+
+    /// Computes the reply for a request.
+    fn reply(
+        &self,
+        method: &Method,
+        path: &str,
+        query: &[(String, String)],
+        headers: &HeaderMap,
+    ) -> (StatusCode, Option<String>, Option<Value>) {
+        let mut candidates: Vec<_> = self
+            .routes
+            .iter()
+            .filter_map(|route| route.match_path(path).map(|literals| (literals, route)))
+            .collect();
+        if candidates.is_empty() {
+            let error = json!({ "error": format!("no stub matches {method} {path}") });
+            return (StatusCode::NOT_FOUND, None, Some(error));
+        }
+        // Most specific templates first
+        candidates.sort_by_key(|(literals, _)| std::cmp::Reverse(*literals));
+
+        let Some((_, route)) = candidates.iter().find(|(_, route)| route.method == method) else {
+            let error = json!({ "error": format!("method {method} not stubbed for {path}") });
+            return (StatusCode::METHOD_NOT_ALLOWED, None, Some(error));
+        };
+
+        let header_names: Vec<_> = headers
+            .keys()
+            .map(|name| name.as_str().to_string())
+            .collect();
+        if let Some(missing) = route.missing_parameter(query, &header_names) {
+            let error = json!({ "error": format!("missing required parameter '{missing}'") });
+            return (StatusCode::BAD_REQUEST, None, Some(error));
+        }
+
+        let preferred = preferred_status(headers);
+        match route.select_response(preferred) {
+            Some(response) => (
+                response.status,
+                response.content_type.clone(),
+                response.example.clone(),
+            ),
+            None => (StatusCode::NOT_IMPLEMENTED, None, None),
+        }
+    }
+}
+
+impl From<&OpenApi> for MockServer {
+    fn from(spec: &OpenApi) -> Self {
+        Self::from_openapi(spec)
+    }
+}
+
+/// Handle on a running [`MockServer`].
+///
+/// The server is stopped when the handle is dropped.
+#[derive(Debug)]
+pub struct MockServerHandle {
+    local_addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl MockServerHandle {
+    /// Returns the local address the server is listening on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Returns the base URL of the server, e.g. `http://127.0.0.1:38271`.
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.local_addr)
+    }
+}
+
+impl Drop for MockServerHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Reads the status requested with a `Prefer: code=<status>` header.
+fn preferred_status(headers: &HeaderMap) -> Option<StatusCode> {
+    headers
+        .get_all("prefer")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split([',', ';']))
+        .find_map(|part| part.trim().strip_prefix("code="))
+        .and_then(|code| code.trim().parse::<u16>().ok())
+        .and_then(|code| StatusCode::from_u16(code).ok())
+}
+
+async fn handle_request(State(server): State<Arc<MockServer>>, request: Request) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let query: Vec<(String, String)> = request
+        .uri()
+        .query()
+        .map(|query| {
+            url::form_urlencoded::parse(query.as_bytes())
+                .into_owned()
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let (status, content_type, example) = server.reply(&method, &path, &query, request.headers());
+    debug!(%method, path, %status, "mock server reply");
+
+    let (content_type, body) = match example {
+        None => (content_type, Body::empty()),
+        // Text representations are sent verbatim
+        Some(Value::String(text))
+            if content_type
+                .as_deref()
+                .is_some_and(|content_type| !content_type.contains("json")) =>
+        {
+            (content_type, Body::from(text))
+        }
+        Some(value) => (
+            content_type.or_else(|| Some("application/json".to_string())),
+            Body::from(value.to_string()),
+        ),
+    };
+
+    let mut response = Response::new(body);
+    *response.status_mut() = status;
+    if let Some(value) = content_type.and_then(|ct| HeaderValue::from_str(&ct).ok()) {
+        response.headers_mut().insert(CONTENT_TYPE, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utoipa::openapi::path::{OperationBuilder, PathItemBuilder};
+    use utoipa::openapi::{ContentBuilder, HttpMethod, PathsBuilder, ResponseBuilder};
+
+    fn create_test_spec() -> OpenApi {
+        let json_response = |example: Value| {
+            ResponseBuilder::new()
+                .description("ok")
+                .content(
+                    "application/json",
+                    ContentBuilder::new().example(Some(example)).build(),
+                )
+                .build()
+        };
+
+        let get_user = OperationBuilder::new()
+            .response("200", json_response(json!({"id": 42, "name": "Alice"})))
+            .response("404", json_response(json!({"error": "not found"})))
+            .build();
+        let get_me = OperationBuilder::new()
+            .response("200", json_response(json!({"id": 1, "name": "Me"})))
+            .build();
+        let create_user = OperationBuilder::new()
+            .response("201", ResponseBuilder::new().description("created").build())
+            .build();
+
+        let paths = PathsBuilder::new()
+            .path(
+                "/users/{id}",
+                PathItemBuilder::new()
+                    .operation(HttpMethod::Get, get_user)
+                    .build(),
+            )
+            .path(
+                "/users/me",
+                PathItemBuilder::new()
+                    .operation(HttpMethod::Get, get_me)
+                    .build(),
+            )
+            .path(
+                "/users",
+                PathItemBuilder::new()
+                    .operation(HttpMethod::Post, create_user)
+                    .build(),
+            )
+            .build();
+
+        OpenApi::builder().paths(paths).build()
+    }
+
+    #[test]
+    fn should_build_routes_from_openapi() {
+        let server = MockServer::from_openapi(&create_test_spec());
+
+        assert_eq!(server.route_count(), 3);
+    }
+
+    #[test]
+    fn should_reply_with_recorded_examples() {
+        let server = MockServer::from_openapi(&create_test_spec());
+        let headers = HeaderMap::new();
+
+        let (status, _, body) = server.reply(&Method::GET, "/users/42", &[], &headers);
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, Some(json!({"id": 42, "name": "Alice"})));
+
+        let (status, _, body) = server.reply(&Method::GET, "/users/me", &[], &headers);
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, Some(json!({"id": 1, "name": "Me"})));
+
+        let (status, _, body) = server.reply(&Method::POST, "/users", &[], &headers);
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(body, None);
+    }
+
+    #[test]
+    fn should_reply_with_errors_when_nothing_matches() {
+        let server = MockServer::from_openapi(&create_test_spec());
+        let headers = HeaderMap::new();
+
+        let (status, _, _) = server.reply(&Method::GET, "/orders", &[], &headers);
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _, _) = server.reply(&Method::DELETE, "/users", &[], &headers);
+        assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[test]
+    fn should_honor_prefer_header() {
+        let server = MockServer::from_openapi(&create_test_spec());
+        let mut headers = HeaderMap::new();
+        headers.insert("prefer", HeaderValue::from_static("code=404"));
+
+        let (status, _, body) = server.reply(&Method::GET, "/users/42", &[], &headers);
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body, Some(json!({"error": "not found"})));
+    }
+
+    #[test]
+    fn should_add_examples_to_known_operations() {
+        let server = MockServer::from_openapi(&create_test_spec()).with_example(
+            Method::POST,
+            "/users",
+            StatusCode::CREATED,
+            "application/json",
+            json!({"id": 7}),
+        );
+
+        let (status, content_type, body) =
+            server.reply(&Method::POST, "/users", &[], &HeaderMap::new());
+
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(content_type.as_deref(), Some("application/json"));
+        assert_eq!(body, Some(json!({"id": 7})));
+    }
+
+    #[tokio::test]
+    async fn should_serve_examples_over_http() {
+        let server = MockServer::from_openapi(&create_test_spec())
+            .start()
+            .await
+            .expect("should start mock server");
+
+        let response = reqwest::get(format!("{}/users/42", server.base_url()))
+            .await
+            .expect("should call mock server");
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = response.json().await.expect("should be JSON");
+        assert_eq!(body, json!({"id": 42, "name": "Alice"}));
+    }
+}
@@ -0,0 +1,21 @@
+//! Helpers reading the operations of an OpenAPI specification.
+
+use http::Method;
+use utoipa::openapi::PathItem;
+use utoipa::openapi::path::Operation;
+
+/// Iterates over all operations of a path item with their HTTP method.
+pub(crate) fn iter_operations(path_item: &PathItem) -> impl Iterator<Item = (Method, &Operation)> {
+    [
+        (Method::GET, path_item.get.as_ref()),
+        (Method::PUT, path_item.put.as_ref()),
+        (Method::POST, path_item.post.as_ref()),
+        (Method::DELETE, path_item.delete.as_ref()),
+        (Method::OPTIONS, path_item.options.as_ref()),
+        (Method::HEAD, path_item.head.as_ref()),
+        (Method::PATCH, path_item.patch.as_ref()),
+        (Method::TRACE, path_item.trace.as_ref()),
+    ]
+    .into_iter()
+    .filter_map(|(method, operation)| operation.map(|op| (method, op)))
+}
@@ -0,0 +1,784 @@
+//! Bundling of split OpenAPI specifications back into a single document.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use percent_encoding::percent_decode_str;
+use serde_json::{Map, Value};
+use utoipa::openapi::OpenApi;
+
+/// Error type for bundling operations.
+#[derive(Debug, derive_more::Error, derive_more::Display)]
+pub enum BundleError {
+    /// A specification file cannot be read.
+    #[display("cannot read {}: {source}", path.display())]
+    Io {
+        /// The path of the file.
+        path: PathBuf,
+        /// The underlying I/O error.
+        source: std::io::Error,
+    },
+
+    /// A specification file is not valid JSON or YAML.
+    #[display("cannot parse {}: {message}", path.display())]
+    Parse {
+        /// The path of the file.
+        path: PathBuf,
+        /// The parser error message.
+        message: String,
+    },
+
+    /// A `$ref` points to a location that does not exist.
+    #[display("unresolved reference `{reference}` in {}", file.display())]
+    UnresolvedReference {
+        /// The reference, as written in the file.
+        reference: String,
+        /// The file containing the reference.
+        file: PathBuf,
+    },
+
+    /// References form a cycle that cannot be inlined.
+    #[display("reference cycle: {}", references.join(" -> "))]
+    Cycle {
+        /// The references of the cycle, in order.
+        references: Vec<String>,
+    },
+
+    /// Two files define the same component differently.
+    #[display("conflicting definitions of `#/components/{section}/{name}`")]
+    ConflictingComponent {
+        /// The components section, e.g. `schemas`.
+        section: String,
+        /// The component name.
+        name: String,
+    },
+
+    /// The bundled document cannot be read as an [`OpenApi`] specification.
+    #[display("invalid bundled specification: {_0}")]
+    InvalidSpec(serde_json::Error),
+}
+
+/// Bundles a split OpenAPI specification into a single self-contained document.
+///
+/// This is the inverse of [`OpenApiSplitter::split`](super::OpenApiSplitter::split): the
+/// main specification is read from `main_file`, and every relative external `$ref`
+/// (e.g. `common.yaml#/components/schemas/Error`) is resolved from the referenced file,
+/// relative to the file containing the reference.
+///
+/// - References to components (`#/components/{section}/{name}`) are hoisted into the
+///   components of the bundled specification, and rewritten as local references.
+///   Recursive schemas are therefore supported.
+/// - Other references (e.g. a path item in another file) are inlined. A cycle of such
+///   references is reported as [`BundleError::Cycle`].
+/// - Fragments holding bare [`Components`](utoipa::openapi::Components), as written by
+///   the splitters, are supported: `#/components/schemas/User` is looked up as
+///   `#/schemas/User` in such a file. Local component references that cannot be
+///   resolved in a fragment are resolved against the main specification.
+/// - Remote references (`https://...`) are left untouched.
+///
+/// The bundled document is returned as JSON: utoipa's [`OpenApi`] has no `parameters`,
+/// `requestBodies`, `headers`, or `examples` components, nor parameter references, so such
+/// a document cannot be read back into it. Use [`bundle_openapi`] to get an [`OpenApi`].
+///
+/// Files with a `.yaml` or `.yml` extension require the `yaml` feature, unless they hold
+/// JSON; other files are read as JSON.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use clawspec_core::split::bundle;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let openapi = bundle("docs/openapi.json")?;
+/// std::fs::write("target/openapi-bundled.json", serde_json::to_string_pretty(&openapi)?)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns a [`BundleError`] if a file cannot be read or parsed, if a reference cannot
+/// be resolved, on reference cycles, or on conflicting component definitions.
+pub fn bundle(main_file: impl AsRef<Path>) -> Result<Value, BundleError> {
+    Bundler::new(main_file.as_ref(), load_file).bundle()
+}
+
+/// Bundles a split OpenAPI specification into a single [`OpenApi`] document.
+///
+/// Like [`bundle`], then reads the bundled document as an [`OpenApi`]. The components
+/// utoipa cannot represent, like `parameters`, are dropped, and a document with
+/// parameter references is rejected: use [`bundle`] for such specifications.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use clawspec_core::split::bundle_openapi;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let openapi = bundle_openapi("docs/openapi.json")?;
+/// println!("{}", openapi.to_pretty_json()?);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns a [`BundleError`] like [`bundle`], or [`BundleError::InvalidSpec`] if the
+/// bundled document is not a valid [`OpenApi`] specification.
+pub fn bundle_openapi(main_file: impl AsRef<Path>) -> Result<OpenApi, BundleError> {
+    let document = bundle(main_file)?;
+    serde_json::from_value(document).map_err(BundleError::InvalidSpec)
+}
+
+/// A location in a document: a normalized file path and a decoded JSON Pointer.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Location {
+    file: PathBuf,
+    pointer: String,
+}
+
+impl Location {
+    /// Returns the `(section, name)` of the component at this location, if any.
+    fn component_key(&self) -> Option<(String, String)> {
+        let rest = self.pointer.strip_prefix("/components/")?;
+        let (section, name) = rest.split_once('/')?;
+        if name.contains('/') {
+            return None;
+        }
+        Some((unescape(section), unescape(name)))
+    }
+
+    fn display(&self) -> String {
+        format!("{}#{}", self.file.display(), self.pointer)
+    }
+}
+
+/// A component moved into the bundled specification.
+#[derive(Debug)]
+struct Hoisted {
+    location: Location,
+    content: Value,
+}
+
+struct Bundler<L> {
+    main: PathBuf,
+    loader: L,
+    documents: HashMap<PathBuf, Value>,
+    components: BTreeMap<(String, String), Hoisted>,
+    /// The locations being inlined, to detect cycles.
+    inlining: Vec<Location>,
+}
+
+impl<L> Bundler<L>
+where
+    L: FnMut(&Path) -> Result<Value, BundleError>,
+{
+    fn new(main: &Path, loader: L) -> Self {
+        Self {
+            main: normalize(main),
+            loader,
+            documents: HashMap::new(),
+            components: BTreeMap::new(),
+            inlining: vec![],
+        }
+    }
+
+    fn bundle(mut self) -> Result<Value, BundleError> {
+        let main = self.main.clone();
+        let mut document = self.document(&main)?.clone();
+
+        // The components of the main document are hoisted like the external ones
+        if let Some(Value::Object(components)) = document
+            .as_object_mut()
+            .and_then(|document| document.remove("components"))
+        {
+            for (section, entries) in components {
+                let Value::Object(entries) = entries else {
+                    continue;
+                };
+                for name in entries.keys() {
+                    let location = Location {
+                        file: main.clone(),
+                        pointer: format!("/components/{}/{}", escape(&section), escape(name)),
+                    };
+                    self.hoist((section.clone(), name.clone()), location)?;
+                }
+            }
+        }
+
+        self.bundle_value(&mut document, &main)?;
+
+        if !self.components.is_empty()
+            && let Some(document) = document.as_object_mut()
+        {
+            let mut components = Map::new();
+            for ((section, name), hoisted) in self.components {
+                let entries = components
+                    .entry(section)
+                    .or_insert_with(|| Value::Object(Map::new()));
+                if let Some(entries) = entries.as_object_mut() {
+                    entries.insert(name, hoisted.content);
+                }
+            }
+            document.insert("components".to_string(), Value::Object(components));
+        }
+
+        Ok(document)
+    }
+
+    fn document(&mut self, file: &Path) -> Result<&Value, BundleError> {
+        if !self.documents.contains_key(file) {
+            let document = (self.loader)(file)?;
+            self.documents.insert(file.to_path_buf(), document);
+        }
+        Ok(&self.documents[file])
+    }
+
+    /// Looks up a location, supporting fragments holding bare components.
+    fn lookup(&mut self, location: &Location) -> Result<Option<Value>, BundleError> {
+        let document = self.document(&location.file)?;
+        let found = document.pointer(&location.pointer).or_else(|| {
+            if document.get("components").is_some() {
+                return None;
+            }
+            let rest = location.pointer.strip_prefix("/components")?;
+            document.pointer(rest)
+        });
+        Ok(found.cloned())
+    }
+
+    fn resolve(&mut self, location: &Location, reference: &str) -> Result<Value, BundleError> {
+        self.lookup(location)?
+            .ok_or_else(|| BundleError::UnresolvedReference {
+                reference: reference.to_string(),
+                file: location.file.clone(),
+            })
+    }
+
+    /// Returns the location of a reference, or `None` for remote references.
+    fn locate(&self, reference: &str, file: &Path) -> Option<Location> {
+        if reference.contains("://") {
+            return None;
+        }
+        let (path, pointer) = reference.split_once('#').unwrap_or((reference, ""));
+        let pointer = percent_decode_str(pointer).decode_utf8_lossy().into_owned();
+        let file = if path.is_empty() {
+            file.to_path_buf()
+        } else {
+            let path = percent_decode_str(path).decode_utf8_lossy().into_owned();
+            normalize(&file.parent().unwrap_or(Path::new("")).join(path))
+        };
+        Some(Location { file, pointer })
+    }
+
+    /// Falls back to the main document for component references missing in a fragment.
+    fn component_location(&mut self, location: Location) -> Result<Location, BundleError> {
+        if location.file != self.main && self.lookup(&location)?.is_none() {
+            return Ok(Location {
+                file: self.main.clone(),
+                pointer: location.pointer,
+            });
+        }
+        Ok(location)
+    }
+
+    fn bundle_value(&mut self, value: &mut Value, file: &Path) -> Result<(), BundleError> {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(reference)) = map.get("$ref") {
+                    let reference = reference.clone();
+                    match self.bundle_reference(&reference, file)? {
+                        Some(Replacement::Reference(local)) => {
+                            map.insert("$ref".to_string(), Value::String(local));
+                        }
+                        Some(Replacement::Inline(content)) => *value = content,
+                        None => {}
+                    }
+                    return Ok(());
+                }
+                for child in map.values_mut() {
+                    self.bundle_value(child, file)?;
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.bundle_value(item, file)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn bundle_reference(
+        &mut self,
+        reference: &str,
+        file: &Path,
+    ) -> Result<Option<Replacement>, BundleError> {
+        if reference.starts_with('#') && file == self.main {
+            return Ok(None);
+        }
+        let Some(location) = self.locate(reference, file) else {
+            return Ok(None);
+        };
+
+        if let Some((section, name)) = location.component_key() {
+            let location = self.component_location(location)?;
+            let local = format!("#/components/{}/{}", escape(&section), escape(&name));
+            self.hoist((section, name), location)?;
+            return Ok(Some(Replacement::Reference(local)));
+        }
+
+        if self.inlining.contains(&location) {
+            let mut references: Vec<_> = self.inlining.iter().map(Location::display).collect();
+            references.push(location.display());
+            return Err(BundleError::Cycle { references });
+        }
+        self.inlining.push(location.clone());
+        let mut content = self.resolve(&location, reference)?;
+        self.bundle_value(&mut content, &location.file)?;
+        self.inlining.pop();
+
+        Ok(Some(Replacement::Inline(content)))
+    }
+
+    /// Follows the references aliasing a component to its definition.
+    ///
+    /// The split main specification holds entries like
+    /// `User: {$ref: users.yaml#/components/schemas/User}`.
+    fn definition(
+        &mut self,
+        key: &(String, String),
+        location: Location,
+    ) -> Result<Location, BundleError> {
+        let mut chain = vec![];
+        let mut current = location;
+        loop {
+            let content = self.resolve(&current, &current.display())?;
+            let Some(reference) = pure_reference(&content) else {
+                return Ok(current);
+            };
+            let Some(target) = self.locate(reference, &current.file) else {
+                return Ok(current);
+            };
+            if target.component_key().as_ref() != Some(key) {
+                return Ok(current);
+            }
+            let target = self.component_location(target)?;
+            chain.push(current);
+            if chain.contains(&target) {
+                let mut references: Vec<_> = chain.iter().map(Location::display).collect();
+                references.push(target.display());
+                return Err(BundleError::Cycle { references });
+            }
+            current = target;
+        }
+    }
+
+    fn hoist(&mut self, key: (String, String), location: Location) -> Result<(), BundleError> {
+        let definition = self.definition(&key, location)?;
+
+        if let Some(existing) = self.components.get(&key) {
+            if existing.location == definition {
+                return Ok(());
+            }
+            // The same component may be defined identically in several files
+            let existing_location = existing.location.clone();
+            let existing = self.resolve(&existing_location, &existing_location.display())?;
+            let other = self.resolve(&definition, &definition.display())?;
+            if existing == other {
+                return Ok(());
+            }
+            let (section, name) = key;
+            return Err(BundleError::ConflictingComponent { section, name });
+        }
+
+        // Registered before bundling the content, so recursive references terminate
+        self.components.insert(
+            key.clone(),
+            Hoisted {
+                location: definition.clone(),
+                content: Value::Null,
+            },
+        );
+        let mut content = self.resolve(&definition, &definition.display())?;
+        self.bundle_value(&mut content, &definition.file)?;
+        if let Some(hoisted) = self.components.get_mut(&key) {
+            hoisted.content = content;
+        }
+        Ok(())
+    }
+}
+
+enum Replacement {
+    Reference(String),
+    Inline(Value),
+}
+
+/// Returns the reference of an object only holding a `$ref`.
+fn pure_reference(value: &Value) -> Option<&str> {
+    match value {
+        Value::Object(map) if map.len() == 1 => map.get("$ref")?.as_str(),
+        _ => None,
+    }
+}
+
+pub(super) fn load_file(path: &Path) -> Result<Value, BundleError> {
+    let contents = fs::read_to_string(path).map_err(|source| BundleError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let extension = path.extension().unwrap_or_default();
+    if extension == "yaml" || extension == "yml" {
+        parse_yaml(path, &contents)
+    } else {
+        serde_json::from_str(&contents).map_err(|err| BundleError::Parse {
+            path: path.to_path_buf(),
+            message: err.to_string(),
+        })
+    }
+}
+
+#[cfg(feature = "yaml")]
+fn parse_yaml(path: &Path, contents: &str) -> Result<Value, BundleError> {
+    serde_saphyr::from_str(contents).map_err(|err| BundleError::Parse {
+        path: path.to_path_buf(),
+        message: err.to_string(),
+    })
+}
+
+#[cfg(not(feature = "yaml"))]
+fn parse_yaml(path: &Path, contents: &str) -> Result<Value, BundleError> {
+    // JSON is valid YAML
+    serde_json::from_str(contents).map_err(|_| BundleError::Parse {
+        path: path.to_path_buf(),
+        message: "reading YAML files requires the `yaml` feature".to_string(),
+    })
+}
+
+/// Lexically normalizes a path, resolving `.` and `..` components.
+pub(super) fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            // A `..` only cancels a named directory, not a previous `..`
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir | Component::Prefix(_)) => {}
+                _ => normalized.push(".."),
+            },
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+fn escape(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+fn unescape(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bundle_documents(documents: Vec<(&str, Value)>) -> Result<Value, BundleError> {
+        let documents: HashMap<PathBuf, Value> = documents
+            .into_iter()
+            .map(|(path, document)| (PathBuf::from(path), document))
+            .collect();
+        let loader = |path: &Path| {
+            documents.get(path).cloned().ok_or_else(|| BundleError::Io {
+                path: path.to_path_buf(),
+                source: std::io::ErrorKind::NotFound.into(),
+            })
+        };
+        Bundler::new(Path::new("api/openapi.json"), loader).bundle()
+    }
+
+    fn main_spec(paths: Value, schemas: Value) -> Value {
+        json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": paths,
+            "components": {"schemas": schemas}
+        })
+    }
+
+    #[test]
+    fn should_normalize_paths() {
+        assert_eq!(
+            normalize(Path::new("api/./schemas/../common.yaml")),
+            PathBuf::from("api/common.yaml")
+        );
+        assert_eq!(
+            normalize(Path::new("../common.yaml")),
+            PathBuf::from("../common.yaml")
+        );
+        assert_eq!(
+            normalize(Path::new("../../specs/openapi.json")),
+            PathBuf::from("../../specs/openapi.json")
+        );
+        assert_eq!(normalize(Path::new("api/../../x")), PathBuf::from("../x"));
+        assert_eq!(normalize(Path::new("/../x")), PathBuf::from("/x"));
+    }
+
+    #[test]
+    fn should_bundle_split_result() {
+        let main = main_spec(
+            json!({}),
+            json!({
+                "User": {"$ref": "users.json#/components/schemas/User"},
+                "Error": {"$ref": "common/errors.json#/components/schemas/Error"}
+            }),
+        );
+        // Fragments written by the splitters hold bare components
+        let users = json!({"schemas": {"User": {
+            "type": "object",
+            "properties": {
+                "error": {"$ref": "#/components/schemas/Error"},
+                "friends": {"type": "array", "items": {"$ref": "#/components/schemas/User"}}
+            }
+        }}});
+        let errors = json!({"schemas": {"Error": {"type": "string"}}});
+
+        let bundled = bundle_documents(vec![
+            ("api/openapi.json", main),
+            ("api/users.json", users),
+            ("api/common/errors.json", errors),
+        ])
+        .expect("should bundle");
+
+        assert_eq!(
+            bundled["components"]["schemas"],
+            json!({
+                "Error": {"type": "string"},
+                "User": {
+                    "type": "object",
+                    "properties": {
+                        "error": {"$ref": "#/components/schemas/Error"},
+                        "friends": {"type": "array", "items": {"$ref": "#/components/schemas/User"}}
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn should_hoist_referenced_components_and_inline_other_references() {
+        let main = main_spec(json!({"/users": {"$ref": "paths/users.json"}}), json!({}));
+        let users = json!({
+            "get": {
+                "responses": {
+                    "200": {
+                        "description": "ok",
+                        "content": {"application/json": {"schema": {"$ref": "../schemas.json#/components/schemas/User"}}}
+                    }
+                }
+            }
+        });
+        let schemas = json!({"components": {"schemas": {
+            "User": {"type": "object", "properties": {"address": {"$ref": "#/components/schemas/Address"}}},
+            "Address": {"type": "string"}
+        }}});
+
+        let bundled = bundle_documents(vec![
+            ("api/openapi.json", main),
+            ("api/paths/users.json", users),
+            ("api/schemas.json", schemas),
+        ])
+        .expect("should bundle");
+
+        assert_eq!(
+            bundled["paths"]["/users"]["get"]["responses"]["200"]["content"]["application/json"]["schema"],
+            json!({"$ref": "#/components/schemas/User"})
+        );
+        assert_eq!(
+            bundled["components"]["schemas"]["Address"],
+            json!({"type": "string"})
+        );
+    }
+
+    #[test]
+    fn should_keep_parameter_references() {
+        let main = json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {"/users": {"$ref": "paths/users.json#/~1users"}},
+            "components": {"parameters": {"RequestId": {"$ref": "common.json#/components/parameters/RequestId"}}}
+        });
+        let users = json!({"/users": {"get": {
+            "parameters": [{"$ref": "../common.json#/components/parameters/RequestId"}],
+            "responses": {"200": {"description": "ok"}}
+        }}});
+        let common = json!({"parameters": {"RequestId": {
+            "name": "X-Request-Id", "in": "header", "schema": {"type": "string"}
+        }}});
+
+        let bundled = bundle_documents(vec![
+            ("api/openapi.json", main),
+            ("api/paths/users.json", users),
+            ("api/common.json", common),
+        ])
+        .expect("should bundle");
+
+        assert_eq!(
+            bundled["paths"]["/users"]["get"]["parameters"],
+            json!([{"$ref": "#/components/parameters/RequestId"}])
+        );
+        assert_eq!(
+            bundled["components"],
+            json!({"parameters": {"RequestId": {
+                "name": "X-Request-Id", "in": "header", "schema": {"type": "string"}
+            }}})
+        );
+    }
+
+    #[test]
+    fn should_detect_inlining_cycles() {
+        let main = main_spec(json!({"/a": {"$ref": "a.json"}}), json!({}));
+        let a = json!({"get": {"$ref": "b.json"}});
+        let b = json!({"$ref": "a.json"});
+
+        let error = bundle_documents(vec![
+            ("api/openapi.json", main),
+            ("api/a.json", a),
+            ("api/b.json", b),
+        ])
+        .expect_err("should detect the cycle");
+
+        insta::assert_snapshot!(error, @"reference cycle: api/a.json# -> api/b.json# -> api/a.json#");
+    }
+
+    #[test]
+    fn should_detect_alias_cycles() {
+        let main = main_spec(
+            json!({}),
+            json!({"User": {"$ref": "users.json#/components/schemas/User"}}),
+        );
+        let users = json!({"schemas": {"User": {"$ref": "openapi.json#/components/schemas/User"}}});
+
+        let error = bundle_documents(vec![("api/openapi.json", main), ("api/users.json", users)])
+            .expect_err("should detect the cycle");
+
+        assert!(matches!(error, BundleError::Cycle { .. }));
+    }
+
+    #[test]
+    fn should_report_conflicting_components() {
+        let main = main_spec(
+            json!({}),
+            json!({
+                "Error": {"type": "string"},
+                "User": {"$ref": "users.json#/components/schemas/User"}
+            }),
+        );
+        let users = json!({"components": {"schemas": {
+            "User": {"properties": {"error": {"$ref": "#/components/schemas/Error"}}},
+            "Error": {"type": "integer"}
+        }}});
+
+        let error = bundle_documents(vec![("api/openapi.json", main), ("api/users.json", users)])
+            .expect_err("should report the conflict");
+
+        assert_eq!(
+            error.to_string(),
+            "conflicting definitions of `#/components/schemas/Error`"
+        );
+    }
+
+    #[test]
+    fn should_report_unresolved_references() {
+        let main = main_spec(
+            json!({}),
+            json!({"User": {"$ref": "users.json#/components/schemas/Missing"}}),
+        );
+        let users = json!({"components": {"schemas": {}}});
+
+        let error = bundle_documents(vec![("api/openapi.json", main), ("api/users.json", users)])
+            .expect_err("should report the missing reference");
+
+        assert!(matches!(error, BundleError::UnresolvedReference { .. }));
+    }
+
+    #[test]
+    fn should_resolve_references_to_parent_directories() {
+        let documents: HashMap<PathBuf, Value> = [
+            (
+                "../../specs/openapi.json",
+                main_spec(
+                    json!({}),
+                    json!({"Error": {"$ref": "../common/errors.json#/components/schemas/Error"}}),
+                ),
+            ),
+            (
+                "../../common/errors.json",
+                json!({"schemas": {"Error": {"type": "string"}}}),
+            ),
+        ]
+        .into_iter()
+        .map(|(path, document)| (PathBuf::from(path), document))
+        .collect();
+        let loader = |path: &Path| {
+            documents.get(path).cloned().ok_or_else(|| BundleError::Io {
+                path: path.to_path_buf(),
+                source: std::io::ErrorKind::NotFound.into(),
+            })
+        };
+
+        let bundled = Bundler::new(Path::new("../../specs/openapi.json"), loader)
+            .bundle()
+            .expect("should bundle");
+
+        insta::assert_snapshot!(bundled["components"], @r#"{"schemas":{"Error":{"type":"string"}}}"#);
+    }
+
+    #[test]
+    fn should_reject_invalid_openapi_documents() {
+        let dir = std::env::temp_dir().join(format!("clawspec-bundle-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("should create the directory");
+        fs::write(dir.join("openapi.json"), json!({"paths": []}).to_string())
+            .expect("should write");
+
+        let error = bundle_openapi(dir.join("openapi.json"));
+        fs::remove_dir_all(&dir).expect("should clean up");
+
+        assert!(matches!(error, Err(BundleError::InvalidSpec(_))));
+    }
+
+    #[test]
+    fn should_bundle_files_from_disk() {
+        let dir = std::env::temp_dir().join(format!("clawspec-bundle-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).expect("should create the directory");
+        let main = main_spec(
+            json!({}),
+            json!({"Error": {"$ref": "common.json#/components/schemas/Error"}}),
+        );
+        fs::write(dir.join("openapi.json"), main.to_string()).expect("should write");
+        fs::write(
+            dir.join("common.json"),
+            json!({"schemas": {"Error": {"type": "string"}}}).to_string(),
+        )
+        .expect("should write");
+
+        let openapi = bundle_openapi(dir.join("openapi.json"));
+        fs::remove_dir_all(&dir).expect("should clean up");
+
+        let components = openapi
+            .expect("should bundle")
+            .components
+            .expect("should have components");
+        assert!(matches!(
+            components.schemas.get("Error"),
+            Some(utoipa::openapi::RefOr::T(_))
+        ));
+    }
+}
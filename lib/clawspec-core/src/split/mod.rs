@@ -8,7 +8,8 @@
 //! When working with large APIs, it's common to want to:
 //! - Extract common schemas (like error types) into a shared file
 //! - Organize schemas by domain or tag
-//! - Share schemas across multiple API specifications
+//! - Move path items into per-resource files
+//! - Share schemas across multiple API specifications, see [`SharedSchemas`]
 //!
 //! This module provides the [`OpenApiSplitter`] trait and several implementations for common
 //! splitting strategies.
@@ -19,7 +20,7 @@
 //!
 #![cfg_attr(feature = "yaml", doc = "```rust,ignore")]
 #![cfg_attr(not(feature = "yaml"), doc = "```rust,ignore")]
-//! use clawspec_core::split::{OpenApiSplitter, SplitSchemasByTag};
+//! use clawspec_core::split::{OpenApiSplitter, SplitFormat, SplitSchemasByTag};
 //! use std::path::PathBuf;
 //!
 //! let spec: OpenApi = /* your collected OpenAPI spec */;
@@ -28,15 +29,8 @@
 //! let splitter = SplitSchemasByTag::new(PathBuf::from("common-types.yaml"));
 //! let result = splitter.split(spec);
 //!
-//! // Write fragments using the convenient to_yaml() method
-//! for fragment in &result.fragments {
-//!     let content = fragment.to_yaml()?;
-//!     std::fs::write(&fragment.path, content)?;
-//! }
-//!
-//! // Write main spec
-//! let main_content = result.main_to_yaml()?;
-//! std::fs::write("openapi.yaml", main_content)?;
+//! // Write the main spec and the fragments, with references relative to each file
+//! result.write_to("docs", "openapi.yaml", SplitFormat::Yaml)?;
 //! ```
 //!
 //! ## Using ToYaml trait directly
@@ -50,11 +44,25 @@
 //! // The ToYaml trait is implemented for all Serialize types
 //! let main_yaml = result.main.to_yaml()?;
 //! ```
+//!
+//! # Bundling
+//!
+//! [`bundle`] is the inverse operation: it reads a split specification from disk and
+//! resolves its external references into a single self-contained document.
+//! [`bundle_openapi`] reads the bundled document as an [`OpenApi`](utoipa::openapi::OpenApi).
 
+mod bundle;
 mod fragment;
+mod paths;
+mod shared;
 mod splitter;
 mod strategies;
+mod write;
 
+pub use bundle::{BundleError, bundle, bundle_openapi};
 pub use fragment::{Fragment, SplitResult};
+pub use paths::{PathsFragment, SplitPathsByPrefix, SplitPathsByTag};
+pub use shared::{SchemaConflict, SchemaDefinition, SharedSchemas, SharedSplit};
 pub use splitter::{OpenApiSplitExt, OpenApiSplitter};
 pub use strategies::{ExtractSchemasByPredicate, SplitSchemasByTag};
+pub use write::{SplitFormat, SplitWriteError};
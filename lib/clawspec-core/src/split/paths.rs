@@ -0,0 +1,491 @@
+//! Splitting strategies moving path items into separate files.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use utoipa::openapi::extensions::Extensions;
+use utoipa::openapi::path::{PathItem, Paths};
+use utoipa::openapi::{Components, OpenApi, Ref, RefOr};
+
+use super::write::reference_path;
+use super::{Fragment, OpenApiSplitter, SplitResult};
+use crate::operations::iter_operations;
+
+/// Characters percent-encoded in the JSON Pointer of a `$ref`, e.g. the braces of path
+/// parameters.
+const POINTER: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'<')
+    .add(b'>')
+    .add(b'\\')
+    .add(b'^')
+    .add(b'`')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
+
+/// The content of a fragment produced by the path splitters.
+///
+/// Serializes as the bare content: path files hold a map of paths to path items, and the
+/// components file holds the shared [`Components`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum PathsFragment {
+    /// Path items, referenced from the main specification as `file#/~1users`.
+    Paths(Paths),
+    /// Shared `responses` and `parameters` components.
+    Components(Components),
+}
+
+/// Moves path items into one file per tag.
+///
+/// Each path item goes into `{dir}/{tag}.yaml`, where `tag` is the first tag of its first
+/// tagged operation, or `default` for untagged path items. The main specification keeps a
+/// `$ref` to the path item, e.g. `paths/users.yaml#/~1users~1%7Bid%7D`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use clawspec_core::split::{OpenApiSplitter, SplitFormat, SplitPathsByTag};
+///
+/// let splitter = SplitPathsByTag::new("paths").with_components_file("components.yaml");
+/// let result = splitter.split(spec);
+///
+/// // Writes paths/users.yaml, paths/orders.yaml, components.yaml, and openapi.yaml
+/// result.write_to("docs", "openapi.yaml", SplitFormat::Yaml)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct SplitPathsByTag {
+    options: PathsSplitOptions,
+}
+
+impl SplitPathsByTag {
+    /// Creates a new splitter writing the path files into `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            options: PathsSplitOptions::new(dir.into()),
+        }
+    }
+
+    /// Sets the extension of the path files, `yaml` by default.
+    pub fn with_extension(mut self, extension: impl Into<String>) -> Self {
+        self.options.extension = extension.into();
+        self
+    }
+
+    /// Also moves the `responses` and `parameters` components into a shared file.
+    pub fn with_components_file(mut self, file: impl Into<PathBuf>) -> Self {
+        self.options.components_file = Some(file.into());
+        self
+    }
+}
+
+impl OpenApiSplitter for SplitPathsByTag {
+    type Fragment = PathsFragment;
+
+    fn split(&self, spec: OpenApi) -> SplitResult<Self::Fragment> {
+        self.options.split(spec, |_, item| {
+            iter_operations(item)
+                .find_map(|(_, operation)| operation.tags.as_ref()?.first())
+                .map_or_else(|| "default".to_string(), slug::slugify)
+        })
+    }
+}
+
+/// Moves path items into one file per first path segment.
+///
+/// Each path item goes into `{dir}/{segment}.yaml`: `/users` and `/users/{id}` go into
+/// `users.yaml`, and `/` into `root.yaml`. The main specification keeps a `$ref` to the
+/// path item, e.g. `paths/users.yaml#/~1users~1%7Bid%7D`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use clawspec_core::split::{OpenApiSplitter, SplitPathsByPrefix};
+///
+/// let splitter = SplitPathsByPrefix::new("paths").with_extension("json");
+/// let result = splitter.split(spec);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SplitPathsByPrefix {
+    options: PathsSplitOptions,
+}
+
+impl SplitPathsByPrefix {
+    /// Creates a new splitter writing the path files into `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            options: PathsSplitOptions::new(dir.into()),
+        }
+    }
+
+    /// Sets the extension of the path files, `yaml` by default.
+    pub fn with_extension(mut self, extension: impl Into<String>) -> Self {
+        self.options.extension = extension.into();
+        self
+    }
+
+    /// Also moves the `responses` and `parameters` components into a shared file.
+    pub fn with_components_file(mut self, file: impl Into<PathBuf>) -> Self {
+        self.options.components_file = Some(file.into());
+        self
+    }
+}
+
+impl OpenApiSplitter for SplitPathsByPrefix {
+    type Fragment = PathsFragment;
+
+    fn split(&self, spec: OpenApi) -> SplitResult<Self::Fragment> {
+        self.options.split(spec, |path, _| {
+            path.split('/')
+                .find(|segment| !segment.is_empty())
+                .map(slug::slugify)
+                .filter(|segment| !segment.is_empty())
+                .unwrap_or_else(|| "root".to_string())
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PathsSplitOptions {
+    dir: PathBuf,
+    extension: String,
+    components_file: Option<PathBuf>,
+}
+
+impl PathsSplitOptions {
+    fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            extension: "yaml".to_string(),
+            components_file: None,
+        }
+    }
+
+    fn split(
+        &self,
+        mut spec: OpenApi,
+        file_name: impl Fn(&str, &PathItem) -> String,
+    ) -> SplitResult<PathsFragment> {
+        if spec.paths.paths.is_empty() {
+            return SplitResult::new(spec);
+        }
+
+        let mut files: BTreeMap<PathBuf, Paths> = BTreeMap::new();
+        for (path, item) in std::mem::take(&mut spec.paths.paths) {
+            let file = self
+                .dir
+                .join(format!("{}.{}", file_name(&path, &item), self.extension));
+            let reference = format!(
+                "{}#/{}",
+                reference_path(&file),
+                utf8_percent_encode(&escape(&path), POINTER)
+            );
+            spec.paths
+                .paths
+                .insert(path.clone(), reference_item(reference));
+            files.entry(file).or_default().paths.insert(path, item);
+        }
+
+        let components = match &self.components_file {
+            Some(file) => extract_components(&mut spec, file),
+            None => None,
+        };
+
+        let mut result = SplitResult::new(spec);
+        for (file, paths) in files {
+            result.add_fragment(Fragment::new(file, PathsFragment::Paths(paths)));
+        }
+        if let Some(fragment) = components {
+            result.add_fragment(fragment);
+        }
+        result
+    }
+}
+
+/// Returns a path item only holding a `$ref`.
+fn reference_item(reference: String) -> PathItem {
+    // `PathItem` has no `$ref` field, the flattened extensions are serialized as is
+    let mut extensions = Extensions::default();
+    extensions.insert("$ref".to_string(), Value::String(reference));
+    let mut item = PathItem::default();
+    item.extensions = Some(extensions);
+    item
+}
+
+/// Moves the `responses` and `parameters` components into a fragment.
+///
+/// `utoipa` components have no `parameters` field, they are read from the extensions.
+fn extract_components(spec: &mut OpenApi, file: &Path) -> Option<Fragment<PathsFragment>> {
+    let components = spec.components.as_mut()?;
+    let external_ref = |section: &str, name: &str| {
+        format!(
+            "{}#/components/{section}/{}",
+            reference_path(file),
+            escape(name)
+        )
+    };
+
+    let mut extracted = Components::new();
+    for (name, response) in std::mem::take(&mut components.responses) {
+        components.responses.insert(
+            name.clone(),
+            RefOr::Ref(Ref::new(external_ref("responses", &name))),
+        );
+        extracted.responses.insert(name, response);
+    }
+
+    let parameters = components
+        .extensions
+        .as_mut()
+        .and_then(|extensions| extensions.remove("parameters"));
+    if let Some(Value::Object(parameters)) = parameters {
+        let references: Map<_, _> = parameters
+            .keys()
+            .map(|name| {
+                (
+                    name.clone(),
+                    json!({"$ref": external_ref("parameters", name)}),
+                )
+            })
+            .collect();
+        if let Some(extensions) = components.extensions.as_mut() {
+            extensions.insert("parameters".to_string(), Value::Object(references));
+        }
+        extracted
+            .extensions
+            .get_or_insert_with(Extensions::default)
+            .insert("parameters".to_string(), Value::Object(parameters));
+    } else if let Some(parameters) = parameters
+        && let Some(extensions) = components.extensions.as_mut()
+    {
+        extensions.insert("parameters".to_string(), parameters);
+    }
+
+    if extracted.responses.is_empty() && extracted.extensions.is_none() {
+        return None;
+    }
+    Some(Fragment::new(file, PathsFragment::Components(extracted)))
+}
+
+fn escape(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::split::{SplitFormat, bundle};
+
+    fn spec(value: Value) -> OpenApi {
+        serde_json::from_value(value).expect("should be a valid spec")
+    }
+
+    fn request_id() -> Value {
+        json!({"name": "X-Request-Id", "in": "header", "required": true, "schema": {"type": "string"}})
+    }
+
+    fn create_test_spec() -> OpenApi {
+        let operation = |tag: &str| {
+            json!({
+                "tags": [tag],
+                "parameters": [request_id()],
+                "responses": {
+                    "200": {
+                        "description": "ok",
+                        "content": {"application/json": {"schema": {"$ref": "#/components/schemas/User"}}}
+                    },
+                    "404": {"$ref": "#/components/responses/NotFound"}
+                }
+            })
+        };
+        let mut spec = spec(json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {
+                "/users": {"get": operation("users")},
+                "/users/{id}": {
+                    "parameters": [{"name": "id", "in": "path", "required": true, "schema": {"type": "integer"}}],
+                    "get": operation("users")
+                },
+                "/admin/users": {"delete": operation("admin")},
+                "/": {"get": {"responses": {"200": {"description": "ok"}}}}
+            },
+            "components": {
+                "schemas": {"User": {"type": "object"}},
+                "responses": {"NotFound": {"description": "Not found"}}
+            }
+        }));
+        // `utoipa` components have no `parameters` field, the shared header is an extension
+        if let Some(components) = spec.components.as_mut() {
+            components
+                .extensions
+                .get_or_insert_with(Extensions::default)
+                .insert("parameters".to_string(), json!({"RequestId": request_id()}));
+        }
+        spec
+    }
+
+    fn main_paths(result: &SplitResult<PathsFragment>) -> Value {
+        serde_json::to_value(&result.main.paths).expect("should serialize")
+    }
+
+    fn fragment_paths(result: &SplitResult<PathsFragment>) -> Vec<(String, Vec<String>)> {
+        result
+            .fragments
+            .iter()
+            .filter_map(|fragment| match &fragment.content {
+                PathsFragment::Paths(paths) => Some((
+                    reference_path(&fragment.path),
+                    paths.paths.keys().cloned().collect(),
+                )),
+                PathsFragment::Components(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_split_paths_by_tag() {
+        let result = SplitPathsByTag::new("paths").split(create_test_spec());
+
+        insta::assert_debug_snapshot!(fragment_paths(&result), @r#"
+        [
+            (
+                "paths/admin.yaml",
+                [
+                    "/admin/users",
+                ],
+            ),
+            (
+                "paths/default.yaml",
+                [
+                    "/",
+                ],
+            ),
+            (
+                "paths/users.yaml",
+                [
+                    "/users",
+                    "/users/{id}",
+                ],
+            ),
+        ]
+        "#);
+        assert_eq!(
+            main_paths(&result),
+            json!({
+                "/": {"$ref": "paths/default.yaml#/~1"},
+                "/admin/users": {"$ref": "paths/admin.yaml#/~1admin~1users"},
+                "/users": {"$ref": "paths/users.yaml#/~1users"},
+                "/users/{id}": {"$ref": "paths/users.yaml#/~1users~1%7Bid%7D"}
+            })
+        );
+    }
+
+    #[test]
+    fn should_split_paths_by_prefix() {
+        let result = SplitPathsByPrefix::new("paths")
+            .with_extension("json")
+            .split(create_test_spec());
+
+        insta::assert_debug_snapshot!(fragment_paths(&result), @r#"
+        [
+            (
+                "paths/admin.json",
+                [
+                    "/admin/users",
+                ],
+            ),
+            (
+                "paths/root.json",
+                [
+                    "/",
+                ],
+            ),
+            (
+                "paths/users.json",
+                [
+                    "/users",
+                    "/users/{id}",
+                ],
+            ),
+        ]
+        "#);
+        assert!(result.main.components.is_some_and(|components| {
+            matches!(components.responses.get("NotFound"), Some(RefOr::T(_)))
+        }));
+    }
+
+    #[test]
+    fn should_not_split_without_paths() {
+        let spec = spec(json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {}
+        }));
+
+        let result = SplitPathsByTag::new("paths").split(spec);
+
+        assert!(result.is_unsplit());
+    }
+
+    #[test]
+    fn should_extract_shared_components() {
+        let result = SplitPathsByTag::new("paths")
+            .with_components_file("components.yaml")
+            .split(create_test_spec());
+
+        let main = serde_json::to_value(&result.main.components).expect("should serialize");
+        assert_eq!(
+            main["responses"],
+            json!({"NotFound": {"$ref": "components.yaml#/components/responses/NotFound"}})
+        );
+        assert_eq!(
+            main["parameters"],
+            json!({"RequestId": {"$ref": "components.yaml#/components/parameters/RequestId"}})
+        );
+        let fragment = result.fragments.last().expect("should have fragments");
+        assert_eq!(fragment.path, PathBuf::from("components.yaml"));
+        let shared = serde_json::to_value(&fragment.content).expect("should serialize");
+        assert_eq!(
+            shared,
+            json!({
+                "responses": {"NotFound": {"description": "Not found"}},
+                "parameters": {"RequestId": request_id()}
+            })
+        );
+    }
+
+    #[test]
+    fn should_bundle_written_paths() {
+        let dir = std::env::temp_dir().join(format!("clawspec-paths-{}", uuid::Uuid::new_v4()));
+        let result = SplitPathsByPrefix::new("paths")
+            .with_extension("json")
+            .with_components_file("components.json")
+            .split(create_test_spec());
+
+        result
+            .write_to(&dir, "openapi.json", SplitFormat::Json)
+            .expect("should write");
+        let bundled = bundle(dir.join("openapi.json"));
+        let users = std::fs::read_to_string(dir.join("paths/users.json"));
+        std::fs::remove_dir_all(&dir).expect("should clean up");
+
+        let users: Value = serde_json::from_str(&users.expect("should read")).expect("JSON");
+        assert_eq!(
+            users["/users"]["get"]["responses"]["404"],
+            json!({"$ref": "../components.json#/components/responses/NotFound"})
+        );
+        assert_eq!(users["/users"]["get"]["parameters"], json!([request_id()]));
+        assert_eq!(
+            bundled.expect("should bundle"),
+            serde_json::to_value(create_test_spec()).expect("should serialize")
+        );
+    }
+}
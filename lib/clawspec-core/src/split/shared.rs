@@ -0,0 +1,353 @@
+//! Sharing of component schemas across several OpenAPI specifications.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use serde_json::Value;
+use utoipa::openapi::{Components, OpenApi, Ref, RefOr};
+
+use super::strategies::{collect_refs, extract_schema_name};
+use super::write::reference_path;
+use super::{Fragment, SplitResult};
+
+/// Moves the schemas shared by several specifications into a common components file.
+///
+/// Typical use is several APIs built from the same domain crates, each collected by its
+/// own [`ApiClient`](crate::ApiClient). A schema is shared when:
+///
+/// - it is defined by at least two specifications, with the same content,
+/// - no specification defines a different schema with the same name,
+/// - all the schemas it references are shared too.
+///
+/// Shared schemas are moved to the common file, and replaced in each specification by a
+/// `$ref` to it. Schemas with the same name but different content are reported as
+/// [`SchemaConflict`]s, and kept in their specifications.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use clawspec_core::split::{SharedSchemas, SplitFormat};
+/// # use clawspec_core::ApiClient;
+///
+/// # async fn example(mut users: ApiClient, mut orders: ApiClient) -> Result<(), Box<dyn std::error::Error>> {
+/// let shared = SharedSchemas::new("common.json")
+///     .with_spec("users", users.collected_openapi().await)
+///     .with_spec("orders", orders.collected_openapi().await)
+///     .split();
+///
+/// if !shared.conflicts.is_empty() {
+///     eprintln!("{}", shared.conflict_report());
+/// }
+/// for name in ["users", "orders"] {
+///     if let Some(result) = shared.split_result(name) {
+///         result.write_to("docs", format!("{name}.json"), SplitFormat::Json)?;
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SharedSchemas {
+    common_file: PathBuf,
+    specs: Vec<(String, OpenApi)>,
+}
+
+impl SharedSchemas {
+    /// Creates a new sharing with the specified common file path.
+    ///
+    /// The path is relative to the specification files.
+    pub fn new(common_file: impl Into<PathBuf>) -> Self {
+        Self {
+            common_file: common_file.into(),
+            specs: vec![],
+        }
+    }
+
+    /// Adds a named specification.
+    pub fn with_spec(mut self, name: impl Into<String>, spec: OpenApi) -> Self {
+        self.specs.push((name.into(), spec));
+        self
+    }
+
+    /// Moves the shared schemas into the common components.
+    pub fn split(self) -> SharedSplit {
+        // Group the definitions of each schema by canonical JSON, `serde_json` sorts the keys
+        let mut definitions: BTreeMap<String, BTreeMap<String, Definition>> = BTreeMap::new();
+        for (spec_name, spec) in &self.specs {
+            for (name, schema) in spec.components.iter().flat_map(|c| &c.schemas) {
+                let content = serde_json::to_value(schema).unwrap_or_default();
+                let definition = definitions
+                    .entry(name.clone())
+                    .or_default()
+                    .entry(content.to_string())
+                    .or_insert_with(|| Definition {
+                        schema: schema.clone(),
+                        content,
+                        specs: vec![],
+                    });
+                definition.specs.push(spec_name.clone());
+            }
+        }
+
+        let conflicts: Vec<_> = definitions
+            .iter()
+            .filter(|(_, by_content)| by_content.len() > 1)
+            .map(|(name, by_content)| SchemaConflict {
+                name: name.clone(),
+                definitions: by_content
+                    .iter()
+                    .map(|(canonical, definition)| SchemaDefinition {
+                        hash: content_hash(canonical),
+                        specs: definition.specs.clone(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let mut shared: BTreeSet<&String> = definitions
+            .iter()
+            .filter(|(_, by_content)| {
+                by_content.len() == 1 && by_content.values().all(|def| def.specs.len() > 1)
+            })
+            .map(|(name, _)| name)
+            .collect();
+
+        // A shared schema cannot reference a schema kept in the specifications
+        loop {
+            let unshareable: Vec<_> = shared
+                .iter()
+                .copied()
+                .filter(|name| {
+                    definitions[*name].values().any(|definition| {
+                        let mut refs = BTreeSet::new();
+                        collect_refs(&definition.content, &mut refs);
+                        refs.into_iter()
+                            .filter_map(extract_schema_name)
+                            .any(|dependency| !shared.contains(&dependency))
+                    })
+                })
+                .collect();
+            if unshareable.is_empty() {
+                break;
+            }
+            for name in unshareable {
+                shared.remove(name);
+            }
+        }
+
+        let mut common = Components::new();
+        for name in &shared {
+            if let Some(definition) = definitions[*name].values().next() {
+                common
+                    .schemas
+                    .insert((*name).clone(), definition.schema.clone());
+            }
+        }
+
+        let common_file = reference_path(&self.common_file);
+        let specs = self
+            .specs
+            .into_iter()
+            .map(|(spec_name, mut spec)| {
+                if let Some(components) = spec.components.as_mut() {
+                    for (name, schema) in components.schemas.iter_mut() {
+                        if shared.contains(name) {
+                            let reference = format!("{common_file}#/components/schemas/{name}");
+                            *schema = RefOr::Ref(Ref::new(reference));
+                        }
+                    }
+                }
+                (spec_name, spec)
+            })
+            .collect();
+
+        SharedSplit {
+            common: Fragment::new(self.common_file, common),
+            specs,
+            conflicts,
+        }
+    }
+}
+
+/// The definitions of a schema with the same content.
+#[derive(Debug)]
+struct Definition {
+    schema: RefOr<utoipa::openapi::Schema>,
+    content: Value,
+    specs: Vec<String>,
+}
+
+/// The result of [`SharedSchemas::split`].
+#[derive(Debug, Clone)]
+pub struct SharedSplit {
+    /// The common components, holding the shared schemas.
+    pub common: Fragment<Components>,
+
+    /// The specifications, in insertion order, with `$ref` references to the common file.
+    pub specs: Vec<(String, OpenApi)>,
+
+    /// The schemas defined differently by several specifications.
+    pub conflicts: Vec<SchemaConflict>,
+}
+
+impl SharedSplit {
+    /// Returns the specification with the given name.
+    pub fn spec(&self, name: &str) -> Option<&OpenApi> {
+        self.specs
+            .iter()
+            .find(|(spec_name, _)| spec_name == name)
+            .map(|(_, spec)| spec)
+    }
+
+    /// Returns a specification with the common file as fragment, e.g. to
+    /// [`write_to`](SplitResult::write_to) a directory.
+    pub fn split_result(&self, name: &str) -> Option<SplitResult<Components>> {
+        let mut result = SplitResult::new(self.spec(name)?.clone());
+        if !self.common.content.schemas.is_empty() {
+            result.add_fragment(self.common.clone());
+        }
+        Some(result)
+    }
+
+    /// Returns a human-readable report of the conflicts, one line per conflict.
+    pub fn conflict_report(&self) -> String {
+        let mut report = String::new();
+        for conflict in &self.conflicts {
+            let _ = writeln!(report, "{conflict}");
+        }
+        report
+    }
+}
+
+/// A schema name with different definitions in several specifications.
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+#[display(
+    "schema `{name}` has {} different definitions: {}",
+    definitions.len(),
+    definitions.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+)]
+pub struct SchemaConflict {
+    /// The schema name.
+    pub name: String,
+    /// The different definitions.
+    pub definitions: Vec<SchemaDefinition>,
+}
+
+/// A definition of a conflicting schema.
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+#[display("{hash} in {}", specs.join(", "))]
+pub struct SchemaDefinition {
+    /// The hash of the schema content, to tell the definitions apart in reports.
+    pub hash: String,
+    /// The specifications with this definition.
+    pub specs: Vec<String>,
+}
+
+/// Returns the FNV-1a hash of the canonical JSON of a schema.
+///
+/// The hash only identifies the definitions in reports, they are compared by content.
+fn content_hash(canonical: &str) -> String {
+    let hash = canonical
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec(schemas: Value) -> OpenApi {
+        serde_json::from_value(json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {},
+            "components": {"schemas": schemas}
+        }))
+        .expect("should be a valid spec")
+    }
+
+    fn schema_names(components: &Components) -> Vec<&str> {
+        components.schemas.keys().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn should_share_identical_schemas() {
+        let users = spec(json!({
+            "Error": {"type": "object", "properties": {"code": {"$ref": "#/components/schemas/Code"}}},
+            "Code": {"type": "integer"},
+            "User": {"type": "object"}
+        }));
+        let orders = spec(json!({
+            "Error": {"type": "object", "properties": {"code": {"$ref": "#/components/schemas/Code"}}},
+            "Code": {"type": "integer"},
+            "Order": {"type": "object"}
+        }));
+
+        let shared = SharedSchemas::new("common.yaml")
+            .with_spec("users", users)
+            .with_spec("orders", orders)
+            .split();
+
+        assert_eq!(schema_names(&shared.common.content), vec!["Code", "Error"]);
+        assert!(shared.conflicts.is_empty());
+        let users = serde_json::to_value(shared.spec("users")).expect("should serialize");
+        assert_eq!(
+            users["components"]["schemas"],
+            json!({
+                "Code": {"$ref": "common.yaml#/components/schemas/Code"},
+                "Error": {"$ref": "common.yaml#/components/schemas/Error"},
+                "User": {"type": "object"}
+            })
+        );
+    }
+
+    #[test]
+    fn should_report_conflicting_schemas() {
+        let shared = SharedSchemas::new("common.yaml")
+            .with_spec("users", spec(json!({"Id": {"type": "integer"}})))
+            .with_spec("orders", spec(json!({"Id": {"type": "string"}})))
+            .with_spec("admin", spec(json!({"Id": {"type": "integer"}})))
+            .split();
+
+        assert!(shared.common.content.schemas.is_empty());
+        insta::assert_snapshot!(shared.conflict_report(), @"schema `Id` has 2 different definitions: 0ff906d1666eb6a5 in users, admin; 37140e51efa8b132 in orders");
+    }
+
+    #[test]
+    fn should_not_share_schemas_referencing_local_schemas() {
+        let page = json!({"type": "array", "items": {"$ref": "#/components/schemas/Item"}});
+        let shared = SharedSchemas::new("common.yaml")
+            .with_spec(
+                "users",
+                spec(json!({"Page": page, "Item": {"type": "integer"}})),
+            )
+            .with_spec(
+                "orders",
+                spec(json!({"Page": page, "Item": {"type": "string"}})),
+            )
+            .split();
+
+        assert!(shared.common.content.schemas.is_empty());
+        assert_eq!(shared.conflicts.len(), 1);
+        assert_eq!(shared.conflicts[0].name, "Item");
+    }
+
+    #[test]
+    fn should_create_split_results() {
+        let shared = SharedSchemas::new("../common.yaml")
+            .with_spec("users", spec(json!({"Error": {"type": "string"}})))
+            .with_spec("orders", spec(json!({"Error": {"type": "string"}})))
+            .split();
+
+        let result = shared.split_result("users").expect("should find the spec");
+
+        assert_eq!(result.fragment_count(), 1);
+        assert_eq!(result.fragments[0].path, PathBuf::from("../common.yaml"));
+        assert!(shared.split_result("unknown").is_none());
+    }
+}
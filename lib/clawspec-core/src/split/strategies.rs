@@ -3,26 +3,12 @@
 use std::collections::{BTreeMap, BTreeSet};
 use std::path::PathBuf;
 
-use utoipa::openapi::path::{Operation, PathItem};
+use serde_json::Value;
 use utoipa::openapi::{Components, OpenApi, Ref, RefOr};
 
+use super::write::reference_path;
 use super::{Fragment, OpenApiSplitter, SplitResult};
-
-/// Helper to iterate over all operations in a PathItem.
-fn iter_operations(path_item: &PathItem) -> impl Iterator<Item = &Operation> {
-    [
-        path_item.get.as_ref(),
-        path_item.put.as_ref(),
-        path_item.post.as_ref(),
-        path_item.delete.as_ref(),
-        path_item.options.as_ref(),
-        path_item.head.as_ref(),
-        path_item.patch.as_ref(),
-        path_item.trace.as_ref(),
-    ]
-    .into_iter()
-    .flatten()
-}
+use crate::operations::iter_operations;
 
 /// Splits schemas based on which tags use them.
 ///
@@ -32,6 +18,10 @@ fn iter_operations(path_item: &PathItem) -> impl Iterator<Item = &Operation> {
 /// - Schemas used by only one tag go into a file named after that tag
 /// - Schemas used by multiple tags go into a common file
 ///
+/// A schema is used by the tags of all operations reaching it, from a request body, a
+/// response, or a parameter, directly or through other schemas. Nested types therefore
+/// follow the schemas using them.
+///
 /// # Example
 ///
 /// ```rust,ignore
@@ -73,72 +63,56 @@ impl SplitSchemasByTag {
         self
     }
 
-    /// Analyzes which tags reference which schemas.
+    /// Analyzes which tags reference which schemas, directly or through other schemas.
     fn analyze_schema_usage(&self, spec: &OpenApi) -> BTreeMap<String, BTreeSet<String>> {
         let mut schema_to_tags: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let graph = schema_graph(spec);
+        let responses: BTreeMap<&str, Value> = spec
+            .components
+            .iter()
+            .flat_map(|components| &components.responses)
+            .filter_map(|(name, response)| {
+                Some((name.as_str(), serde_json::to_value(response).ok()?))
+            })
+            .collect();
 
         // Iterate through all paths and operations
         for path_item in spec.paths.paths.values() {
-            for operation in iter_operations(path_item) {
+            let path_parameters = serde_json::to_value(&path_item.parameters).unwrap_or_default();
+            for (_, operation) in iter_operations(path_item) {
                 let tags = operation.tags.clone().unwrap_or_default();
                 if tags.is_empty() {
                     continue;
                 }
 
-                // Collect schema references from request body
-                if let Some(ref request_body) = operation.request_body {
-                    for content in request_body.content.values() {
-                        if let Some(ref schema) = content.schema {
-                            self.collect_schema_refs(schema, &tags, &mut schema_to_tags);
-                        }
-                    }
-                }
-
-                // Collect schema references from responses
-                for response in operation.responses.responses.values() {
-                    if let RefOr::T(resp) = response {
-                        for content in resp.content.values() {
-                            if let Some(ref schema) = content.schema {
-                                self.collect_schema_refs(schema, &tags, &mut schema_to_tags);
-                            }
-                        }
-                    }
-                }
-
-                // Collect schema references from parameters
-                if let Some(ref parameters) = operation.parameters {
-                    for param in parameters {
-                        if let Some(ref schema) = param.schema {
-                            self.collect_schema_refs(schema, &tags, &mut schema_to_tags);
-                        }
+                // Collect references from bodies, responses, and parameters, inline or not
+                let Ok(operation) = serde_json::to_value(operation) else {
+                    continue;
+                };
+                let mut refs = BTreeSet::new();
+                collect_refs(&operation, &mut refs);
+                collect_refs(&path_parameters, &mut refs);
+                for reference in refs.clone() {
+                    if let Some(response) = reference
+                        .strip_prefix(RESPONSE_PREFIX)
+                        .and_then(|name| responses.get(name))
+                    {
+                        collect_refs(response, &mut refs);
                     }
                 }
-            }
-        }
 
-        schema_to_tags
-    }
-
-    /// Collects schema references from a schema, adding tag associations.
-    fn collect_schema_refs(
-        &self,
-        schema: &RefOr<utoipa::openapi::Schema>,
-        tags: &[String],
-        schema_to_tags: &mut BTreeMap<String, BTreeSet<String>>,
-    ) {
-        match schema {
-            RefOr::Ref(r) => {
-                if let Some(name) = extract_schema_name(&r.ref_location) {
+                // Schemas reached through other schemas belong to the operation too
+                let roots = refs.iter().copied().filter_map(extract_schema_name);
+                for name in reachable_schemas(&graph, roots) {
                     let entry = schema_to_tags.entry(name).or_default();
-                    for tag in tags {
+                    for tag in &tags {
                         entry.insert(tag.clone());
                     }
                 }
             }
-            RefOr::T(_) => {
-                // Inline schema, no reference to extract
-            }
         }
+
+        schema_to_tags
     }
 
     /// Determines the target file for a schema based on its tag usage.
@@ -163,7 +137,7 @@ impl SplitSchemasByTag {
     fn create_external_ref(file_path: &std::path::Path, schema_name: &str) -> String {
         format!(
             "{}#/components/schemas/{}",
-            file_path.display(),
+            reference_path(file_path),
             schema_name
         )
     }
@@ -321,7 +295,7 @@ where
         for name in &schemas_to_extract {
             let external_ref = format!(
                 "{}#/components/schemas/{}",
-                self.target_file.display(),
+                reference_path(&self.target_file),
                 name
             );
             components
@@ -337,6 +311,67 @@ where
     }
 }
 
+const RESPONSE_PREFIX: &str = "#/components/responses/";
+
+/// Collects the `$ref` strings of a value, at any depth.
+pub(super) fn collect_refs<'a>(value: &'a Value, refs: &mut BTreeSet<&'a str>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(reference)) = map.get("$ref") {
+                refs.insert(reference);
+            }
+            for child in map.values() {
+                collect_refs(child, refs);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_refs(item, refs);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the schemas referenced by each component schema.
+fn schema_graph(spec: &OpenApi) -> BTreeMap<String, BTreeSet<String>> {
+    let Some(components) = &spec.components else {
+        return BTreeMap::new();
+    };
+    components
+        .schemas
+        .iter()
+        .map(|(name, schema)| {
+            let value = serde_json::to_value(schema).unwrap_or_default();
+            let mut refs = BTreeSet::new();
+            collect_refs(&value, &mut refs);
+            let dependencies = refs.into_iter().filter_map(extract_schema_name).collect();
+            (name.clone(), dependencies)
+        })
+        .collect()
+}
+
+/// Returns the schemas reachable from the roots, including the roots.
+fn reachable_schemas(
+    graph: &BTreeMap<String, BTreeSet<String>>,
+    roots: impl IntoIterator<Item = String>,
+) -> BTreeSet<String> {
+    let mut reached = BTreeSet::new();
+    let mut pending: Vec<String> = roots.into_iter().collect();
+    while let Some(name) = pending.pop() {
+        if let Some(dependencies) = graph.get(&name) {
+            pending.extend(
+                dependencies
+                    .iter()
+                    .filter(|dependency| !reached.contains(*dependency))
+                    .cloned(),
+            );
+        }
+        reached.insert(name);
+    }
+    reached
+}
+
 /// Extracts the schema name from a $ref string.
 ///
 /// # Example
@@ -344,7 +379,7 @@ where
 /// ```rust,ignore
 /// assert_eq!(extract_schema_name("#/components/schemas/User"), Some("User".to_string()));
 /// ```
-fn extract_schema_name(ref_location: &str) -> Option<String> {
+pub(super) fn extract_schema_name(ref_location: &str) -> Option<String> {
     const SCHEMA_PREFIX: &str = "#/components/schemas/";
     ref_location
         .strip_prefix(SCHEMA_PREFIX)
@@ -1057,4 +1092,112 @@ mod tests {
             );
         }
     }
+
+    fn create_nested_spec() -> OpenApi {
+        serde_json::from_value(serde_json::json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {
+                "/users": {"get": {
+                    "tags": ["users"],
+                    "responses": {"200": {
+                        "description": "ok",
+                        "content": {"application/json": {"schema": {
+                            "type": "array",
+                            "items": {"$ref": "#/components/schemas/User"}
+                        }}}
+                    }}
+                }},
+                "/orders": {"post": {
+                    "tags": ["orders"],
+                    "requestBody": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/Order"}}}},
+                    "responses": {"404": {"$ref": "#/components/responses/NotFound"}}
+                }}
+            },
+            "components": {
+                "schemas": {
+                    "User": {"type": "object", "properties": {"address": {"$ref": "#/components/schemas/Address"}}},
+                    "Order": {"type": "object", "properties": {
+                        "items": {"type": "array", "items": {"$ref": "#/components/schemas/OrderItem"}},
+                        "shipping": {"oneOf": [{"$ref": "#/components/schemas/Address"}, {"type": "null"}]}
+                    }},
+                    "OrderItem": {"type": "object", "properties": {"order": {"$ref": "#/components/schemas/Order"}}},
+                    "Address": {"type": "object"},
+                    "Problem": {"type": "object"},
+                    "Unused": {"type": "object"}
+                },
+                "responses": {"NotFound": {
+                    "description": "Not found",
+                    "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Problem"}}}
+                }}
+            }
+        }))
+        .expect("should be a valid spec")
+    }
+
+    #[test]
+    fn should_analyze_transitive_schema_usage() {
+        let spec = create_nested_spec();
+        let splitter = SplitSchemasByTag::new("common.yaml");
+
+        let usage = splitter.analyze_schema_usage(&spec);
+
+        insta::assert_debug_snapshot!(usage, @r#"
+        {
+            "Address": {
+                "orders",
+                "users",
+            },
+            "Order": {
+                "orders",
+            },
+            "OrderItem": {
+                "orders",
+            },
+            "Problem": {
+                "orders",
+            },
+            "User": {
+                "users",
+            },
+        }
+        "#);
+    }
+
+    #[test]
+    fn should_keep_nested_schemas_with_their_users() {
+        let spec = create_nested_spec();
+        let splitter = SplitSchemasByTag::new("common.yaml");
+
+        let result = splitter.split(spec);
+
+        let files: BTreeMap<_, Vec<_>> = result
+            .fragments
+            .iter()
+            .map(|fragment| {
+                let names = fragment.content.schemas.keys().cloned().collect();
+                (fragment.path.display().to_string(), names)
+            })
+            .collect();
+        insta::assert_debug_snapshot!(files, @r#"
+        {
+            "common.yaml": [
+                "Address",
+            ],
+            "orders.yaml": [
+                "Order",
+                "OrderItem",
+                "Problem",
+            ],
+            "users.yaml": [
+                "User",
+            ],
+        }
+        "#);
+        let components = result.main.components.expect("should have components");
+        assert!(matches!(
+            components.schemas.get("Unused"),
+            Some(RefOr::T(_))
+        ));
+    }
 }
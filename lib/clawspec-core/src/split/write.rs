@@ -0,0 +1,648 @@
+//! Writing of split OpenAPI specifications to disk.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use super::SplitResult;
+use super::bundle::{load_file, normalize};
+
+/// The sections of OpenAPI components, used to recognize fragment files.
+const COMPONENT_SECTIONS: &[&str] = &[
+    "schemas",
+    "responses",
+    "parameters",
+    "examples",
+    "requestBodies",
+    "headers",
+    "securitySchemes",
+    "links",
+    "callbacks",
+    "pathItems",
+];
+
+/// The serialization format of written specification files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SplitFormat {
+    /// Pretty-printed JSON.
+    #[default]
+    Json,
+    /// YAML, requires the `yaml` feature.
+    Yaml,
+}
+
+/// Error type for [`SplitResult::write_to`].
+#[derive(Debug, derive_more::Error, derive_more::Display)]
+pub enum SplitWriteError {
+    /// A file or directory cannot be read or written.
+    #[display("cannot write {}: {source}", path.display())]
+    Io {
+        /// The path of the file or directory.
+        path: PathBuf,
+        /// The underlying I/O error.
+        source: std::io::Error,
+    },
+
+    /// A file path is absolute, or escapes the output directory.
+    #[display("invalid path {}, expected a relative path inside the output directory", path.display())]
+    InvalidPath {
+        /// The offending path.
+        #[error(not(source))]
+        path: PathBuf,
+    },
+
+    /// Several files of the split result would be written to the same path.
+    #[display("several files would be written to {}", path.display())]
+    DuplicatePath {
+        /// The duplicated path, relative to the output directory.
+        #[error(not(source))]
+        path: PathBuf,
+    },
+
+    /// A file that is not an OpenAPI document already exists at a written path.
+    #[display("refusing to overwrite {}, it is not an OpenAPI document", path.display())]
+    WouldOverwrite {
+        /// The existing file.
+        #[error(not(source))]
+        path: PathBuf,
+    },
+
+    /// A file content cannot be serialized.
+    #[display("cannot serialize {}: {message}", path.display())]
+    Serialize {
+        /// The path of the file.
+        path: PathBuf,
+        /// The serializer error message.
+        message: String,
+    },
+}
+
+impl<T: Serialize> SplitResult<T> {
+    /// Writes the main specification and the fragments into a directory.
+    ///
+    /// The main specification is written to `dir/main_file`, and each fragment to its
+    /// [`path`](super::Fragment::path), relative to the directory of the main file.
+    ///
+    /// References of the fragments are rewritten relative to the fragment file:
+    ///
+    /// - a local component reference (`#/components/schemas/Error`) to a component moved to
+    ///   another file, or kept in the main specification, points to that file,
+    ///   e.g. `common.yaml#/components/schemas/Error` from `schemas/users.yaml`;
+    /// - a relative external reference, which is relative to the main file like the
+    ///   fragment paths, is rebased on the fragment directory.
+    ///
+    /// The files are written with the given `format`, whatever their extension.
+    ///
+    /// Existing files are only overwritten if they are OpenAPI documents, components, or
+    /// path items, typically from a previous run. Nothing is written if a check fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use clawspec_core::split::{OpenApiSplitter, SplitFormat, SplitSchemasByTag};
+    /// # use utoipa::openapi::OpenApi;
+    ///
+    /// # fn example(spec: OpenApi) -> Result<(), Box<dyn std::error::Error>> {
+    /// let splitter = SplitSchemasByTag::new("common.json").with_schemas_dir("schemas");
+    /// let result = splitter.split(spec);
+    ///
+    /// // Writes docs/openapi.json, docs/schemas/common.json, docs/schemas/{tag}.json, ...
+    /// result.write_to("docs", "openapi.json", SplitFormat::Json)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SplitWriteError`] if a path is invalid or duplicated, if an unrelated
+    /// file would be overwritten, or if a file cannot be serialized or written.
+    pub fn write_to(
+        &self,
+        dir: impl AsRef<Path>,
+        main_file: impl AsRef<Path>,
+        format: SplitFormat,
+    ) -> Result<(), SplitWriteError> {
+        let dir = dir.as_ref();
+        let main_path = output_path(Path::new(""), main_file.as_ref())?;
+        let main_dir = main_path.parent().unwrap_or(Path::new("")).to_path_buf();
+
+        let main = serde_json::to_value(&self.main).map_err(|err| SplitWriteError::Serialize {
+            path: main_path.clone(),
+            message: err.to_string(),
+        })?;
+
+        let mut fragments = Vec::with_capacity(self.fragments.len());
+        for fragment in &self.fragments {
+            let path = output_path(&main_dir, &fragment.path)?;
+            let content = serde_json::to_value(&fragment.content).map_err(|err| {
+                SplitWriteError::Serialize {
+                    path: path.clone(),
+                    message: err.to_string(),
+                }
+            })?;
+            fragments.push((path, content));
+        }
+
+        let mut paths = BTreeSet::from([main_path.clone()]);
+        for (path, _) in &fragments {
+            if !paths.insert(path.clone()) {
+                return Err(SplitWriteError::DuplicatePath { path: path.clone() });
+            }
+        }
+        for path in &paths {
+            let path = dir.join(path);
+            if path.exists() && !is_spec_document(&path) {
+                return Err(SplitWriteError::WouldOverwrite { path });
+            }
+        }
+
+        let rewriter = RefRewriter::new(&main, &main_path, &fragments);
+        let mut outputs = vec![(
+            main_path.clone(),
+            serialize(&self.main, &main_path, format)?,
+        )];
+        for (path, content) in &fragments {
+            let mut content = content.clone();
+            rewriter.rewrite(&mut content, path);
+            outputs.push((path.clone(), serialize(&content, path, format)?));
+        }
+
+        for (path, contents) in outputs {
+            let path = dir.join(path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).map_err(|source| SplitWriteError::Io {
+                    path: parent.to_path_buf(),
+                    source,
+                })?;
+            }
+            fs::write(&path, contents).map_err(|source| SplitWriteError::Io { path, source })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the path of a file relative to the output directory.
+fn output_path(base: &Path, path: &Path) -> Result<PathBuf, SplitWriteError> {
+    let invalid = || SplitWriteError::InvalidPath {
+        path: path.to_path_buf(),
+    };
+    if path.has_root() || path.components().any(|c| matches!(c, Component::Prefix(_))) {
+        return Err(invalid());
+    }
+    let normalized = normalize(&base.join(path));
+    if normalized.as_os_str().is_empty() || normalized.starts_with("..") {
+        return Err(invalid());
+    }
+    Ok(normalized)
+}
+
+/// Returns `true` if the file holds an OpenAPI document, or a fragment written by the
+/// splitters.
+///
+/// A document has an `openapi`, `paths`, or `components` key. A fragment is not empty,
+/// and only holds components sections, or path items, as objects.
+fn is_spec_document(path: &Path) -> bool {
+    let Ok(Value::Object(document)) = load_file(path) else {
+        return false;
+    };
+    if ["openapi", "paths", "components"]
+        .iter()
+        .any(|key| document.contains_key(*key))
+    {
+        return true;
+    }
+    !document.is_empty()
+        && document.values().all(Value::is_object)
+        && (document
+            .keys()
+            .all(|key| COMPONENT_SECTIONS.contains(&key.as_str()))
+            || document.keys().all(|key| key.starts_with('/')))
+}
+
+fn serialize<S: Serialize>(
+    content: &S,
+    path: &Path,
+    format: SplitFormat,
+) -> Result<String, SplitWriteError> {
+    let result = match format {
+        SplitFormat::Json => serde_json::to_string_pretty(content).map_err(|err| err.to_string()),
+        SplitFormat::Yaml => to_yaml(content),
+    };
+    result.map_err(|message| SplitWriteError::Serialize {
+        path: path.to_path_buf(),
+        message,
+    })
+}
+
+#[cfg(feature = "yaml")]
+fn to_yaml<S: Serialize>(content: &S) -> Result<String, String> {
+    crate::ToYaml::to_yaml(content).map_err(|err| err.to_string())
+}
+
+#[cfg(not(feature = "yaml"))]
+fn to_yaml<S: Serialize>(_content: &S) -> Result<String, String> {
+    Err("writing YAML files requires the `yaml` feature".to_string())
+}
+
+/// Rewrites the references of fragments relative to the fragment files.
+struct RefRewriter<'a> {
+    main: &'a Value,
+    main_path: &'a Path,
+    /// The file of the components moved out of the main specification.
+    locations: BTreeMap<(String, String), PathBuf>,
+    fragments: BTreeMap<&'a Path, &'a Value>,
+}
+
+impl<'a> RefRewriter<'a> {
+    fn new(main: &'a Value, main_path: &'a Path, fragments: &'a [(PathBuf, Value)]) -> Self {
+        let main_dir = main_path.parent().unwrap_or(Path::new(""));
+        let mut locations = BTreeMap::new();
+        if let Some(Value::Object(sections)) = main.get("components") {
+            for (section, entries) in sections {
+                let Value::Object(entries) = entries else {
+                    continue;
+                };
+                for (name, entry) in entries {
+                    let Some(reference) = entry.get("$ref").and_then(Value::as_str) else {
+                        continue;
+                    };
+                    let Some((file, _)) = reference.split_once('#') else {
+                        continue;
+                    };
+                    if !file.is_empty() && !reference.contains("://") {
+                        locations.insert(
+                            (section.clone(), name.clone()),
+                            normalize(&main_dir.join(file)),
+                        );
+                    }
+                }
+            }
+        }
+
+        Self {
+            main,
+            main_path,
+            locations,
+            fragments: fragments
+                .iter()
+                .map(|(path, content)| (path.as_path(), content))
+                .collect(),
+        }
+    }
+
+    fn rewrite(&self, value: &mut Value, file: &Path) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(reference)) = map.get_mut("$ref") {
+                    if let Some(rewritten) = self.rewrite_reference(reference, file) {
+                        *reference = rewritten;
+                    }
+                    return;
+                }
+                for child in map.values_mut() {
+                    self.rewrite(child, file);
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.rewrite(item, file);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn rewrite_reference(&self, reference: &str, file: &Path) -> Option<String> {
+        if reference.contains("://") {
+            return None;
+        }
+        let (target, pointer) = reference.split_once('#').unwrap_or((reference, ""));
+
+        let target = if target.is_empty() {
+            let (section, name) = component_key(pointer)?;
+            if self.defines(file, &section, &name) {
+                return None;
+            }
+            match self.locations.get(&(section.clone(), name.clone())) {
+                Some(location) => location.clone(),
+                None if self.defines(self.main_path, &section, &name) => {
+                    self.main_path.to_path_buf()
+                }
+                None => return None,
+            }
+        } else {
+            let main_dir = self.main_path.parent().unwrap_or(Path::new(""));
+            normalize(&main_dir.join(target))
+        };
+
+        if target == file {
+            return Some(format!("#{pointer}"));
+        }
+        let relative = relative_path(file.parent().unwrap_or(Path::new("")), &target);
+        if pointer.is_empty() {
+            Some(relative)
+        } else {
+            Some(format!("{relative}#{pointer}"))
+        }
+    }
+
+    /// Returns `true` if the file defines the component, possibly as bare components.
+    fn defines(&self, file: &Path, section: &str, name: &str) -> bool {
+        let document = if file == self.main_path {
+            Some(self.main)
+        } else {
+            self.fragments.get(file).copied()
+        };
+        let Some(document) = document else {
+            return false;
+        };
+        let components = document.get("components").unwrap_or(document);
+        if components
+            .get(section)
+            .and_then(|entries| entries.get(name))
+            .is_none()
+        {
+            return false;
+        }
+        // The main specification holds references to the moved components
+        file != self.main_path
+            || !self
+                .locations
+                .contains_key(&(section.to_string(), name.to_string()))
+    }
+}
+
+/// Returns the `(section, name)` of a component pointer.
+fn component_key(pointer: &str) -> Option<(String, String)> {
+    let rest = pointer.strip_prefix("/components/")?;
+    let (section, name) = rest.split_once('/')?;
+    if name.contains('/') {
+        return None;
+    }
+    Some((section.to_string(), name.to_string()))
+}
+
+/// Returns a path as written in a `$ref`, with `/` separators.
+pub(super) fn reference_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Returns the path of `to` relative to the directory `from`, with `/` separators.
+///
+/// Both paths are normalized and relative to the same directory.
+fn relative_path(from: &Path, to: &Path) -> String {
+    let from: Vec<_> = from.components().collect();
+    let to: Vec<_> = to.components().collect();
+    let common = from
+        .iter()
+        .zip(&to)
+        .take_while(|(from, to)| from == to)
+        .count();
+
+    let parents = std::iter::repeat_n("..".to_string(), from.len() - common);
+    let rest = std::iter::once(reference_path(&to[common..].iter().collect::<PathBuf>()));
+    parents
+        .chain(rest)
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::split::{Fragment, OpenApiSplitter, SplitSchemasByTag, bundle};
+    use serde_json::json;
+    use utoipa::openapi::{Components, OpenApi};
+
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            let dir = std::env::temp_dir().join(format!("clawspec-write-{}", uuid::Uuid::new_v4()));
+            fs::create_dir_all(&dir).expect("should create the directory");
+            Self(dir)
+        }
+
+        fn read(&self, path: &str) -> Value {
+            let contents = fs::read_to_string(self.0.join(path)).expect("should read");
+            serde_json::from_str(&contents).expect("should be JSON")
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn spec(value: Value) -> OpenApi {
+        serde_json::from_value(value).expect("should be a valid spec")
+    }
+
+    fn split_spec() -> OpenApi {
+        let response = |schema: &str| {
+            json!({"200": {
+                "description": "ok",
+                "content": {"application/json": {"schema": {"$ref": format!("#/components/schemas/{schema}")}}}
+            }})
+        };
+        spec(json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {
+                "/users": {"get": {"tags": ["users"], "responses": response("User")}},
+                "/orders": {"get": {"tags": ["orders"], "responses": response("Order")}},
+                "/errors": {"get": {"tags": ["users", "orders"], "responses": response("Error")}}
+            },
+            "components": {"schemas": {
+                "User": {"type": "object", "properties": {"error": {"$ref": "#/components/schemas/Error"}}},
+                "Order": {"type": "object", "properties": {"owner": {"$ref": "#/components/schemas/Owner"}}},
+                "Owner": {"type": "string"},
+                "Error": {"type": "string"}
+            }}
+        }))
+    }
+
+    #[test]
+    fn should_compute_relative_paths() {
+        assert_eq!(
+            relative_path(Path::new("schemas"), Path::new("schemas/common.json")),
+            "common.json"
+        );
+        assert_eq!(
+            relative_path(Path::new("schemas/users"), Path::new("common/errors.json")),
+            "../../common/errors.json"
+        );
+        assert_eq!(
+            relative_path(Path::new(""), Path::new("openapi.json")),
+            "openapi.json"
+        );
+    }
+
+    #[test]
+    fn should_write_fragments_with_relative_references() {
+        let dir = TempDir::new();
+        let result = SplitSchemasByTag::new("common.json")
+            .with_schemas_dir("schemas")
+            .split(split_spec());
+
+        result
+            .write_to(&dir.0, "openapi.json", SplitFormat::Json)
+            .expect("should write");
+
+        let main = dir.read("openapi.json");
+        assert_eq!(
+            main["components"]["schemas"]["User"],
+            json!({"$ref": "schemas/users.yaml#/components/schemas/User"})
+        );
+        // Tag files are YAML files, holding JSON here
+        let users = dir.read("schemas/users.yaml");
+        assert_eq!(
+            users["schemas"]["User"]["properties"]["error"],
+            json!({"$ref": "common.json#/components/schemas/Error"})
+        );
+        let orders = dir.read("schemas/orders.yaml");
+        assert_eq!(
+            orders["schemas"]["Order"]["properties"]["owner"],
+            json!({"$ref": "#/components/schemas/Owner"})
+        );
+
+        let bundled = bundle(dir.0.join("openapi.json")).expect("should bundle");
+        assert_eq!(
+            bundled,
+            serde_json::to_value(split_spec()).expect("should serialize")
+        );
+    }
+
+    #[test]
+    fn should_reference_components_kept_in_the_main_file() {
+        let dir = TempDir::new();
+        let mut result = SplitResult::new(spec(json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Test", "version": "1.0"},
+            "paths": {},
+            "components": {"schemas": {
+                "Error": {"type": "string"},
+                "User": {"$ref": "../schemas/users.json#/components/schemas/User"}
+            }}
+        })));
+        let users: Components = serde_json::from_value(json!({"schemas": {
+            "User": {"type": "object", "properties": {"error": {"$ref": "#/components/schemas/Error"}}}
+        }}))
+        .expect("should be valid components");
+        result.add_fragment(Fragment::new("../schemas/users.json", users));
+
+        result
+            .write_to(&dir.0, "api/openapi.json", SplitFormat::Json)
+            .expect("should write");
+
+        let users = dir.read("schemas/users.json");
+        assert_eq!(
+            users["schemas"]["User"]["properties"]["error"],
+            json!({"$ref": "../api/openapi.json#/components/schemas/Error"})
+        );
+    }
+
+    #[test]
+    fn should_overwrite_previous_output() {
+        let dir = TempDir::new();
+        let result = SplitSchemasByTag::new("common.json").split(split_spec());
+
+        result
+            .write_to(&dir.0, "openapi.json", SplitFormat::Json)
+            .expect("should write");
+        result
+            .write_to(&dir.0, "openapi.json", SplitFormat::Json)
+            .expect("should overwrite the previous output");
+    }
+
+    #[test]
+    fn should_refuse_to_overwrite_unrelated_files() {
+        let dir = TempDir::new();
+        fs::write(dir.0.join("common.json"), "not a spec").expect("should write");
+        let result = SplitSchemasByTag::new("common.json").split(split_spec());
+
+        let error = result
+            .write_to(&dir.0, "openapi.json", SplitFormat::Json)
+            .expect_err("should refuse");
+
+        assert!(matches!(error, SplitWriteError::WouldOverwrite { .. }));
+        assert!(!dir.0.join("openapi.json").exists());
+    }
+
+    #[test]
+    fn should_refuse_to_overwrite_other_json_documents() {
+        for contents in [
+            "{}",
+            r#"{"name": "my-app"}"#,
+            r#"{"schemas": "not components"}"#,
+        ] {
+            let dir = TempDir::new();
+            fs::write(dir.0.join("common.json"), contents).expect("should write");
+            let result = SplitSchemasByTag::new("common.json").split(split_spec());
+
+            let error = result
+                .write_to(&dir.0, "openapi.json", SplitFormat::Json)
+                .expect_err("should refuse");
+
+            assert!(matches!(error, SplitWriteError::WouldOverwrite { .. }));
+        }
+    }
+
+    #[test]
+    fn should_reject_invalid_paths() {
+        let result: SplitResult<Components> = SplitResult::new(split_spec());
+
+        let error = result
+            .write_to("target", "../openapi.json", SplitFormat::Json)
+            .expect_err("should reject the path");
+
+        insta::assert_snapshot!(error, @"invalid path ../openapi.json, expected a relative path inside the output directory");
+    }
+
+    #[test]
+    fn should_reject_duplicate_paths() {
+        let mut result = SplitResult::new(split_spec());
+        result.add_fragment(Fragment::new("./openapi.json", Components::new()));
+
+        let error = result
+            .write_to("target", "openapi.json", SplitFormat::Json)
+            .expect_err("should reject the path");
+
+        assert!(matches!(error, SplitWriteError::DuplicatePath { .. }));
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn should_write_yaml() {
+        let dir = TempDir::new();
+        let result = SplitSchemasByTag::new("common.yaml").split(split_spec());
+
+        result
+            .write_to(&dir.0, "openapi.yaml", SplitFormat::Yaml)
+            .expect("should write");
+
+        let users = fs::read_to_string(dir.0.join("users.yaml")).expect("should read");
+        insta::assert_snapshot!(users, @r#"
+        schemas:
+          User:
+            properties:
+              error:
+                $ref: "common.yaml#/components/schemas/Error"
+            type: object
+        "#);
+        let bundled = bundle(dir.0.join("openapi.yaml")).expect("should bundle");
+        assert_eq!(
+            bundled,
+            serde_json::to_value(split_spec()).expect("should serialize")
+        );
+    }
+}
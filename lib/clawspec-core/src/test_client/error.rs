@@ -78,6 +78,28 @@ pub enum TestAppError {
     /// launch failures and health check errors.
     #[display("Server error: {_0}")]
     ServerError(Box<dyn std::error::Error + Send + Sync + 'static>),
+
+    /// TLS setup failed.
+    ///
+    /// This occurs when generating the test certificates, or when configuring
+    /// the HTTPS endpoint of a test server.
+    #[cfg(feature = "tls")]
+    #[from(ignore)]
+    #[display("TLS error: {error}")]
+    TlsError {
+        /// Detailed error message from the TLS library.
+        error: String,
+    },
+}
+
+#[cfg(feature = "tls")]
+impl TestAppError {
+    /// Creates a TLS error from any error type that implements Display.
+    pub(crate) fn tls_error(error: impl std::fmt::Display) -> Self {
+        Self::TlsError {
+            error: error.to_string(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -58,9 +58,12 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use backon::{ExponentialBuilder, Retryable};
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 
 use crate::ApiClient;
+use crate::docs::{to_markdown, write_html};
+use crate::downgrade::{OpenApi30, SpecVersion, downgrade_to_3_0};
+use crate::export::PactSpecification;
 
 mod error;
 pub use self::error::*;
@@ -68,6 +71,11 @@ pub use self::error::*;
 mod test_server;
 pub use self::test_server::*;
 
+#[cfg(feature = "tls")]
+mod tls;
+#[cfg(feature = "tls")]
+pub use self::tls::TestServerTls;
+
 /// A generic test client for async server testing.
 ///
 /// `TestClient<T>` provides a framework-agnostic way to test web servers by wrapping
@@ -264,6 +272,8 @@ pub struct TestClient<T> {
     #[deref_mut]
     client: ApiClient,
     handle: Option<ServerTaskHandle>,
+    #[cfg(feature = "tls")]
+    tls_handle: Option<ServerTaskHandle>,
     #[allow(dead_code)]
     test_server: Arc<T>,
 }
@@ -416,6 +426,22 @@ where
 
         // Build client with comprehensive OpenAPI metadata
         let client = api_client.unwrap_or_else(ApiClient::builder);
+
+        // Call the server over HTTPS, terminating TLS in front of it with a proxy
+        #[cfg(feature = "tls")]
+        let (client, local_addr, tls_handle) = match test_server.tls() {
+            Some(tls) if tls.is_proxied() => {
+                let acceptor = tls.acceptor()?;
+                let tls_listener = tokio::net::TcpListener::bind(addr).await?;
+                let tls_addr = tls_listener.local_addr()?;
+                let tls_handle = tokio::spawn(tls::serve(acceptor, tls_listener, local_addr));
+                let client = tls.configure_client(client)?;
+                (client, tls_addr, Some(ServerTaskHandle::new(tls_handle)))
+            }
+            Some(tls) => (tls.configure_client(client)?, local_addr, None),
+            None => (client, local_addr, None),
+        };
+
         let client = client.with_port(local_addr.port()).build()?;
 
         // Wait until ready with exponential backoff
@@ -440,6 +466,8 @@ where
             local_addr,
             client,
             handle: Some(ServerTaskHandle::new(handle)),
+            #[cfg(feature = "tls")]
+            tls_handle,
             test_server,
         };
         Ok(result)
@@ -661,27 +689,278 @@ where
     ///
     /// The specification follows OpenAPI 3.1 format and can be used with various
     /// tools for documentation generation, client generation, and API validation.
-    pub async fn write_openapi(mut self, path: impl AsRef<Path>) -> Result<(), TestAppError> {
+    pub async fn write_openapi(self, path: impl AsRef<Path>) -> Result<(), TestAppError> {
+        self.write_openapi_as(path, SpecVersion::V3_1).await
+    }
+
+    /// Writes the collected OpenAPI specification to a file, in the given OpenAPI version.
+    ///
+    /// This behaves like [`write_openapi`](Self::write_openapi). With [`SpecVersion::V3_0`],
+    /// the specification is converted with [`downgrade_to_3_0`], and the constructs that
+    /// cannot be represented in OpenAPI 3.0 are logged as `tracing` warnings.
+    ///
+    /// Writing an OpenAPI 3.0 specification as YAML requires the `yaml` feature.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use clawspec_core::test_client::{TestClient, TestServer};
+    /// # use clawspec_core::downgrade::SpecVersion;
+    /// # use std::net::TcpListener;
+    /// # #[derive(Debug)] struct MyServer;
+    /// # impl TestServer for MyServer {
+    /// #   type Error = std::io::Error;
+    /// #   async fn launch(&self, listener: TcpListener) -> Result<(), Self::Error> {
+    /// #       listener.set_nonblocking(true)?;
+    /// #       let _tokio_listener = tokio::net::TcpListener::from_std(listener)?;
+    /// #       Ok(())
+    /// #   }
+    /// # }
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = TestClient::start(MyServer).await?;
+    /// client.get("/api/health")?.await?.as_empty().await?;
+    ///
+    /// client.write_openapi_as("docs/openapi-3.0.json", SpecVersion::V3_0).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_openapi_as(
+        mut self,
+        path: impl AsRef<Path>,
+        version: SpecVersion,
+    ) -> Result<(), TestAppError> {
         let path = path.as_ref();
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
 
-        let openapi = self.client.collected_openapi().await;
+        let openapi = self.client.try_collected_openapi().await?;
 
         let ext = path.extension().unwrap_or_default();
-        let contents = if ext == "yml" || ext == "yaml" {
-            openapi.to_yaml().map_err(|err| TestAppError::YamlError {
-                error: format!("{err:#?}"),
-            })?
-        } else {
-            serde_json::to_string_pretty(&openapi)?
+        let yaml = ext == "yml" || ext == "yaml";
+        let contents = match version {
+            SpecVersion::V3_1 if yaml => {
+                openapi.to_yaml().map_err(|err| TestAppError::YamlError {
+                    error: format!("{err:#?}"),
+                })?
+            }
+            SpecVersion::V3_1 => serde_json::to_string_pretty(&openapi)?,
+            SpecVersion::V3_0 => {
+                let downgraded = downgrade_to_3_0(&openapi)?;
+                for warning in downgraded.warnings() {
+                    warn!(%warning, "OpenAPI 3.0 conversion");
+                }
+                if yaml {
+                    downgraded_to_yaml(&downgraded)?
+                } else {
+                    serde_json::to_string_pretty(&downgraded)?
+                }
+            }
         };
 
         fs::write(path, contents)?;
 
         Ok(())
     }
+
+    /// Writes the collected calls as [WireMock](https://wiremock.org/) stub mappings.
+    ///
+    /// The file uses the WireMock `{"mappings": [...]}` JSON format and can be dropped
+    /// into a WireMock `mappings` directory. Parent directories are created if needed.
+    ///
+    /// See [`ApiClient::collected_wiremock_mappings`] for the content of the mappings.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use clawspec_core::test_client::{TestClient, TestServer};
+    /// # use std::net::TcpListener;
+    /// # #[derive(Debug)] struct MyServer;
+    /// # impl TestServer for MyServer {
+    /// #   type Error = std::io::Error;
+    /// #   async fn launch(&self, listener: TcpListener) -> Result<(), Self::Error> {
+    /// #       listener.set_nonblocking(true)?;
+    /// #       let _tokio_listener = tokio::net::TcpListener::from_std(listener)?;
+    /// #       Ok(())
+    /// #   }
+    /// # }
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = TestClient::start(MyServer).await?;
+    /// client.get("/api/health")?.await?.as_empty().await?;
+    ///
+    /// client.write_wiremock_mappings("stubs/mappings/api.json").await?;
+    /// client.write_openapi("docs/openapi.yml").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_wiremock_mappings(
+        &mut self,
+        path: impl AsRef<Path>,
+    ) -> Result<(), TestAppError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mappings = self.client.collected_wiremock_mappings().await;
+        fs::write(path, serde_json::to_string_pretty(&mappings)?)?;
+
+        Ok(())
+    }
+
+    /// Writes the collected calls as a [Pact](https://docs.pact.io/) contract file.
+    ///
+    /// Parent directories are created if needed. See [`ApiClient::collected_pact`]
+    /// for the content of the contract.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use clawspec_core::test_client::{TestClient, TestServer};
+    /// # use clawspec_core::export::PactSpecification;
+    /// # use std::net::TcpListener;
+    /// # #[derive(Debug)] struct MyServer;
+    /// # impl TestServer for MyServer {
+    /// #   type Error = std::io::Error;
+    /// #   async fn launch(&self, listener: TcpListener) -> Result<(), Self::Error> {
+    /// #       listener.set_nonblocking(true)?;
+    /// #       let _tokio_listener = tokio::net::TcpListener::from_std(listener)?;
+    /// #       Ok(())
+    /// #   }
+    /// # }
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = TestClient::start(MyServer).await?;
+    /// client.get("/api/health")?.await?.as_empty().await?;
+    ///
+    /// client
+    ///     .write_pact(
+    ///         "pacts/frontend-backend.json",
+    ///         "frontend",
+    ///         "backend",
+    ///         PactSpecification::V4,
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_pact(
+        &mut self,
+        path: impl AsRef<Path>,
+        consumer: &str,
+        provider: &str,
+        specification: PactSpecification,
+    ) -> Result<(), TestAppError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let pact = self
+            .client
+            .collected_pact(consumer, provider)
+            .await
+            .with_specification(specification);
+        fs::write(path, serde_json::to_string_pretty(&pact)?)?;
+
+        Ok(())
+    }
+
+    /// Writes the collected OpenAPI specification as a Markdown API reference.
+    ///
+    /// Parent directories are created if needed. See [`to_markdown`] for the content
+    /// of the document.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use clawspec_core::test_client::{TestClient, TestServer};
+    /// # use std::net::TcpListener;
+    /// # #[derive(Debug)] struct MyServer;
+    /// # impl TestServer for MyServer {
+    /// #   type Error = std::io::Error;
+    /// #   async fn launch(&self, listener: TcpListener) -> Result<(), Self::Error> {
+    /// #       listener.set_nonblocking(true)?;
+    /// #       let _tokio_listener = tokio::net::TcpListener::from_std(listener)?;
+    /// #       Ok(())
+    /// #   }
+    /// # }
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = TestClient::start(MyServer).await?;
+    /// client.get("/api/health")?.await?.as_empty().await?;
+    ///
+    /// client.write_markdown_docs("docs/API.md").await?;
+    /// client.write_openapi("docs/openapi.yml").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_markdown_docs(
+        &mut self,
+        path: impl AsRef<Path>,
+    ) -> Result<(), TestAppError> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let openapi = self.client.try_collected_openapi().await?;
+        fs::write(path, to_markdown(&openapi))?;
+
+        Ok(())
+    }
+
+    /// Writes the collected OpenAPI specification as a static HTML documentation site.
+    ///
+    /// The pages are written into `dir`, created if needed, starting with `index.html`.
+    /// The site does not fetch anything from the network, so it can be browsed offline
+    /// or published as a CI artifact. See [`to_html_pages`](crate::docs::to_html_pages)
+    /// for the generated pages.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use clawspec_core::test_client::{TestClient, TestServer};
+    /// # use std::net::TcpListener;
+    /// # #[derive(Debug)] struct MyServer;
+    /// # impl TestServer for MyServer {
+    /// #   type Error = std::io::Error;
+    /// #   async fn launch(&self, listener: TcpListener) -> Result<(), Self::Error> {
+    /// #       listener.set_nonblocking(true)?;
+    /// #       let _tokio_listener = tokio::net::TcpListener::from_std(listener)?;
+    /// #       Ok(())
+    /// #   }
+    /// # }
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut client = TestClient::start(MyServer).await?;
+    /// client.get("/api/health")?.await?.as_empty().await?;
+    ///
+    /// client.write_html_docs("target/api-docs").await?;
+    /// client.write_openapi("docs/openapi.yml").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_html_docs(&mut self, dir: impl AsRef<Path>) -> Result<(), TestAppError> {
+        let openapi = self.client.try_collected_openapi().await?;
+        write_html(&openapi, dir)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "yaml")]
+fn downgraded_to_yaml(downgraded: &OpenApi30) -> Result<String, TestAppError> {
+    use crate::ToYaml;
+
+    downgraded.to_yaml().map_err(|err| TestAppError::YamlError {
+        error: format!("{err:#?}"),
+    })
+}
+
+#[cfg(not(feature = "yaml"))]
+fn downgraded_to_yaml(_downgraded: &OpenApi30) -> Result<String, TestAppError> {
+    Err(TestAppError::YamlError {
+        error: "writing an OpenAPI 3.0 specification as YAML requires the `yaml` feature"
+            .to_string(),
+    })
 }
 
 /// Automatic cleanup when TestClient is dropped.
@@ -725,6 +1004,10 @@ impl<T> Drop for TestClient<T> {
         if let Some(handle) = self.handle.take() {
             handle.abort();
         }
+        #[cfg(feature = "tls")]
+        if let Some(handle) = self.tls_handle.take() {
+            handle.abort();
+        }
     }
 }
 
@@ -284,6 +284,37 @@ pub trait TestServer {
     fn config(&self) -> TestServerConfig {
         TestServerConfig::default()
     }
+
+    /// Provide the TLS configuration, to serve the server over HTTPS.
+    ///
+    /// The server terminates TLS on its listener, with the
+    /// [`server_config`](super::TestServerTls::server_config) of the certificates. The
+    /// TestClient configures the ApiClient to trust the certificate, and to send the
+    /// client certificate for mutual TLS. With
+    /// [`with_proxy`](super::TestServerTls::with_proxy), the server receives plain TCP
+    /// connections instead, from a proxy of the TestClient terminating TLS.
+    ///
+    /// The default implementation returns `None`, the server is called over HTTP.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use clawspec_core::test_client::{TestServer, TestServerTls};
+    /// # use std::net::TcpListener;
+    /// # struct MyServer;
+    /// impl TestServer for MyServer {
+    ///     # type Error = std::io::Error;
+    ///     # async fn launch(&self, _listener: TcpListener) -> Result<(), Self::Error> { Ok(()) }
+    ///     fn tls(&self) -> Option<TestServerTls> {
+    ///         // This server serves plain HTTP
+    ///         TestServerTls::self_signed().ok().map(TestServerTls::with_proxy)
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "tls")]
+    fn tls(&self) -> Option<super::TestServerTls> {
+        None
+    }
 }
 
 /// Configuration for test server behavior and client setup.